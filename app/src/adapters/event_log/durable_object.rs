@@ -1,25 +1,30 @@
 use gloo_utils::format::JsValueSerdeExt;
-use shared::time::{Duration, SystemTime};
 use std::{cell::RefCell, rc::Rc};
 use wasm_bindgen::JsValue;
 
-use anyhow::Result;
+use anyhow::{Result, anyhow, bail};
 use im::Vector;
-use shared::{models::events::Event, time};
+use shared::models::{
+    events::{Event, EventEnvelope, Origin},
+    fairness::{Secret, random_secret},
+    projections::GameView,
+};
+use shared::time::Clock;
 use tracing::instrument;
 use worker::{ListOptions, Storage};
 
-use crate::ports::event_log::EventLog;
+use crate::ports::event_log::{EventLog, VersionConflict};
 
 struct Inner {
     storage: Storage,
-    events: Vector<Event>,
+    events: GameView,
     hydrated: bool,
 }
 
 #[derive(Clone)]
 pub struct DurableObjectKeyValue {
     inner: Rc<RefCell<Inner>>,
+    clock: Rc<dyn Clock>,
 }
 
 // Safety: wasm32 is single threaded but axum doesn't know that
@@ -30,13 +35,14 @@ unsafe impl Send for DurableObjectKeyValue {}
 unsafe impl Sync for DurableObjectKeyValue {}
 
 impl DurableObjectKeyValue {
-    pub fn new(storage: Storage) -> DurableObjectKeyValue {
+    pub fn new(storage: Storage, clock: Rc<dyn Clock>) -> DurableObjectKeyValue {
         DurableObjectKeyValue {
             inner: Rc::new(RefCell::new(Inner {
                 storage,
-                events: Vector::new(),
+                events: GameView::default(),
                 hydrated: false,
             })),
+            clock,
         }
     }
 
@@ -59,62 +65,80 @@ impl DurableObjectKeyValue {
             .try_for_each::<_, worker::Result<_>>(&mut |value: Result<JsValue, JsValue>| {
                 let value = value?;
 
-                let event = JsValueSerdeExt::into_serde(&value).inspect_err(|err| {
-                    tracing::error!(
-                        ?err,
-                        ?value,
-                        "failed to parse value from log during hydration"
-                    );
-                })?;
+                let envelope = JsValueSerdeExt::into_serde(&value)
+                    .map_err(anyhow::Error::from)
+                    .and_then(|json| EventEnvelope::upcast(this.events.len(), json))
+                    .map_err(|err| {
+                        tracing::error!(
+                            ?err,
+                            ?value,
+                            "failed to parse value from log during hydration"
+                        );
 
-                this.events.push_back(event);
+                        err.to_string()
+                    })?;
+
+                this.events.apply(envelope.event);
 
                 Ok(())
             })?;
 
-        this.hydrated = true;
+        let secret = match this.storage.get::<Secret>("SECRET").await {
+            Ok(secret) => secret,
+            Err(_) => {
+                let secret = random_secret();
+                this.storage.put("SECRET", &secret).await?;
+                secret
+            }
+        };
 
-        Ok(())
-    }
-
-    pub async fn write_alarm(&self, alarm: Duration) -> Result<()> {
-        let wakeup = time::SystemTime::now() + alarm;
-
-        (*self.inner)
-            .borrow_mut()
-            .storage
-            .put(
-                "ALARM",
-                &wakeup.duration_since(time::UNIX_EPOCH)?.as_secs_f64(),
-            )
-            .await?;
+        this.events = std::mem::take(&mut this.events).with_secret(secret);
+        this.hydrated = true;
 
         Ok(())
     }
-
-    pub async fn read_alarm(&self) -> Option<SystemTime> {
-        let time = (*self.inner)
-            .borrow_mut()
-            .storage
-            .get::<f64>("ALARM")
-            .await
-            .ok()?;
-
-        Some(SystemTime::UNIX_EPOCH + Duration::from_secs_f64(time))
-    }
 }
 
 impl EventLog for DurableObjectKeyValue {
     #[instrument(skip_all, err)]
-    async fn push(&self, event: Event) -> Result<()> {
+    async fn append(
+        &self,
+        expected_version: usize,
+        events: Vec<Event>,
+        origin: Origin,
+    ) -> Result<()> {
         self.hydrate().await?;
 
         let mut this = (*self.inner).borrow_mut();
 
-        let key = format!("EVENT#{:0>5}", this.events.len());
+        if this.events.len() != expected_version {
+            bail!(VersionConflict {
+                expected: expected_version,
+                actual: this.events.len(),
+            });
+        }
+
+        // A single put of several keys is written atomically by the Durable Object
+        let entries = js_sys::Object::new();
 
-        this.storage.put(&key, &event).await?;
-        this.events.push_back(event);
+        for (offset, event) in events.iter().enumerate() {
+            let sequence = expected_version + offset;
+            let envelope =
+                EventEnvelope::new(sequence, origin.clone(), event.clone(), &*self.clock);
+
+            js_sys::Reflect::set(
+                &entries,
+                &JsValue::from(format!("EVENT#{:0>5}", sequence)),
+                &JsValue::from_serde(&envelope)?,
+            )
+            .map_err(|err| anyhow!("failed to build event batch: {err:?}"))?;
+        }
+
+        this.storage.put_multiple_raw(entries).await?;
+
+        for event in events {
+            this.events.apply(event);
+        }
 
         Ok(())
     }
@@ -123,13 +147,20 @@ impl EventLog for DurableObjectKeyValue {
     async fn iter(&self) -> Result<impl Iterator<Item = Event>> {
         self.hydrate().await?;
 
-        Ok((*self.inner).borrow().events.clone().into_iter())
+        Ok((*self.inner).borrow().events.events().clone().into_iter())
     }
 
     #[instrument(skip_all)]
     async fn vector(&self) -> Result<Vector<Event>> {
         self.hydrate().await?;
 
+        Ok((*self.inner).borrow().events.events().clone())
+    }
+
+    #[instrument(skip_all)]
+    async fn view(&self) -> Result<GameView> {
+        self.hydrate().await?;
+
         Ok((*self.inner).borrow().events.clone())
     }
 }
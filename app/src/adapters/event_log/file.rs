@@ -1,21 +1,30 @@
-use std::result::Result::Ok;
+use std::{result::Result::Ok, sync::Arc};
 
-use anyhow::Result;
-use shared::models::{events::Event, game_code::GameCode};
-use tokio::io::AsyncWriteExt;
+use anyhow::{Result, bail};
+use shared::models::{
+    events::{Event, EventEnvelope, Origin},
+    fairness::{Secret, random_secret},
+    game_code::GameCode,
+    projections::GameView,
+};
+use shared::time::{Clock, SystemClock};
 use tracing::instrument;
 
-use crate::ports::event_log::EventLog;
+use crate::ports::event_log::{EventLog, VersionConflict};
 
 #[derive(Debug, Clone)]
 pub struct FileEventLog {
     path: String,
+    secret_path: String,
+    clock: Arc<dyn Clock>,
 }
 
 impl FileEventLog {
-    pub fn from_game_id(game_id: GameCode) -> Self {
+    pub fn from_game_id(game_id: GameCode, clock: Arc<dyn Clock>) -> Self {
         Self {
             path: format!(".game_state/{game_id}.json"),
+            secret_path: format!(".game_state/{game_id}.secret"),
+            clock,
         }
     }
 }
@@ -24,20 +33,68 @@ impl Default for FileEventLog {
     fn default() -> Self {
         Self {
             path: ".game_state/default.json".to_string(),
+            secret_path: ".game_state/default.secret".to_string(),
+            clock: Arc::new(SystemClock),
         }
     }
 }
 
+impl FileEventLog {
+    async fn envelopes(&self) -> Result<Vec<EventEnvelope>> {
+        let data = tokio::fs::read_to_string(&self.path)
+            .await
+            .unwrap_or_else(|_| "[]".to_string());
+
+        serde_json::from_str::<Vec<serde_json::Value>>(&data)?
+            .into_iter()
+            .enumerate()
+            .map(|(sequence, value)| EventEnvelope::upcast(sequence, value))
+            .collect()
+    }
+
+    // Kept next to the event log rather than in it, as the log is sent to every client
+    async fn secret(&self) -> Result<Secret> {
+        if let Ok(data) = tokio::fs::read_to_string(&self.secret_path).await {
+            return Ok(serde_json::from_str(&data)?);
+        }
+
+        let secret = random_secret();
+        tokio::fs::write(&self.secret_path, serde_json::to_string(&secret)?).await?;
+
+        Ok(secret)
+    }
+}
+
 impl EventLog for FileEventLog {
     #[instrument(err)]
-    async fn push(&self, event: Event) -> Result<()> {
-        let mut event_log = self.vector().await?;
-        event_log.push_back(event);
+    async fn append(
+        &self,
+        expected_version: usize,
+        events: Vec<Event>,
+        origin: Origin,
+    ) -> Result<()> {
+        let mut event_log = self.envelopes().await?;
+
+        if event_log.len() != expected_version {
+            bail!(VersionConflict {
+                expected: expected_version,
+                actual: event_log.len(),
+            });
+        }
 
-        let data = serde_json::to_string(&Vec::from_iter(&event_log))?;
+        for event in events {
+            event_log.push(EventEnvelope::new(
+                event_log.len(),
+                origin.clone(),
+                event,
+                &*self.clock,
+            ));
+        }
 
-        let mut file = tokio::fs::File::create(&self.path).await?;
-        file.write_all(data.as_bytes()).await?;
+        // Renaming over the log is atomic, a crash part way through can't leave half a batch behind
+        let temporary_path = format!("{}.tmp", self.path);
+        tokio::fs::write(&temporary_path, serde_json::to_string(&event_log)?).await?;
+        tokio::fs::rename(&temporary_path, &self.path).await?;
 
         Ok(())
     }
@@ -49,12 +106,16 @@ impl EventLog for FileEventLog {
 
     #[instrument(err)]
     async fn vector(&self) -> Result<im::Vector<Event>> {
-        let data = tokio::fs::read_to_string(&self.path)
-            .await
-            .unwrap_or_else(|_| "[]".to_string());
-
-        let event_log = serde_json::from_str::<Vec<Event>>(&data)?;
+        Ok(self
+            .envelopes()
+            .await?
+            .into_iter()
+            .map(|envelope| envelope.event)
+            .collect())
+    }
 
-        Ok(event_log.into())
+    #[instrument(err)]
+    async fn view(&self) -> Result<GameView> {
+        Ok(GameView::from(self.vector().await?).with_secret(self.secret().await?))
     }
 }
@@ -1,20 +1,44 @@
 use std::sync::{Arc, RwLock};
 
-use anyhow::{anyhow, Ok, Result};
-use im::Vector;
-use shared::models::events::Event;
+use anyhow::{Ok, Result, anyhow, bail};
+use shared::models::{
+    events::{Event, Origin},
+    fairness::random_secret,
+    projections::GameView,
+};
 
-use crate::ports::event_log::EventLog;
+use crate::ports::event_log::{EventLog, VersionConflict};
 
-#[derive(Clone, Default)]
-pub struct InMemoryKV(Arc<RwLock<Vector<Event>>>);
+#[derive(Clone)]
+pub struct InMemoryKV(Arc<RwLock<GameView>>);
+
+impl Default for InMemoryKV {
+    fn default() -> Self {
+        Self(Arc::new(RwLock::new(
+            GameView::default().with_secret(random_secret()),
+        )))
+    }
+}
 
 impl EventLog for InMemoryKV {
-    async fn push(&self, event: Event) -> Result<()> {
-        self.0
-            .write()
-            .map_err(|_| anyhow!("lock poisoned"))?
-            .push_back(event);
+    async fn append(
+        &self,
+        expected_version: usize,
+        events: Vec<Event>,
+        _origin: Origin,
+    ) -> Result<()> {
+        let mut view = self.0.write().map_err(|_| anyhow!("lock poisoned"))?;
+
+        if view.len() != expected_version {
+            bail!(VersionConflict {
+                expected: expected_version,
+                actual: view.len(),
+            });
+        }
+
+        for event in events {
+            view.apply(event);
+        }
 
         Ok(())
     }
@@ -24,11 +48,21 @@ impl EventLog for InMemoryKV {
             .0
             .read()
             .map_err(|_| anyhow!("lock poisoned"))?
+            .events()
             .clone()
             .into_iter())
     }
 
     async fn vector(&self) -> Result<im::Vector<Event>> {
+        Ok(self
+            .0
+            .read()
+            .map_err(|_| anyhow!("lock poisoned"))?
+            .events()
+            .clone())
+    }
+
+    async fn view(&self) -> Result<GameView> {
         Ok(self.0.read().map_err(|_| anyhow!("lock poisoned"))?.clone())
     }
 }
@@ -1,16 +1,20 @@
-use std::rc::Rc;
+use std::{cell::Cell, rc::Rc};
 
 use anyhow::Result;
 use im::Vector;
-use shared::models::events::{Event, EventStream};
+use shared::models::commands::DisconnectPlayer;
+use shared::models::events::{Event, EventStream, Origin};
 use shared::models::process_managers::{Alarm, run_processors};
-use shared::time::SystemTime;
+use shared::models::projections::GameView;
+use shared::time::{Clock, SystemClock};
 use tower::Service;
 use tracing::instrument;
+use uuid::Uuid;
 use worker::{Env, State, WebSocket, durable_object};
 use worker_macros::send;
 
 use crate::adapters::event_log::durable_object::DurableObjectKeyValue;
+use crate::handlers::register_command::run_command;
 use crate::ports::event_log::EventLog;
 use crate::ports::game_service::GameBy;
 use crate::ports::game_state::{GameDirectory, GameState};
@@ -21,6 +25,9 @@ use crate::router::into_game_router;
 pub struct Game {
     state: Rc<State>,
     events: DurableObjectKeyValue,
+    // Whether the alarm has been re-armed from the event log since the object was created
+    armed: Rc<Cell<bool>>,
+    clock: Rc<dyn Clock>,
     // sessions: Sessions,
 }
 
@@ -34,41 +41,36 @@ unsafe impl Sync for Game {}
 #[durable_object]
 impl DurableObject for Game {
     pub fn new(state: State, _env: Env) -> Self {
-        let events = DurableObjectKeyValue::new(state.storage());
+        let clock: Rc<dyn Clock> = Rc::new(SystemClock);
+        let events = DurableObjectKeyValue::new(state.storage(), clock.clone());
         Self {
             events,
             state: Rc::new(state), // sessions,
+            armed: Rc::new(Cell::new(false)),
+            clock,
         }
     }
 
     #[instrument(name = "Game::fetch", skip_all)]
     pub async fn fetch(&mut self, req: worker::Request) -> worker::Result<worker::Response> {
+        if !self.armed.replace(true) {
+            self.rearm().await.map_err(|err| err.to_string())?;
+        }
+
         Ok(into_game_router(self.clone())
             .call(req.try_into()?)
             .await?
             .try_into()?)
     }
 
+    // Waking up too early is harmless, no timers are due so the alarm is just set again
     pub async fn alarm(&mut self) -> worker::Result<worker::Response> {
-        if let Some(alarm) = self.events.read_alarm().await {
-            let now = SystemTime::now();
-            if now < alarm {
-                let delta = alarm.duration_since(now).map_err(|err| err.to_string())?;
-
-                tracing::warn!(?delta, "woke up too early, going back to sleep");
-
-                self.set_alarm(delta).await.map_err(|err| err.to_string())?;
-
-                return worker::Response::empty();
-            }
-        };
+        let view = self.events.view().await.map_err(|err| err.to_string())?;
 
-        let events = self.events.vector().await.map_err(|err| err.to_string())?;
+        let (events, alarm) = run_processors(&view, &*self.clock).map_err(|err| err.to_string())?;
 
-        let (events, alarm) = run_processors(&events).map_err(|err| err.to_string())?;
-
-        for event in events {
-            self.push_event(event)
+        if !events.is_empty() {
+            self.append(view.len(), events, Origin::system())
                 .await
                 .map_err(|err| err.to_string())?;
         }
@@ -81,6 +83,65 @@ impl DurableObject for Game {
 
         worker::Response::empty()
     }
+
+    pub async fn websocket_close(
+        &mut self,
+        ws: WebSocket,
+        _code: usize,
+        _reason: String,
+        _was_clean: bool,
+    ) -> worker::Result<()> {
+        self.disconnect(&ws).await
+    }
+
+    pub async fn websocket_error(
+        &mut self,
+        ws: WebSocket,
+        _error: worker::Error,
+    ) -> worker::Result<()> {
+        self.disconnect(&ws).await
+    }
+}
+
+impl Game {
+    // Alarms survive restarts but can be lost, the pending timers in the event log are the source
+    // of truth
+    async fn rearm(&self) -> Result<()> {
+        let view = self.events.view().await?;
+
+        if let Some((timer, at)) = view.next_timer() {
+            tracing::info!(?timer, "re-arming alarm from the event log");
+
+            self.set_alarm(at.duration_since(self.clock.now()).unwrap_or_default())
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    // Sockets are tagged with the player's id when they're accepted, see `accept_web_socket`
+    async fn disconnect(&self, ws: &WebSocket) -> worker::Result<()> {
+        for tag in self.state.get_tags(ws) {
+            let Ok(player_id) = Uuid::parse_str(&tag) else {
+                continue;
+            };
+
+            let remaining = self
+                .release_web_socket(player_id)
+                .await
+                .map_err(|err| err.to_string())?;
+
+            if remaining > 0 {
+                continue;
+            }
+
+            run_command::<DisconnectPlayer, _>(self.clone(), player_id, ())
+                .await
+                .map_err(|err| err.to_string())?;
+        }
+
+        Ok(())
+    }
 }
 
 // DurableObject Game State Can Ignore the GameID parameter as there is one per game
@@ -93,33 +154,55 @@ impl GameState for Game {
     }
 
     #[send]
-    async fn push_event(&self, event: Event) -> Result<()> {
-        self.events.push(event.clone()).await?;
+    async fn view(&self) -> Result<GameView> {
+        Ok(self.events.view().await?)
+    }
+
+    #[send]
+    async fn append(
+        &self,
+        expected_version: usize,
+        events: Vec<Event>,
+        origin: Origin,
+    ) -> Result<()> {
+        self.events
+            .append(expected_version, events.clone(), origin)
+            .await?;
 
-        for ws in self.state.get_websockets() {
-            ws.send(&EventStream::Event(event.clone()))?;
+        for event in events {
+            for ws in self.state.get_websockets() {
+                ws.send(&EventStream::Event(event.clone()))?;
+            }
         }
 
         Ok(())
     }
 
-    async fn accept_web_socket(&self, ws: WebSocket) -> Result<()> {
-        self.state.accept_web_socket(&ws);
+    async fn accept_web_socket(&self, ws: WebSocket, player_id: Uuid) -> Result<()> {
+        self.state
+            .accept_websocket_with_tags(&ws, &[&player_id.to_string()]);
 
         Ok(())
     }
 
-    #[send]
-    async fn set_alarm(&self, duration: std::time::Duration) -> Result<()> {
-        self.events.write_alarm(duration.clone()).await?;
-
-        let storage = self.state.storage();
+    // The runtime keeps track of the sockets, the one that just closed is no longer open
+    async fn release_web_socket(&self, player_id: Uuid) -> Result<usize> {
+        Ok(self
+            .state
+            .get_websockets_with_tag(&player_id.to_string())
+            .iter()
+            .filter(|ws| ws.as_ref().ready_state() == web_sys::WebSocket::OPEN)
+            .count())
+    }
 
-        if let Some(time) = storage.get_alarm().await? {
-            tracing::warn!(?time, "overriding timer");
-        };
+    fn clock(&self) -> &dyn Clock {
+        &*self.clock
+    }
 
-        storage.set_alarm(duration).await?;
+    #[send]
+    async fn set_alarm(&self, duration: std::time::Duration) -> Result<()> {
+        // Always for the earliest pending timer, so replacing the current alarm is expected
+        self.state.storage().set_alarm(duration).await?;
 
         Ok(())
     }
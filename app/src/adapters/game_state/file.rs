@@ -2,11 +2,17 @@ use std::{collections::HashMap, sync::Arc, time::Duration};
 use tokio::{sync::Mutex, task::JoinHandle};
 
 use anyhow::Result;
-use axum::extract::ws::WebSocket;
-use shared::models::{
-    events::{Event, EventStream},
-    game_code::GameCode,
+use axum::extract::ws::{self, Message};
+use futures::{SinkExt, stream::SplitSink};
+use shared::{
+    models::{
+        events::{Event, EventStream, Origin},
+        game_code::GameCode,
+        projections::GameView,
+    },
+    time::{Clock, SystemClock},
 };
+use uuid::Uuid;
 
 use crate::{
     adapters::event_log::file::FileEventLog,
@@ -17,34 +23,49 @@ use crate::{
     },
 };
 
+// Only the sending half is kept, `on_connect` reads from the other half to notice it closing
+pub type WebSocket = SplitSink<ws::WebSocket, Message>;
+
 struct InnerGame {
     events: FileEventLog,
     sockets: Vec<WebSocket>,
+    // How many sockets each player has open
+    connections: HashMap<Uuid, usize>,
     alarm: Option<JoinHandle<()>>,
 }
 
 #[derive(Clone)]
 pub struct Game {
     inner: Arc<Mutex<InnerGame>>,
+    clock: Arc<dyn Clock>,
 }
 
 impl InnerGame {
-    async fn push_event(&mut self, event: Event) -> Result<()> {
-        self.events.push(event.clone()).await?;
-
-        for mut socket in std::mem::take(&mut self.sockets) {
-            let message = serde_json::to_string(&EventStream::Event(event.clone()))?;
-
-            if let Err(err) = socket.send(message.into()).await {
-                tracing::warn!(
-                    ?err,
-                    "error forwarding event to client sockets, removing socket"
-                );
+    async fn append(
+        &mut self,
+        expected_version: usize,
+        events: Vec<Event>,
+        origin: Origin,
+    ) -> Result<()> {
+        self.events
+            .append(expected_version, events.clone(), origin)
+            .await?;
+
+        for event in events {
+            let message = serde_json::to_string(&EventStream::Event(event))?;
+
+            for mut socket in std::mem::take(&mut self.sockets) {
+                if let Err(err) = socket.send(message.clone().into()).await {
+                    tracing::warn!(
+                        ?err,
+                        "error forwarding event to client sockets, removing socket"
+                    );
+
+                    continue;
+                }
 
-                continue;
+                self.sockets.push(socket);
             }
-
-            self.sockets.push(socket);
         }
 
         Ok(())
@@ -52,20 +73,29 @@ impl InnerGame {
 }
 
 impl Game {
-    fn from_game_code(game_code: GameCode) -> Self {
+    fn from_game_code(game_code: GameCode, clock: Arc<dyn Clock>) -> Self {
         Self {
             inner: Arc::new(Mutex::new(InnerGame {
-                events: FileEventLog::from_game_id(game_code),
+                events: FileEventLog::from_game_id(game_code, clock.clone()),
                 sockets: vec![],
+                connections: HashMap::new(),
                 alarm: None,
             })),
+            clock,
         }
     }
 }
 
-#[derive(Clone, Default)]
+#[derive(Clone)]
 pub struct FileGameDirectory {
     inner: Arc<Mutex<HashMap<GameCode, Game>>>,
+    clock: Arc<dyn Clock>,
+}
+
+impl Default for FileGameDirectory {
+    fn default() -> Self {
+        Self::with_clock(Arc::new(SystemClock))
+    }
 }
 
 impl GameDirectory for FileGameDirectory {
@@ -82,18 +112,64 @@ impl GameDirectory for FileGameDirectory {
             .lock()
             .await
             .entry(game_id)
-            .or_insert_with(|| Game::from_game_code(game_id))
+            .or_insert_with(|| Game::from_game_code(game_id, self.clock.clone()))
             .clone()
     }
 }
 
+impl FileGameDirectory {
+    pub fn with_clock(clock: Arc<dyn Clock>) -> Self {
+        Self {
+            inner: Default::default(),
+            clock,
+        }
+    }
+
+    // Alarms only live as long as the process, so on startup every game with a pending timer in its
+    // event log has its alarm set again
+    pub async fn rearm(&self) -> Result<()> {
+        let mut entries = match tokio::fs::read_dir(".game_state").await {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+            Err(err) => return Err(err.into()),
+        };
+
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+
+            if path.extension().is_none_or(|ext| ext != "json") {
+                continue;
+            }
+
+            let Some(game_code) = path
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .and_then(|stem| GameCode::try_from(stem).ok())
+            else {
+                continue;
+            };
+
+            let game = self.get(GameBy::Code(game_code)).await;
+
+            if let Some((timer, at)) = game.view().await?.next_timer() {
+                tracing::info!(%game_code, ?timer, "re-arming timer from the event log");
+
+                game.set_alarm(at.duration_since(self.clock.now()).unwrap_or_default())
+                    .await?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
 // Error
 
 // A separate module is required to solve problems with the compiler not knowing if the hidden type
 // of the opaque type satisfies the auto trait bounds.
 mod set_alarm {
     use anyhow::Result;
-    use shared::models::process_managers::run_processors;
+    use shared::models::{events::Origin, process_managers::run_processors};
     use std::{future::Future, sync::Arc, time::Duration};
 
     use crate::ports::{event_log::EventLog, game_state::GameState};
@@ -108,6 +184,7 @@ mod set_alarm {
                 handle.abort();
             }
 
+            let clock = this.clock.clone();
             let this = Arc::downgrade(&this.inner);
 
             game.alarm = Some(tokio::spawn(async move {
@@ -121,17 +198,20 @@ mod set_alarm {
 
                     let mut game = this.lock().await;
 
-                    let (new_events, alarm) = run_processors(&game.events.vector().await?)?;
+                    // Holding the lock means nothing else can append between reading and writing
+                    let view = game.events.view().await?;
+                    let (new_events, alarm) = run_processors(&view, &*clock)?;
 
-                    for event in new_events {
-                        game.push_event(event).await?;
+                    if !new_events.is_empty() {
+                        game.append(view.len(), new_events, Origin::system())
+                            .await?;
                     }
 
                     game.alarm = None;
                     drop(game);
 
                     if let Some(alarm) = alarm {
-                        Game { inner: this }.set_alarm(alarm.0).await?;
+                        Game { inner: this, clock }.set_alarm(alarm.0).await?;
                     }
                 };
 
@@ -152,20 +232,54 @@ impl GameState for Game {
         self.inner.lock().await.events.vector().await
     }
 
-    async fn push_event(&self, event: Event) -> Result<()> {
+    async fn view(&self) -> Result<GameView> {
+        self.inner.lock().await.events.view().await
+    }
+
+    async fn append(
+        &self,
+        expected_version: usize,
+        events: Vec<Event>,
+        origin: Origin,
+    ) -> Result<()> {
         let mut lock_guard = self.inner.lock().await;
 
-        lock_guard.push_event(event).await?;
+        lock_guard.append(expected_version, events, origin).await?;
 
         Ok(())
     }
 
-    async fn accept_web_socket(&self, ws: WebSocket) -> Result<()> {
-        self.inner.lock().await.sockets.push(ws);
+    // The player's presence is tracked by `on_connect`, which holds the other half of the socket
+    async fn accept_web_socket(&self, ws: WebSocket, player_id: Uuid) -> Result<()> {
+        let mut game = self.inner.lock().await;
+
+        game.sockets.push(ws);
+        *game.connections.entry(player_id).or_default() += 1;
 
         Ok(())
     }
 
+    async fn release_web_socket(&self, player_id: Uuid) -> Result<usize> {
+        let mut game = self.inner.lock().await;
+
+        let Some(count) = game.connections.get_mut(&player_id) else {
+            return Ok(0);
+        };
+
+        *count = count.saturating_sub(1);
+        let remaining = *count;
+
+        if remaining == 0 {
+            game.connections.remove(&player_id);
+        }
+
+        Ok(remaining)
+    }
+
+    fn clock(&self) -> &dyn Clock {
+        &*self.clock
+    }
+
     async fn set_alarm(&self, duration: Duration) -> Result<()> {
         set_alarm::set_alarm(&self, duration).await
     }
@@ -14,8 +14,13 @@ pub async fn main() {
 
     tracing_subscriber::fmt().pretty().init();
 
+    shared::models::roster::install_from_env("game/assets").expect("failed to load monster roster");
+
+    let games = FileGameDirectory::default();
+    games.rearm().await.expect("failed to re-arm game timers");
+
     let app = into_outer_router(AxumGameService {
-        router: into_game_router(FileGameDirectory::default()),
+        router: into_game_router(games),
     })
     .layer(
         TraceLayer::new_for_http().make_span_with(DefaultMakeSpan::default().include_headers(true)),
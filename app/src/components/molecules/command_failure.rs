@@ -0,0 +1,14 @@
+use leptos::prelude::*;
+
+use crate::server_fns::{CommandResult, error_message};
+
+// Explains why the last attempt at a command failed, if it did
+#[component]
+pub fn command_failure(#[prop(into)] result: Signal<Option<CommandResult>>) -> impl IntoView {
+    move || {
+        result
+            .get()
+            .and_then(Result::err)
+            .map(|err| view! { <p class="error">{error_message(&err)}</p> })
+    }
+}
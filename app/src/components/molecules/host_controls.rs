@@ -0,0 +1,88 @@
+use leptos::prelude::*;
+use shared::models::commands::{
+    EndGame, ExtendTimer, PauseGame, ResumeGame, StartRace, VoidRound, extend_timer,
+};
+
+use crate::{
+    components::molecules::CommandFailure,
+    server_fns::server_fn,
+    utils::{use_events, use_game_id, use_player_id},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum HostAction {
+    Pause,
+    Resume,
+    ExtendTimer,
+    StartRace,
+    VoidRound,
+    EndGame,
+}
+
+// The buttons the person running the TV uses to keep the game moving, only the buttons that make
+// sense for the current phase are shown
+#[component]
+pub fn host_controls() -> impl IntoView {
+    let events = use_events();
+    let game_id = use_game_id();
+    let session_id = use_player_id();
+
+    let action = Action::new(move |action: &HostAction| {
+        let action = *action;
+
+        async move {
+            match action {
+                HostAction::Pause => server_fn::<PauseGame>(game_id, &()).await,
+                HostAction::Resume => server_fn::<ResumeGame>(game_id, &()).await,
+                HostAction::ExtendTimer => {
+                    server_fn::<ExtendTimer>(game_id, &extend_timer::Input { seconds: 30 }).await
+                }
+                HostAction::StartRace => server_fn::<StartRace>(game_id, &()).await,
+                HostAction::VoidRound => server_fn::<VoidRound>(game_id, &()).await,
+                HostAction::EndGame => server_fn::<EndGame>(game_id, &()).await,
+            }
+        }
+    });
+
+    let is_host = move || events().is_host(session_id);
+    let betting = move || events().currently_betting().is_some();
+    let racing = move || events().currently_racing().is_some();
+    let paused = move || events().paused();
+    let timed = move || betting() && events().pregame_deadline().is_some();
+    let finished = move || events().game_finished();
+
+    let button = move |label: &'static str, host_action: HostAction| {
+        view! {
+            <button
+                class="button"
+                on:click=move |_| {
+                    action.dispatch(host_action);
+                }
+            >
+                {label}
+            </button>
+        }
+    };
+
+    view! {
+        <Show when=move || is_host() && !finished()>
+            <div class="host-controls">
+                <Show when=betting>
+                    <Show
+                        when=paused
+                        fallback=move || button("Pause", HostAction::Pause)
+                    >
+                        {button("Resume", HostAction::Resume)}
+                    </Show>
+                    <Show when=timed>{button("+30s", HostAction::ExtendTimer)}</Show>
+                    {button("Start Race", HostAction::StartRace)}
+                </Show>
+                <Show when=move || betting() || racing()>
+                    {button("Void Round", HostAction::VoidRound)}
+                </Show>
+                {button("End Game", HostAction::EndGame)}
+                <CommandFailure result=action.value() />
+            </div>
+        </Show>
+    }
+}
@@ -1,2 +1,11 @@
 mod text_input;
 pub use text_input::*;
+
+mod number_input;
+pub use number_input::*;
+
+mod command_failure;
+pub use command_failure::*;
+
+mod host_controls;
+pub use host_controls::*;
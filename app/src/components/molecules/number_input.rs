@@ -0,0 +1,18 @@
+use leptos::prelude::*;
+
+#[component]
+pub fn number_input(
+    id: &'static str,
+    name: &'static str,
+    value: String,
+    min: &'static str,
+    max: &'static str,
+    #[prop(default = "1")] step: &'static str,
+) -> impl IntoView {
+    view! {
+        <div class="input full-width">
+            <label for=id>{name}</label>
+            <input id=id value=value type="number" name=id required min=min max=max step=step />
+        </div>
+    }
+}
@@ -2,38 +2,50 @@ use std::collections::HashMap;
 
 use anyhow::anyhow;
 use axum::{
-    async_trait,
+    Json, RequestPartsExt, async_trait,
     extract::{FromRequestParts, Path},
-    http::request::Parts,
-    RequestPartsExt,
+    http::{StatusCode, request::Parts},
+    response::{IntoResponse, Response},
 };
 use axum_extra::extract::CookieJar;
 use serde::Deserialize;
-use shared::models::game_code;
+use shared::models::{commands::CommandError, game_code, session::SessionToken};
 use tracing::instrument;
 use uuid::Uuid;
 
 use crate::ports::{
     game_service::{GameBy, InternalServerError},
-    game_state::GameDirectory,
+    game_state::{GameDirectory, GameState},
 };
 
+pub const SESSION_COOKIE: &str = "session_token";
+// Before the token was split from the player id the cookie held both, and everyone in the game could
+// see it. It's never trusted, browsers that still have it are given a new token, see `legacy_player_id`.
+pub const LEGACY_SESSION_COOKIE: &str = "session_id";
+// Lets the client know which player it is, the server never trusts it
+pub const PLAYER_COOKIE: &str = "player_id";
+
+// Nil and malformed tokens are treated as missing
+pub fn session_token(jar: &CookieJar) -> Option<SessionToken> {
+    jar.get(SESSION_COOKIE)?.value().parse().ok()
+}
+
+// The public id of the player making the request, derived from the secret token in their cookies.
+// Commands and events only ever see this, never the token.
 #[derive(Debug, Copy, Clone)]
-pub struct SessionID(pub Uuid);
+pub struct PlayerID(pub Uuid);
 
 #[async_trait]
-impl<S: Send + Sync> FromRequestParts<S> for SessionID {
-    type Rejection = &'static str;
+impl<S: Send + Sync> FromRequestParts<S> for PlayerID {
+    type Rejection = (StatusCode, &'static str);
 
     #[instrument(skip_all)]
     async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
         let jar = CookieJar::from_request_parts(parts, state).await.unwrap();
 
-        jar.get("session_id")
-            .ok_or("missing session_id cookie")
-            .map(|it| it.value())
-            .and_then(|it| Uuid::parse_str(it).map_err(|_| "Unable to parse session_id"))
-            .map(SessionID)
+        session_token(&jar)
+            .map(|token| PlayerID(token.player_id()))
+            .ok_or((StatusCode::UNAUTHORIZED, "missing or invalid session token"))
     }
 }
 
@@ -98,3 +110,67 @@ impl<G: GameDirectory> FromRequestParts<G> for Game<G> {
         }
     }
 }
+
+// The game, but only for the session that created it. Used for the routes that expose everyone's
+// hands and session ids or poke at the game's internals.
+#[derive(Clone)]
+pub struct Host<G: GameDirectory>(pub <G as GameDirectory>::GameState);
+
+#[async_trait]
+impl<G: GameDirectory> FromRequestParts<G> for Host<G> {
+    type Rejection = Response;
+
+    #[instrument(skip_all, fields(uri = ?parts.uri))]
+    async fn from_request_parts(parts: &mut Parts, state: &G) -> Result<Self, Self::Rejection> {
+        let forbidden = || {
+            (
+                StatusCode::FORBIDDEN,
+                Json(CommandError::forbidden("only the host can do that")),
+            )
+                .into_response()
+        };
+
+        let Game(game) = Game::<G>::from_request_parts(parts, state)
+            .await
+            .map_err(IntoResponse::into_response)?;
+
+        let Ok(PlayerID(player_id)) = PlayerID::from_request_parts(parts, state).await else {
+            return Err(forbidden());
+        };
+
+        let view = game
+            .view()
+            .await
+            .map_err(|err| InternalServerError::from(err).into_response())?;
+
+        if !view.is_host(player_id) {
+            tracing::warn!(%player_id, "non host tried to use a host only route");
+            return Err(forbidden());
+        }
+
+        Ok(Host(game))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use axum_extra::extract::{CookieJar, cookie::Cookie};
+    use shared::models::session::legacy_player_id;
+    use uuid::Uuid;
+
+    use super::{LEGACY_SESSION_COOKIE, SESSION_COOKIE, session_token};
+
+    #[test]
+    fn replayed_legacy_cookies_are_refused() {
+        // Legacy ids were sent to everyone in the game
+        let legacy = Uuid::new_v4();
+
+        let jar = CookieJar::new().add(Cookie::new(LEGACY_SESSION_COOKIE, legacy.to_string()));
+        assert_eq!(session_token(&jar), None);
+
+        let jar = CookieJar::new().add(Cookie::new(SESSION_COOKIE, legacy.to_string()));
+        assert!(
+            session_token(&jar).is_some_and(|token| token.player_id() != legacy_player_id(legacy))
+        );
+    }
+}
@@ -1,11 +1,12 @@
 use axum::{
+    Form,
     extract::{Request, State},
     http::header::HeaderMap,
     response::Response,
 };
 use shared::models::{
     commands::{self, API},
-    events::{Payout, Settings},
+    events::Settings,
     game_code::generate_game_code,
 };
 
@@ -15,7 +16,7 @@ use crate::ports::game_service::{GameBy, GameRequest, GameService, InternalServe
 pub async fn create_game<G: GameService>(
     State(mut game_service): State<G>,
     headers: HeaderMap,
-    // request: Request,
+    Form(settings): Form<Settings>,
 ) -> Result<Response, InternalServerError> {
     let game_code = generate_game_code();
 
@@ -33,7 +34,7 @@ pub async fn create_game<G: GameService>(
     let req = do_req.body(
         serde_json::to_string(&commands::create_game::Input {
             code: game_code,
-            settings: Settings::default(),
+            settings,
         })?
         .into(),
     )?;
@@ -45,6 +46,11 @@ pub async fn create_game<G: GameService>(
         })
         .await?;
 
+    // Invalid settings are refused with the reason, pass it on rather than hiding it behind a 500
+    if response.status().is_client_error() {
+        return Ok(response);
+    }
+
     if response.status() != 200 {
         let err = anyhow::anyhow!("non-200 received from game-service");
         tracing::error!(status = ?response.status(), ?err);
@@ -61,3 +67,78 @@ pub async fn create_game<G: GameService>(
 
     Ok(response)
 }
+
+#[cfg(test)]
+mod test {
+    use std::{
+        future::Future,
+        pin::Pin,
+        sync::{Arc, Mutex},
+        task::{Context, Poll},
+    };
+
+    use axum::{Router, body::Body, extract::Request, response::Response, routing::post};
+    use shared::models::commands::create_game::Input;
+    use tower::{Service, ServiceExt};
+
+    use super::create_game;
+    use crate::ports::game_service::{GameRequest, InternalServerError};
+
+    // Stands in for the game's Durable Object, remembering what it was asked to create
+    #[derive(Clone, Default)]
+    struct RecordedGames(Arc<Mutex<Vec<Input>>>);
+
+    impl Service<GameRequest> for RecordedGames {
+        type Response = Response;
+        type Error = InternalServerError;
+        type Future = Pin<Box<dyn Future<Output = Result<Response, InternalServerError>> + Send>>;
+
+        fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, GameRequest { req, .. }: GameRequest) -> Self::Future {
+            let games = self.0.clone();
+
+            Box::pin(async move {
+                let body = axum::body::to_bytes(req.into_body(), usize::MAX).await?;
+                games.lock().unwrap().push(serde_json::from_slice(&body)?);
+
+                Ok::<_, InternalServerError>(Response::new(Body::empty()))
+            })
+        }
+    }
+
+    // What the host's browser sends from the new game form, an unchecked box isn't sent at all
+    const FORM: &str = "payout=Odds&rounds=5&field_size=3&max_players=15&starting_balance=1000\
+        &starting_cards=3&hand_limit=5&card_price=100&interest_rate=0.051&pre_game_timeout=90\
+        &summary_duration=15&afk_timeout=60";
+
+    fn submit(games: &RecordedGames, body: String) -> Response {
+        let router = Router::new()
+            .route("/api/create_game", post(create_game::<RecordedGames>))
+            .with_state(games.clone());
+
+        let request = Request::post("/api/create_game")
+            .header("Content-Type", "application/x-www-form-urlencoded")
+            .body(Body::from(body))
+            .unwrap();
+
+        futures::executor::block_on(router.oneshot(request)).unwrap()
+    }
+
+    #[test]
+    fn the_new_game_form_creates_a_game() {
+        let games = RecordedGames::default();
+
+        assert_eq!(submit(&games, FORM.to_string()).status(), 303);
+        assert_eq!(
+            submit(&games, format!("{FORM}&late_join=true")).status(),
+            303
+        );
+
+        let games = games.0.lock().unwrap();
+        assert!(!games[0].settings.late_join);
+        assert!(games[1].settings.late_join);
+    }
+}
@@ -3,15 +3,16 @@ use im::Vector;
 use shared::models::events::Event;
 
 use crate::{
-    extractors::Game,
+    extractors::Host,
     ports::{
         game_service::InternalServerError,
         game_state::{GameDirectory, GameState},
     },
 };
 
+// Every player's hand and session id is in the log, so it's only for the host
 pub async fn event_log<G: GameDirectory>(
-    Game(game): Game<G>,
+    Host(game): Host<G>,
 ) -> Result<Json<Vector<Event>>, InternalServerError> {
     Ok(Json(game.events().await?))
 }
@@ -5,18 +5,23 @@ use axum::{
     response::Response,
     Form,
 };
-use shared::models::commands::{self, CommandHandler, JoinGame, API};
+use shared::models::commands::{CommandHandler, HasGameCode, API};
 
+// Joining as a player or as a spectator both come from the join form, which redirects to the game
 #[tracing::instrument(skip_all, err)]
-pub async fn join_game<G: GameService>(
+pub async fn join_game<C, G>(
     State(mut game_service): State<G>,
     headers: HeaderMap,
-    Form(join_game): Form<<JoinGame as CommandHandler>::Input>,
-) -> Result<Response, InternalServerError> {
-    let mut req = Request::post(format!(
-        "https://DURABLE_OBJECT{}",
-        JoinGame::url(join_game.code)
-    ));
+    Form(join_game): Form<C::Input>,
+) -> Result<Response, InternalServerError>
+where
+    C: CommandHandler + API,
+    C::Input: HasGameCode,
+    G: GameService,
+{
+    let code = join_game.game_code();
+
+    let mut req = Request::post(format!("https://DURABLE_OBJECT{}", C::url(code)));
 
     *req.headers_mut().unwrap() = headers;
     req.headers_mut()
@@ -27,7 +32,7 @@ pub async fn join_game<G: GameService>(
 
     let response = game_service
         .call(GameRequest {
-            by: GameBy::Code(join_game.code),
+            by: GameBy::Code(code),
             req,
         })
         .await?;
@@ -38,10 +43,7 @@ pub async fn join_game<G: GameService>(
     }
 
     let response = Response::builder()
-        .header(
-            "Location",
-            commands::JoinGame::redirect(join_game.code).unwrap(),
-        )
+        .header("Location", C::redirect(code).unwrap())
         .status(303)
         .body("".into())?;
 
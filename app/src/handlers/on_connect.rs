@@ -1,11 +1,12 @@
 #[cfg(target_arch = "wasm32")]
 mod wasm {
     use axum::response::Response;
-    use shared::models::events::EventStream;
+    use shared::models::{commands::ConnectPlayer, events::EventStream};
     use worker::WebSocketPair;
 
     use crate::{
-        extractors::{Game, SessionID},
+        extractors::{Game, PlayerID},
+        handlers::register_command::run_command,
         ports::game_state::{GameDirectory, GameState},
     };
 
@@ -16,15 +17,20 @@ mod wasm {
     // #[instrument(skip_all, err)]
     pub async fn on_connect<G: GameDirectory<WebSocket = WebSocket>>(
         Game(game_state): Game<G>,
-        SessionID(_session_id): SessionID,
+        PlayerID(player_id): PlayerID,
     ) -> Result<Response, InternalServerError> {
         let pair = WebSocketPair::new()?;
 
-        game_state.accept_web_socket(pair.server.clone()).await?;
+        game_state
+            .accept_web_socket(pair.server.clone(), player_id)
+            .await?;
 
         let events = EventStream::Events(game_state.events().await?.into_iter().collect());
         pair.server.send(&events)?;
 
+        // Disconnects are picked up by `Game::websocket_close`
+        run_command::<ConnectPlayer, _>(game_state, player_id, ()).await?;
+
         let response = Response::builder()
             .status(101)
             .extension(pair.client)
@@ -40,36 +46,60 @@ pub use wasm::*;
 #[cfg(not(target_arch = "wasm32"))]
 mod native {
     use axum::response::{IntoResponse, Response};
-    use shared::models::events::EventStream;
+    use futures::{SinkExt, StreamExt};
+    use shared::models::{
+        commands::{ConnectPlayer, DisconnectPlayer},
+        events::EventStream,
+    };
     use tracing::instrument;
 
     use crate::ports::game_service::InternalServerError;
     use crate::{
-        extractors::Game,
+        extractors::{Game, PlayerID},
+        handlers::register_command::run_command,
         ports::game_state::{GameDirectory, GameState},
     };
 
-    pub type WebSocket = axum::extract::ws::WebSocket;
+    pub use crate::adapters::game_state::file::WebSocket;
 
     #[instrument(skip_all, err)]
     pub async fn on_connect<G: GameDirectory<WebSocket = WebSocket>>(
         ws: axum::extract::WebSocketUpgrade,
+        PlayerID(player_id): PlayerID,
         Game(game_state): Game<G>,
     ) -> Result<Response, InternalServerError> {
         Ok(ws
-            .on_upgrade(move |mut ws| async move {
+            .on_upgrade(move |ws| async move {
+                let (mut sink, mut stream) = ws.split();
+
                 let result: anyhow::Result<()> = try {
                     let events =
                         EventStream::Events(game_state.events().await?.into_iter().collect());
 
                     let message = serde_json::to_string(&events)?;
-                    ws.send(message.into()).await?;
+                    sink.send(message.into()).await?;
+
+                    game_state.accept_web_socket(sink, player_id).await?;
+
+                    run_command::<ConnectPlayer, _>(game_state.clone(), player_id, ()).await?;
+                };
 
-                    game_state.accept_web_socket(ws).await?;
+                if let Err(err) = result {
+                    tracing::error!(?err, "error upgrading websocket");
+                    return;
+                }
+
+                // Clients never send anything, the stream only ends once the socket is closed
+                while let Some(Ok(_)) = stream.next().await {}
+
+                let result: anyhow::Result<()> = try {
+                    if game_state.release_web_socket(player_id).await? == 0 {
+                        run_command::<DisconnectPlayer, _>(game_state, player_id, ()).await?;
+                    }
                 };
 
                 if let Err(err) = result {
-                    tracing::error!(?err, "error upgrading websocket")
+                    tracing::error!(?err, "error recording disconnect");
                 }
             })
             .into_response())
@@ -1,52 +1,143 @@
-use std::any::type_name;
+use std::{any::type_name, time::Duration};
 
 use crate::{
-    extractors::{Game, SessionID},
-    ports::game_state::{GameDirectory, GameState},
+    extractors::{Game, PlayerID},
+    ports::{
+        event_log::VersionConflict,
+        game_state::{GameDirectory, GameState},
+    },
 };
 use axum::{
     Json,
+    http::StatusCode,
     response::{IntoResponse, Response},
     routing::post,
 };
 use shared::models::{
-    commands::{API, CommandHandler},
+    commands::{API, CommandError, CommandHandler},
+    events::Origin,
     process_managers::run_processors,
 };
+use uuid::Uuid;
 
 use crate::ports::game_service::{GameService, InternalServerError};
 
+// How many times a command is re-run against a fresh view of the game when another writer gets in
+// first
+const MAX_ATTEMPTS: usize = 5;
+
 pub trait RegisterCommandExt {
     fn register_command_handler<C: CommandHandler + API + 'static>(self) -> Self;
 }
 
 #[tracing::instrument(skip_all, fields(session_id, input, command = type_name::<C>()), err)]
 pub async fn command_handler<C: CommandHandler, G: GameDirectory>(
-    SessionID(session_id): SessionID,
+    PlayerID(session_id): PlayerID,
     Game(game): Game<G>,
     Json(input): Json<C::Input>,
 ) -> Result<Response, InternalServerError> {
-    let mut events = game.events().await?;
+    Ok(run_command::<C, _>(game, session_id, input).await?)
+}
 
-    let new_events = C::handle(session_id, &events, input)?;
-    for event in new_events {
-        game.push_event(event.clone()).await?;
-        events.push_back(event);
-    }
+// Handles a command on behalf of a player and runs the process managers over the result. Also used
+// by the server itself for commands that don't come from a route, like presence changes.
+pub async fn run_command<C: CommandHandler, S: GameState>(
+    game: S,
+    session_id: Uuid,
+    input: C::Input,
+) -> anyhow::Result<Response> {
+    let command = type_name::<C>().rsplit("::").next().unwrap_or_default();
 
-    let (new_events, alarm) = run_processors(&events)?;
+    let mut attempt = 0;
 
-    for event in new_events {
-        game.push_event(event).await?;
-    }
+    // The command is validated against a specific version of the game, if anything else is
+    // appended before its events are written it has to be validated again
+    let mut view = loop {
+        attempt += 1;
+
+        let mut view = game.view().await?;
+
+        // Refused commands are the player's mistake, not the server's
+        let new_events = match C::handle(session_id, &view, input.clone(), game.clock()) {
+            Ok(new_events) => new_events,
+            Err(err) => match err.downcast::<CommandError>() {
+                Ok(refusal) => return Ok(refused(refusal)),
+                Err(err) => return Err(err),
+            },
+        };
+
+        match game
+            .append(
+                view.len(),
+                new_events.clone(),
+                Origin::new(session_id, command),
+            )
+            .await
+        {
+            Ok(()) => {
+                for event in new_events {
+                    view.apply(event);
+                }
+
+                break view;
+            }
+            Err(err) if err.is::<VersionConflict>() && attempt < MAX_ATTEMPTS => {
+                tracing::info!(%err, attempt, "command raced another writer, retrying");
+            }
+            Err(err) if err.is::<VersionConflict>() => {
+                return Ok(refused(CommandError::conflict(
+                    "the game is busy, please try again",
+                )));
+            }
+            Err(err) => return Err(err),
+        }
+    };
+
+    let mut attempt = 0;
+
+    // The command's events are already written, so the process managers are re-run against whatever
+    // got in first rather than giving up on them
+    let alarm = loop {
+        attempt += 1;
+
+        let (new_events, alarm) = run_processors(&view, game.clock())?;
+
+        if new_events.is_empty() {
+            break alarm.map(|alarm| alarm.0);
+        }
+
+        match game.append(view.len(), new_events, Origin::system()).await {
+            Ok(()) => break alarm.map(|alarm| alarm.0),
+            Err(err) if err.is::<VersionConflict>() && attempt < MAX_ATTEMPTS => {
+                tracing::info!(%err, attempt, "process managers raced another writer, retrying");
+
+                view = game.view().await?;
+            }
+            // The alarm runs the process managers as well, so they get another go once it's quieter
+            Err(err) if err.is::<VersionConflict>() => {
+                tracing::warn!(%err, "process managers kept racing other writers, re-arming alarm");
+
+                break Some(Duration::ZERO);
+            }
+            Err(err) => return Err(err),
+        }
+    };
 
     if let Some(alarm) = alarm {
-        game.set_alarm(alarm.0).await?;
+        game.set_alarm(alarm).await?;
     }
 
     Ok(().into_response())
 }
 
+fn refused(refusal: CommandError) -> Response {
+    tracing::info!(%refusal, "command refused");
+
+    let status = StatusCode::from_u16(refusal.status_code()).unwrap_or(StatusCode::BAD_REQUEST);
+
+    (status, Json(refusal)).into_response()
+}
+
 impl<G: GameDirectory> RegisterCommandExt for axum::Router<G> {
     fn register_command_handler<C: CommandHandler + API + 'static>(self) -> Self {
         self.route(&C::url(":code"), post(command_handler::<C, G>))
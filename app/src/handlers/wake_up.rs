@@ -1,5 +1,5 @@
 use crate::{
-    extractors::Game,
+    extractors::Host,
     ports::{
         game_service::InternalServerError,
         game_state::{GameDirectory, GameState},
@@ -9,9 +9,9 @@ use shared::time::Duration;
 
 /*
    wake_up is a semi private endpoint what is used to wake up the game if the game soft locks
-   due to missing a wakeup alarm
+   due to missing a wakeup alarm, only the host can use it
 */
-pub async fn wake_up<G: GameDirectory>(Game(game): Game<G>) -> Result<(), InternalServerError> {
+pub async fn wake_up<G: GameDirectory>(Host(game): Host<G>) -> Result<(), InternalServerError> {
     game.set_alarm(Duration::from_secs(0)).await?;
 
     Ok(())
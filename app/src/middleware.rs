@@ -1,37 +1,49 @@
 use axum::{extract::Request, middleware::Next, response::Response};
 use axum_extra::extract::CookieJar;
 use cookie::CookieBuilder;
-use uuid::Uuid;
+use shared::models::session::SessionToken;
 
-use crate::extractors::SessionID;
+use crate::extractors::{
+    LEGACY_SESSION_COOKIE, PLAYER_COOKIE, PlayerID, SESSION_COOKIE, session_token,
+};
 
 #[tracing::instrument(skip_all)]
 pub async fn session_middleware(
-    session_id: Option<SessionID>,
     cookie_jar: CookieJar,
     mut request: Request,
     next: Next,
 ) -> (CookieJar, Response) {
     let mut cookie_jar = cookie_jar;
 
-    let session_id = match session_id {
-        Some(session_id) => session_id,
-        None => {
-            let session_id = SessionID(Uuid::new_v4());
-            cookie_jar = cookie_jar.add(
-                CookieBuilder::new("session_id", session_id.0.to_string())
+    let token = session_token(&cookie_jar).unwrap_or_else(SessionToken::random);
+    let player_id = token.player_id();
+
+    // Anything that isn't a valid token is replaced with a new one, and the old cookie is dropped
+    if cookie_jar.get(SESSION_COOKIE).map(|cookie| cookie.value()) != Some(&token.to_string()) {
+        cookie_jar = cookie_jar
+            .add(
+                CookieBuilder::new(SESSION_COOKIE, token.to_string())
                     .path("/")
                     .secure(true)
-                    .http_only(false)
+                    .http_only(true)
                     .same_site(cookie::SameSite::Strict)
                     .build(),
-            );
+            )
+            .remove(CookieBuilder::new(LEGACY_SESSION_COOKIE, "").path("/"));
+    }
 
-            session_id
-        }
-    };
+    if cookie_jar.get(PLAYER_COOKIE).map(|cookie| cookie.value()) != Some(&player_id.to_string()) {
+        cookie_jar = cookie_jar.add(
+            CookieBuilder::new(PLAYER_COOKIE, player_id.to_string())
+                .path("/")
+                .secure(true)
+                .http_only(false)
+                .same_site(cookie::SameSite::Strict)
+                .build(),
+        );
+    }
 
-    request.extensions_mut().insert(session_id);
+    request.extensions_mut().insert(PlayerID(player_id));
 
     (cookie_jar, next.run(request).await)
 }
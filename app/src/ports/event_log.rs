@@ -1,11 +1,42 @@
-use std::future::Future;
+use std::{fmt::Display, future::Future};
 
 use anyhow::Result;
 use im::Vector;
-use shared::models::events::Event;
+use shared::models::{
+    events::{Event, Origin},
+    projections::GameView,
+};
 
 pub trait EventLog {
-    fn push(&self, event: Event) -> impl Future<Output = Result<()>>;
+    // Writes all of `events` or none of them, failing with `VersionConflict` unless the log holds
+    // exactly `expected_version` events
+    fn append(
+        &self,
+        expected_version: usize,
+        events: Vec<Event>,
+        origin: Origin,
+    ) -> impl Future<Output = Result<()>>;
     fn iter(&self) -> impl Future<Output = Result<impl Iterator<Item = Event>>>;
     fn vector(&self) -> impl Future<Output = Result<Vector<Event>>>;
+    fn view(&self) -> impl Future<Output = Result<GameView>>;
 }
+
+// Somebody else appended to the log after it was read. Nothing was written, read the log again and
+// retry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VersionConflict {
+    pub expected: usize,
+    pub actual: usize,
+}
+
+impl Display for VersionConflict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "expected the event log to be at version {}, found {}",
+            self.expected, self.actual
+        )
+    }
+}
+
+impl std::error::Error for VersionConflict {}
@@ -2,7 +2,14 @@ use std::{future::Future, time::Duration};
 
 use anyhow::Result;
 use im::Vector;
-use shared::models::events::Event;
+use shared::{
+    models::{
+        events::{Event, Origin},
+        projections::GameView,
+    },
+    time::Clock,
+};
+use uuid::Uuid;
 
 use super::game_service::GameBy;
 
@@ -10,10 +17,28 @@ pub trait GameState: Clone + Send + 'static {
     type WebSocket;
 
     fn events(&self) -> impl Future<Output = Result<Vector<Event>>> + Send;
-    fn push_event(&self, event: Event) -> impl Future<Output = Result<()>> + Send;
+    fn view(&self) -> impl Future<Output = Result<GameView>> + Send;
+    // See `EventLog::append`, the events are sent to every connected client once they're written
+    fn append(
+        &self,
+        expected_version: usize,
+        events: Vec<Event>,
+        origin: Origin,
+    ) -> impl Future<Output = Result<()>> + Send;
     fn set_alarm(&self, duration: Duration) -> impl Future<Output = Result<()>> + Send;
+    // Where commands and process managers run against this game get the time from
+    fn clock(&self) -> &dyn Clock;
 
-    fn accept_web_socket(&self, ws: Self::WebSocket) -> impl Future<Output = Result<()>> + Send;
+    // The player is remembered with the socket so their presence can be updated when it closes
+    fn accept_web_socket(
+        &self,
+        ws: Self::WebSocket,
+        player_id: Uuid,
+    ) -> impl Future<Output = Result<()>> + Send;
+
+    // Called once one of the player's sockets has closed, returns how many they still have open. A
+    // player can have the game open in several tabs, they've only left once the last one closes.
+    fn release_web_socket(&self, player_id: Uuid) -> impl Future<Output = Result<usize>> + Send;
 }
 
 pub trait GameDirectory: Clone + Send + Sync + 'static {
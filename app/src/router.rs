@@ -34,7 +34,8 @@ pub fn into_outer_router<S: GameService>(game_service: S) -> axum::Router {
 
     let router = axum::Router::new()
         .route("/api/create_game", post(create_game::<S>))
-        .route("/api/join_game", post(join_game::<S>))
+        .route("/api/join_game", post(join_game::<commands::JoinGame, S>))
+        .route("/api/watch_game", post(join_game::<commands::WatchGame, S>))
         .route(
             "/api/object/game/by_code/:code/*command",
             any(forward_command_by_code::<S>),
@@ -74,11 +75,20 @@ pub fn into_game_router<G: GameDirectory<WebSocket = WebSocket>>(game: G) -> axu
         )
         .register_command_handler::<commands::CreateGame>()
         .register_command_handler::<commands::JoinGame>()
+        .register_command_handler::<commands::WatchGame>()
         .register_command_handler::<commands::ChangeProfile>()
         .register_command_handler::<commands::ReadyPlayer>()
         .register_command_handler::<commands::PlaceBets>()
+        .register_command_handler::<commands::ReadyToRace>()
         .register_command_handler::<commands::BorrowMoney>()
         .register_command_handler::<commands::BuyCard>()
         .register_command_handler::<commands::PlayCard>()
+        .register_command_handler::<commands::KickPlayer>()
+        .register_command_handler::<commands::PauseGame>()
+        .register_command_handler::<commands::ResumeGame>()
+        .register_command_handler::<commands::ExtendTimer>()
+        .register_command_handler::<commands::StartRace>()
+        .register_command_handler::<commands::VoidRound>()
+        .register_command_handler::<commands::EndGame>()
         .with_state(game)
 }
@@ -2,8 +2,7 @@ use leptos::prelude::*;
 use leptos_router::{hooks::use_navigate, NavigateOptions};
 
 use crate::utils::{
-    create_event_signal, get_session_id, provide_events, provide_session_id, use_game_id,
-    Connection,
+    create_event_signal, get_player_id, provide_events, provide_player_id, use_game_id, Connection,
 };
 
 // Turn this into a resource and add an error boundary
@@ -44,14 +43,14 @@ pub fn game_connection_wrapper(children: ChildrenFn) -> impl IntoView {
         }
         .into_any(),
         Connection::Connected => {
-            let session_id = get_session_id();
-            if session_id.is_none() {
+            let player_id = get_player_id();
+            if player_id.is_none() {
                 use_navigate()("/", NavigateOptions::default());
 
                 return ().into_any();
             }
 
-            provide_session_id(session_id.unwrap());
+            provide_player_id(player_id.unwrap());
             provide_events(events.into());
 
             children()
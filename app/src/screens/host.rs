@@ -9,3 +9,6 @@ pub use lobby::Lobby;
 
 pub mod race;
 pub use race::Race;
+
+pub mod new_game;
+pub use new_game::NewGame;
@@ -1,8 +1,13 @@
 use leptos::prelude::*;
 use leptos_router::location::Url;
-use shared::models::projections;
+use shared::models::commands::{KickPlayer, kick_player};
+use uuid::Uuid;
 
-use crate::utils::{use_events, use_game_id};
+use crate::{
+    components::molecules::CommandFailure,
+    server_fns::server_fn,
+    utils::{use_events, use_game_id, use_player_id},
+};
 
 #[component]
 pub fn lobby() -> impl IntoView {
@@ -19,7 +24,19 @@ pub fn lobby() -> impl IntoView {
         "https://api.qrserver.com/v1/create-qr-code/?size=500x500&data={url}&color=fff&bgcolor=000"
     );
 
-    let players = move || projections::players(&events());
+    let players = move || events().players().clone();
+
+    let session_id = use_player_id();
+    let is_host = move || events().is_host(session_id);
+
+    let kick_player = Action::new(move |player: &Uuid| {
+        server_fn::<KickPlayer>(
+            game_id,
+            &kick_player::Input {
+                session_id: *player,
+            },
+        )
+    });
 
     view! {
         <div class="host-lobby-container">
@@ -29,16 +46,29 @@ pub fn lobby() -> impl IntoView {
                 players()
                     .iter()
                     .map(|(_, player)| {
+                        let player_id = player.session_id;
+
                         view! {
                             <div class="host-lobby-player" class:ready=player.ready>
                                 <div class="profile-image">"Profile Image"</div>
                                 <h1>{player.name.clone()}</h1>
                                 <p>{if player.ready { "Ready" } else { "Busy" }}</p>
+                                <Show when=is_host>
+                                    <button
+                                        class="button"
+                                        on:click=move |_| {
+                                            kick_player.dispatch(player_id);
+                                        }
+                                    >
+                                        "Kick"
+                                    </button>
+                                </Show>
                             </div>
                         }
                     })
                     .collect_view()
             }}
+            <CommandFailure result=kick_player.value() />
 
         </div>
     }
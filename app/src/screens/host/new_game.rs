@@ -0,0 +1,123 @@
+use leptos::prelude::*;
+use leptos_router::components::Form;
+use shared::models::events::{Payout, Settings};
+
+use crate::components::molecules::NumberInput;
+
+#[component]
+pub fn new_game() -> impl IntoView {
+    let defaults = Settings::default();
+
+    view! {
+        <div class="vertical-stack container full-height">
+            <div class="headroom"></div>
+            <h1>"Host a game"</h1>
+            <Form action="/api/create_game" method="POST" attr:class="vertical-stack">
+                <div class="settings-grid">
+                    <div class="input full-width">
+                        <label for="payout">"Payout"</label>
+                        <select id="payout" name="payout">
+                            <option value="Odds" selected=defaults.payout == Payout::Odds>
+                                "Odds"
+                            </option>
+                            <option value="Pool" selected=defaults.payout == Payout::Pool>
+                                "Pool"
+                            </option>
+                        </select>
+                    </div>
+                    <NumberInput
+                        id="rounds"
+                        name="Rounds"
+                        value=defaults.rounds.to_string()
+                        min="1"
+                        max="20"
+                    />
+                    <NumberInput
+                        id="field_size"
+                        name="Monsters per Race"
+                        value=defaults.field_size.to_string()
+                        min="3"
+                        max="8"
+                    />
+                    <NumberInput
+                        id="max_players"
+                        name="Max Players"
+                        value=defaults.max_players.to_string()
+                        min="1"
+                        max="15"
+                    />
+                    <NumberInput
+                        id="starting_balance"
+                        name="Starting Balance"
+                        value=defaults.starting_balance.to_string()
+                        min="100"
+                        max="100000"
+                        step="100"
+                    />
+                    <NumberInput
+                        id="starting_cards"
+                        name="Starting Cards"
+                        value=defaults.starting_cards.to_string()
+                        min="0"
+                        max="10"
+                    />
+                    <NumberInput
+                        id="hand_limit"
+                        name="Hand Limit"
+                        value=defaults.hand_limit.to_string()
+                        min="1"
+                        max="10"
+                    />
+                    <NumberInput
+                        id="card_price"
+                        name="Card Price"
+                        value=defaults.card_price.to_string()
+                        min="0"
+                        max="100000"
+                        step="10"
+                    />
+                    <NumberInput
+                        id="interest_rate"
+                        name="Interest Rate"
+                        value=defaults.interest_rate.to_string()
+                        min="0"
+                        max="1"
+                        step="0.001"
+                    />
+                    <NumberInput
+                        id="pre_game_timeout"
+                        name="Betting Time (s)"
+                        value=defaults.pre_game_timeout.to_string()
+                        min="10"
+                        max="600"
+                    />
+                    <NumberInput
+                        id="summary_duration"
+                        name="Summary Time (s)"
+                        value=defaults.summary_duration.to_string()
+                        min="5"
+                        max="120"
+                    />
+                    <NumberInput
+                        id="afk_timeout"
+                        name="AFK Time (s)"
+                        value=defaults.afk_timeout.to_string()
+                        min="10"
+                        max="600"
+                    />
+                    <div class="input full-width">
+                        <label for="late_join">"Allow Late Joins"</label>
+                        <input
+                            id="late_join"
+                            type="checkbox"
+                            name="late_join"
+                            value="true"
+                            checked=defaults.late_join
+                        />
+                    </div>
+                </div>
+                <input class="button" type="submit" value="Create Game" />
+            </Form>
+        </div>
+    }
+}
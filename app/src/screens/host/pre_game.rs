@@ -1,6 +1,12 @@
 use leptos::{either::Either, prelude::*};
 use leptos_use::{UseIntervalReturn, use_interval};
-use shared::models::{events::OddsExt, monsters::Monster, projections};
+use shared::{
+    models::{
+        events::{OddsExt, Payout},
+        monsters::Monster,
+    },
+    time::SystemClock,
+};
 
 use crate::utils::use_events;
 
@@ -65,12 +71,12 @@ struct MonsterData {
 pub fn pre_game() -> impl IntoView {
     let events = use_events();
 
-    let settings = move || projections::settings(&events());
+    let settings = move || events().settings();
 
-    let race_seed = move || projections::race::race_seed(&events());
-    let monsters = move || projections::monsters(&events(), race_seed());
+    let race_seed = move || events().race_seed();
+    let monsters = move || events().monsters(race_seed());
 
-    let odds = move || projections::pre_computed_odds(&events());
+    let odds = move || events().pre_computed_odds();
 
     let monsters = move || {
         let odds = odds();
@@ -78,31 +84,40 @@ pub fn pre_game() -> impl IntoView {
         monsters()
             .into_iter()
             .map(|monster| MonsterData {
-                monster,
                 odds: odds.odds(monster.uuid) * 100.0,
                 payout: odds.payout(monster.uuid),
+                monster,
             })
             .collect::<Vec<_>>()
     };
 
+    let pool = move || settings().payout == Payout::Pool;
+    let tote_board = Memo::new(move |_| events().tote_board());
+
     let UseIntervalReturn { counter, .. } = use_interval(1000);
 
     let time = move || {
         counter();
-        projections::time_left_in_pregame(&events())
+        events().time_left_in_pregame(&SystemClock)
     };
 
-    let round_number = move || projections::round(&events());
+    let round_number = move || events().round();
 
     view! {
-        <div class="host-pre-game-container">
+        <div
+            class="host-pre-game-container"
+            style:grid-template-columns=move || format!("repeat({}, 1fr)", settings().field_size)
+        >
             <div class="host-pre-game-timer" style="left: 1em;">
                 "Round "
                 {round_number}
                 " of "
                 {settings().rounds}
             </div>
-            <div class="host-pre-game-timer" style="justify-self: center;">
+            <div
+                class="host-pre-game-timer"
+                style="justify-self: center; grid-row: 1; grid-column: 1 / -1;"
+            >
                 "Time Left "
                 {move || match time() {
                     Some(time) => format!("{time}s"),
@@ -110,13 +125,51 @@ pub fn pre_game() -> impl IntoView {
                 }}
 
             </div>
+            <Show when=pool fallback=|| view! {}>
+                <div
+                    class="host-pre-game-timer"
+                    style="justify-self: end; grid-row: 1; grid-column: 1 / -1;"
+                >
+                    "Pool 💎 "
+                    {move || tote_board().total}
+                </div>
+            </Show>
             <For each=monsters key=|it| it.monster.uuid let:data>
                 <div class="monster-stats-container">
                     <h1>{data.monster.name}</h1>
-                    <div class="odds">
-                        <p>"Odds: " {format!("{:.0}", data.odds)} "%"</p>
-                        <p>"Payout: " {format!("{:.2}", data.payout)}"x"</p>
-                    </div>
+                    <Show
+                        when=pool
+                        fallback=move || {
+                            view! {
+                                <div class="odds">
+                                    <p>"Odds: " {format!("{:.0}", data.odds)} "%"</p>
+                                    <p>"Payout: " {format!("{:.2}", data.payout)}"x"</p>
+                                </div>
+                            }
+                        }
+                    >
+                        {move || {
+                            tote_board()
+                                .entry(data.monster.uuid)
+                                .map(|entry| {
+                                    view! {
+                                        <div class="odds">
+                                            <p>
+                                                "Staked: 💎 " {entry.staked} " ("
+                                                {format!("{:.0}", entry.share * 100.0)} "%)"
+                                            </p>
+                                            <p>
+                                                "Payout: "
+                                                {entry
+                                                    .payout
+                                                    .map(|payout| format!("{payout:.2}x"))
+                                                    .unwrap_or_else(|| "-".to_string())}
+                                            </p>
+                                        </div>
+                                    }
+                                })
+                        }}
+                    </Show>
                     <StatRow stat=Stat::Dexterity value=data.monster.dexterity />
                     <StatRow stat=Stat::Strength value=data.monster.strength />
                 </div>
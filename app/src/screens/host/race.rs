@@ -1,6 +1,6 @@
 use leptos::prelude::*;
 use leptos_use::UseIntervalReturn;
-use shared::models::{cards::Card, projections};
+use shared::models::cards::Card;
 
 use crate::utils::use_events;
 
@@ -19,18 +19,22 @@ enum Countdown {
 #[component]
 pub fn race() -> impl IntoView {
     let events = use_events();
-    let race_seed = projections::race::race_seed(&events.get_untracked());
+    let game = events.get_untracked();
+    let race_seed = game.race_seed();
 
-    let monsters = projections::monsters(&events.get_untracked(), race_seed);
-    let cards = projections::unique_played_monster_cards(&events.get_untracked());
+    let monsters = game.monsters(race_seed);
+    let cards = game.unique_played_monster_cards().clone();
 
     let UseIntervalReturn { counter: timer, .. } = leptos_use::use_interval(1000);
 
-    let monster_cards =
-        monsters.map(|monster| (monster.uuid, signal::<Option<PlayedCardInfo>>(None)));
+    let monster_cards = monsters
+        .iter()
+        .map(|monster| (monster.uuid, signal::<Option<PlayedCardInfo>>(None)))
+        .collect::<Vec<_>>();
 
     Effect::new({
         let cards = cards.clone();
+        let monster_cards = monster_cards.clone();
         move |_| {
             let counter = timer.get();
 
@@ -42,7 +46,7 @@ pub fn race() -> impl IntoView {
 
             let active = counter % 4 != 3 && counter != 0;
 
-            for (id, (get_card, set_card)) in monster_cards {
+            for &(id, (get_card, set_card)) in &monster_cards {
                 if card.monster_id == id {
                     set_card(Some(PlayedCardInfo {
                         active,
@@ -87,6 +91,8 @@ pub fn race() -> impl IntoView {
         <Show when=visible.clone() fallback=move || {}>
             <div class="host-race-container">
                 {monster_cards
+                    .iter()
+                    .copied()
                     .map(|data| {
                         view! {
                             <div
@@ -113,7 +119,8 @@ pub fn race() -> impl IntoView {
                                 </div>
                             </div>
                         }
-                    })}
+                    })
+                    .collect::<Vec<_>>()}
             </div>
         </Show>
         <Show when=move || countdown().is_some()>
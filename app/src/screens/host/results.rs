@@ -1,5 +1,4 @@
 use leptos::prelude::*;
-use shared::models::projections;
 
 use crate::utils::use_events;
 
@@ -16,10 +15,10 @@ pub fn results() -> impl IntoView {
     let timer = leptos_use::use_timeout_fn(|_| {}, 3000.);
     (timer.start)(());
 
-    let players = move || projections::players(&events());
-    let balances = move || projections::all_account_balances(&events());
-    let winnings = move || projections::winnings(&events());
-    let debt = move || projections::all_debt(&events());
+    let players = move || events().players().clone();
+    let balances = move || events().all_account_balances().clone();
+    let winnings = move || events().winnings().clone();
+    let debt = move || events().all_debt().clone();
 
     let leaderboard = move || {
         let players = players();
@@ -44,7 +43,7 @@ pub fn results() -> impl IntoView {
         info
     };
 
-    let game_is_finished = move || projections::game_finished(&events());
+    let game_is_finished = move || events().game_finished();
 
     view! {
         <div class="host-results-container">
@@ -1,5 +1,4 @@
 use leptos::prelude::*;
-use leptos_router::components::Form;
 
 use crate::components::layouts::HorizontalStack;
 
@@ -11,9 +10,9 @@ pub fn main_menu() -> impl IntoView {
             <h1 class="title">"Deep Space Derby"</h1>
             // <div class="splash-image">"Image"</div>
             <HorizontalStack>
-                <Form attr:class="button-form" action="/api/create_game" method="POST">
-                    <input class="button" type="submit" value="Host" />
-                </Form>
+                <a class="button" href="/host">
+                    "Host"
+                </a>
                 <a class="button" href="/play">
                     "Join"
                 </a>
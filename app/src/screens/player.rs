@@ -15,3 +15,6 @@ pub use wait::*;
 
 mod summary;
 pub use summary::*;
+
+mod queued;
+pub use queued::*;
@@ -34,6 +34,12 @@ pub fn join() -> impl IntoView {
                     value=None
                 />
                 <input class="button join-button" type="submit" value="Join Now" />
+                <input
+                    class="button"
+                    type="submit"
+                    formaction="/api/watch_game"
+                    value="Just Watch"
+                />
             </Form>
         </div>
     }
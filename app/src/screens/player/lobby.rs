@@ -1,8 +1,9 @@
 use leptos::{either::Either, html::Input, prelude::*};
 
 use crate::{
+    components::molecules::CommandFailure,
     server_fns::server_fn,
-    utils::{use_events, use_game_id, use_session_id},
+    utils::{use_events, use_game_id, use_player_id},
 };
 use shared::models::{
     commands::{change_profile, ChangeProfile, ReadyPlayer},
@@ -13,9 +14,9 @@ use shared::models::{
 pub fn lobby() -> impl IntoView {
     let events = use_events();
     let game_id = use_game_id();
-    let session_id = use_session_id();
+    let session_id = use_player_id();
 
-    let players = move || projections::players(&events.get());
+    let players = move || events.get().players().clone();
 
     let player = move || players().get(&session_id).cloned().unwrap_or_default();
 
@@ -62,6 +63,7 @@ pub fn lobby() -> impl IntoView {
 
                                 "Ready"
                             </button>
+                            <CommandFailure result=ready_player.value() />
                             <a class="button" href="/play">
                                 "Leave"
                             </a>
@@ -1,4 +1,7 @@
-use std::cmp::{max, min};
+use std::{
+    borrow::Cow,
+    cmp::{max, min},
+};
 
 use leptos::{either::Either, leptos_dom::logging::console_log, prelude::*};
 use leptos_use::use_scroll;
@@ -6,22 +9,27 @@ use uuid::Uuid;
 use web_sys::MouseEvent;
 
 use crate::{
+    components::molecules::CommandFailure,
     server_fns::server_fn,
-    utils::{use_events, use_game_id, use_session_id},
+    utils::{use_events, use_game_id, use_player_id},
 };
 use shared::models::{
     cards::{Card, Target, TargetKind},
-    commands::{BorrowMoney, BuyCard, PlaceBets, PlayCard, borrow_money, place_bets, play_card},
-    events::{Event, OddsExt},
+    commands::{
+        BorrowMoney, BuyCard, PlaceBets, PlayCard, ReadyToRace, borrow_money, place_bets, play_card,
+    },
+    events::{BetKind, Event, OddsExt, Payout},
     monsters::Monster,
-    projections::{self, PlayerInfo, race::race_seed},
+    projections::{self, GameView, PlayerInfo, ToteEntry},
 };
 
 #[component]
 pub fn creature_card(
-    name: &'static str,
+    name: Cow<'static, str>,
     amount: RwSignal<i32>,
     available_money: Signal<i32>,
+    tote: Signal<Option<ToteEntry>>,
+    form: String,
 ) -> impl IntoView {
     let set_bet = move |input: i32| {
         console_log(&format!("Setting bet to {input}"));
@@ -47,6 +55,22 @@ pub fn creature_card(
     view! {
         <div class="creature-container">
             <h3>{name}</h3>
+            <p class="form">{form}</p>
+            {move || {
+                tote()
+                    .map(|entry| {
+                        view! {
+                            <p class="tote">
+                                "Pool 💎 " {entry.staked} " ("
+                                {format!("{:.0}%", entry.share * 100.0)} ") pays "
+                                {entry
+                                    .payout
+                                    .map(|payout| format!("{payout:.2}x"))
+                                    .unwrap_or_else(|| "-".to_string())}
+                            </p>
+                        }
+                    })
+            }}
             <div class="betting-row">
                 <button on:click=decrement disabled=move || amount() <= 0>
                     "-"
@@ -63,9 +87,33 @@ pub fn creature_card(
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+// Finishing positions of the last few races, most recent last, e.g. "3-1 🔥"
+fn form_guide(events: &GameView, monster_id: Uuid) -> String {
+    let form = events.form(monster_id);
+
+    let mut guide = match form.last(5) {
+        [] => "Debut".to_string(),
+        finishes => finishes
+            .iter()
+            .map(|finish| finish.position.to_string())
+            .collect::<Vec<_>>()
+            .join("-"),
+    };
+
+    if events.hot_streak(monster_id) {
+        guide.push_str(" 🔥");
+    }
+
+    if events.fatigued(monster_id) {
+        guide.push_str(" 😴");
+    }
+
+    guide
+}
+
+#[derive(Debug, Clone, PartialEq)]
 struct Bet {
-    name: &'static str,
+    name: Cow<'static, str>,
     monster_id: Uuid,
     amount: RwSignal<i32>,
 }
@@ -73,64 +121,75 @@ struct Bet {
 #[component]
 pub fn pre_game() -> impl IntoView {
     let game_id = use_game_id();
-    let player_id = use_session_id();
+    let player_id = use_player_id();
     let events = use_events();
 
-    let player_info = move || projections::player_info(&events(), player_id);
+    let player_info = move || events().player_info(player_id);
     let player_name = move || player_info().map(|player| player.name);
 
     let untracked_events = events.get_untracked();
 
-    // let minimum_bet = projections::minimum_bet(&untracked_events);
-    let monsters = projections::monsters(
-        &untracked_events,
-        projections::race::race_seed(&untracked_events),
-    );
+    let monsters = untracked_events.monsters(untracked_events.race_seed());
 
     let account_balance = Signal::derive(move || {
-        projections::all_account_balances(&events())
+        events()
+            .all_account_balances()
             .get(&player_id)
             .copied()
             .unwrap_or_default()
     });
 
-    let bets = {
-        monsters.map(|Monster { name, uuid, .. }| Bet {
-            name,
-            monster_id: uuid,
-            amount: RwSignal::new(0),
-        })
-    };
+    let bets = StoredValue::new(
+        monsters
+            .into_iter()
+            .map(|Monster { name, uuid, .. }| Bet {
+                name,
+                monster_id: uuid,
+                amount: RwSignal::new(0),
+            })
+            .collect::<Vec<_>>(),
+    );
 
-    let debt = Signal::derive(move || projections::debt(&events(), player_id));
+    let combination_bets = RwSignal::new(Vec::<place_bets::Bet>::new());
 
-    let sum_of_bets = Signal::derive(move || bets.iter().map(|bet| (bet.amount)()).sum::<i32>());
+    let debt = Signal::derive(move || events().debt(player_id));
+
+    let sum_of_bets = Signal::derive(move || {
+        bets.with_value(|bets| bets.iter().map(|bet| (bet.amount)()).sum::<i32>())
+            + combination_bets.with(|bets| bets.iter().map(|bet| bet.amount).sum::<i32>())
+    });
 
     let available_money = Signal::derive(move || max(account_balance() - sum_of_bets(), 0));
 
+    let tote_board = Memo::new(move |_| {
+        let events = events();
+        (events.settings().payout == Payout::Pool).then(|| events.tote_board())
+    });
+
     let place_bets = Action::new({
         move |_: &()| {
             server_fn::<PlaceBets>(
                 game_id,
                 &place_bets::Input {
-                    bets: {
-                        let bets = bets
-                            .iter()
+                    bets: bets.with_value(|bets| {
+                        bets.iter()
                             .filter(|bet| bet.amount.get() > 0)
                             .map(|bet| place_bets::Bet {
                                 monster_id: bet.monster_id,
                                 amount: (bet.amount)(),
+                                kind: BetKind::Win,
                             })
-                            .collect();
-                        bets
-                    },
+                            .chain(combination_bets.get())
+                            .collect()
+                    }),
                 },
             )
         }
     });
 
     let placed_bets = Memo::new(move |_| {
-        projections::placed_bets(&events())
+        events()
+            .placed_bets()
             .get(&player_id)
             .cloned()
             .unwrap_or_default()
@@ -144,13 +203,25 @@ pub fn pre_game() -> impl IntoView {
                 return;
             }
 
-            for placed_bet in placed_bets {
-                for bet in bets {
-                    if bet.monster_id == placed_bet.monster_id {
+            for placed_bet in placed_bets.iter() {
+                for bet in bets.get_value() {
+                    if placed_bet.kind == BetKind::Win && bet.monster_id == placed_bet.monster_id {
                         bet.amount.set(placed_bet.amount);
                     }
                 }
             }
+
+            combination_bets.set(
+                placed_bets
+                    .into_iter()
+                    .filter(|placed_bet| placed_bet.kind != BetKind::Win)
+                    .map(|placed_bet| place_bets::Bet {
+                        monster_id: placed_bet.monster_id,
+                        amount: placed_bet.amount,
+                        kind: placed_bet.kind,
+                    })
+                    .collect(),
+            );
         }
     });
 
@@ -164,22 +235,23 @@ pub fn pre_game() -> impl IntoView {
     let (loan_modal, toggle_loan_modal) = new_modal(false);
     let (card_modal, toggle_card_modal) = new_modal(false);
 
-    let enemy = Signal::derive(move || projections::enemy(&events(), player_id));
+    let enemy = Signal::derive(move || events().enemy(player_id));
     let (enemy_modal, toggle_enemy_modal) = new_modal(enemy.read_untracked().is_some());
 
     // TODO: Fix this, it causes the modal to pop up during re-hydration of the event stream
     let (victim_modal, set_victim_modal) = signal(None);
     Effect::new(move |_| {
-        if let Some((card, perpetrator)) = projections::victim_of_card(&events(), player_id) {
+        if let Some((card, perpetrator)) = events().victim_of_card(player_id) {
             set_victim_modal(Some((card, perpetrator)))
         }
     });
 
-    let cards = move || projections::cards_in_hand(&events(), player_id);
+    let cards = move || events().cards_in_hand(player_id);
+    let track = move || events().track();
 
     let buy_card = Action::new(move |input| server_fn::<BuyCard>(game_id, input));
-    let cards_disabled =
-        Signal::derive(move || !projections::can_play_more_cards(&events(), player_id));
+    let ready_to_race = Action::new(move |input| server_fn::<ReadyToRace>(game_id, input));
+    let cards_disabled = Signal::derive(move || !events().can_play_more_cards(player_id));
 
     view! {
         <Show
@@ -197,6 +269,10 @@ pub fn pre_game() -> impl IntoView {
                         <span>"💎 " {debt}</span>
                     </div>
                 </div>
+                <div class="track">
+                    <h3>{move || track().name()}</h3>
+                    <p>{move || track().description()}</p>
+                </div>
                 <div class="action-grid">
                     <button
                         class="action"
@@ -204,13 +280,13 @@ pub fn pre_game() -> impl IntoView {
                             buy_card.dispatch(());
                         }
 
-                        disabled=move || (cards().len() >= 5)
+                        disabled=move || (cards().len() >= events().settings().hand_limit)
                     >
                         <div class="placeholder-image">
                             <img src="/pkg/icons/spade.svg" />
                         </div>
                         <p>"Buy a card"</p>
-                        <p>"(💎 100)"</p>
+                        <p>"(💎 " {move || events().settings().card_price} ")"</p>
                     </button>
                     <button class="action" on:click=move |_| toggle_loan_modal()>
                         <div class="placeholder-image">
@@ -221,7 +297,17 @@ pub fn pre_game() -> impl IntoView {
                     <button class="action double-width" on:click=move |_| toggle_bets_modal()>
                         "Place Bet"
                     </button>
+                    <button
+                        class="action double-width"
+                        on:click=move |_| {
+                            ready_to_race.dispatch(());
+                        }
+                    >
+                        "Ready to Race"
+                    </button>
                 </div>
+                <CommandFailure result=buy_card.value() />
+                <CommandFailure result=ready_to_race.value() />
                 <div class="card-line">
                     {move || {
                         let cards = cards();
@@ -258,12 +344,23 @@ pub fn pre_game() -> impl IntoView {
                 // <h2>"Place your Bets"</h2>
                 <p>"Available: 💎 " {move || available_money.try_get().unwrap_or_default()}</p>
                 <For
-                    each=move || bets
-                    key=|it| *it
-                    children=move |Bet { name, amount, .. }| {
-                        view! { <CreatureCard name amount available_money /> }
+                    each=move || bets.get_value()
+                    key=|it| it.monster_id
+                    children=move |Bet { name, monster_id, amount }| {
+                        let tote = Signal::derive(move || {
+                            tote_board().and_then(|board| board.entry(monster_id).copied())
+                        });
+                        let form = form_guide(&events.get_untracked(), monster_id);
+                        view! { <CreatureCard name amount available_money tote form /> }
                     }
                 />
+                <CombinationBets
+                    monsters=bets.with_value(|bets| {
+                        bets.iter().map(|bet| (bet.monster_id, bet.name.clone())).collect()
+                    })
+                    combination_bets
+                    available_money
+                />
 
                 <button
                     class="action confirm-bets"
@@ -274,6 +371,7 @@ pub fn pre_game() -> impl IntoView {
 
                     "Confirm bets"
                 </button>
+                <CommandFailure result=place_bets.value() />
             </div>
         </Show>
         <Show when=move || loan_modal() && victim_modal().is_none() fallback=|| view! {}>
@@ -294,6 +392,181 @@ pub fn pre_game() -> impl IntoView {
     }
 }
 
+const COMBINATION_BETS: [&str; 4] = ["Place", "Show", "Exacta", "Trifecta"];
+
+#[component]
+fn combination_bets(
+    monsters: Vec<(Uuid, Cow<'static, str>)>,
+    combination_bets: RwSignal<Vec<place_bets::Bet>>,
+    available_money: Signal<i32>,
+) -> impl IntoView {
+    let events = use_events();
+
+    let (kind, set_kind) = signal(COMBINATION_BETS[0]);
+    let runners: [RwSignal<Option<Uuid>>; 3] = std::array::from_fn(|_| RwSignal::new(None));
+    let (amount, set_amount) = signal(0);
+
+    // How many monsters the selected kind of bet has to name
+    let runner_count = move || match kind() {
+        "Exacta" => 2,
+        "Trifecta" => 3,
+        _ => 1,
+    };
+
+    let bet = move || {
+        let selected = runners[..runner_count()]
+            .iter()
+            .map(|runner| runner.get())
+            .collect::<Option<Vec<_>>>()?;
+
+        if (1..selected.len()).any(|index| selected[..index].contains(&selected[index])) {
+            return None;
+        }
+
+        let kind = match kind() {
+            "Place" => BetKind::Place,
+            "Show" => BetKind::Show,
+            "Exacta" => BetKind::Exacta {
+                second: selected[1],
+            },
+            _ => BetKind::Trifecta {
+                second: selected[1],
+                third: selected[2],
+            },
+        };
+
+        let amount = amount();
+
+        (amount > 0 && amount <= available_money.try_get().unwrap_or_default()).then(|| {
+            place_bets::Bet {
+                monster_id: selected[0],
+                amount,
+                kind,
+            }
+        })
+    };
+
+    let add_bet = move |_| {
+        if let Some(bet) = bet() {
+            combination_bets.update(|bets| bets.push(bet));
+            set_amount(0);
+        }
+    };
+
+    let describe = {
+        let monsters = monsters.clone();
+
+        move |bet: &place_bets::Bet| {
+            let name = |uuid| {
+                monsters
+                    .iter()
+                    .find(|(monster_id, _)| *monster_id == uuid)
+                    .map(|(_, name)| name.clone())
+                    .unwrap_or_default()
+            };
+
+            std::iter::once(bet.monster_id)
+                .chain(bet.kind.runners())
+                .map(name)
+                .collect::<Vec<_>>()
+                .join(" → ")
+        }
+    };
+
+    let payout = move |bet: &place_bets::Bet| {
+        let events = events();
+
+        (events.settings().payout == Payout::Odds).then(|| {
+            format!(
+                " (x{:.1})",
+                events
+                    .pre_computed_odds()
+                    .bet_payout(bet.monster_id, bet.kind)
+            )
+        })
+    };
+
+    view! {
+        <div class="creature-container">
+            <h3>"Combination Bets"</h3>
+            <div class="combination-bet">
+                <select on:change=move |ev| {
+                    let value = event_target_value(&ev);
+                    if let Some(kind) = COMBINATION_BETS.into_iter().find(|kind| *kind == value) {
+                        set_kind(kind);
+                    }
+                }>
+                    {COMBINATION_BETS
+                        .into_iter()
+                        .map(|kind| view! { <option value=kind>{kind}</option> })
+                        .collect::<Vec<_>>()}
+                </select>
+                {runners
+                    .into_iter()
+                    .enumerate()
+                    .map(|(index, runner)| {
+                        let monsters = monsters.clone();
+                        view! {
+                            <Show when=move || index < runner_count() fallback=|| view! {}>
+                                <select on:change=move |ev| {
+                                    runner.set(event_target_value(&ev).parse().ok())
+                                }>
+                                    <option value="">{format!("Place {}", index + 1)}</option>
+                                    {monsters
+                                        .iter()
+                                        .cloned()
+                                        .map(|(uuid, name)| {
+                                            view! {
+                                                <option
+                                                    value=uuid.to_string()
+                                                    prop:selected=move || runner.get() == Some(uuid)
+                                                >
+                                                    {name}
+                                                </option>
+                                            }
+                                        })
+                                        .collect::<Vec<_>>()}
+                                </select>
+                            </Show>
+                        }
+                    })
+                    .collect::<Vec<_>>()}
+                <div class="betting-row">
+                    <input
+                        type="number"
+                        prop:value=amount
+                        on:input=move |ev| set_amount(event_target_value(&ev).parse().unwrap_or_default())
+                    />
+                    <button on:click=add_bet disabled=move || bet().is_none()>
+                        "+"
+                    </button>
+                </div>
+                {move || {
+                    combination_bets()
+                        .into_iter()
+                        .enumerate()
+                        .map(|(index, bet)| {
+                            view! {
+                                <div class="betting-row">
+                                    <span>
+                                        {bet.kind.name()} ": " {describe(&bet)} " 💎 " {bet.amount}
+                                        {payout(&bet)}
+                                    </span>
+                                    <button on:click=move |_| {
+                                        combination_bets.update(|bets| {
+                                            bets.remove(index);
+                                        })
+                                    }>"-"</button>
+                                </div>
+                            }
+                        })
+                        .collect::<Vec<_>>()
+                }}
+            </div>
+        </div>
+    }
+}
+
 #[component]
 fn loan_modal(
     close: impl Fn() + Copy + Send + Sync + 'static,
@@ -305,7 +578,7 @@ fn loan_modal(
 
     let minimum = move || -1 * i32::min(account_balance(), debt() as i32);
 
-    let maximum_debt = move || projections::maximum_debt(&events());
+    let maximum_debt = move || events().maximum_debt();
 
     let (borrow, set_borrow) = {
         let (read, write) = signal(0);
@@ -332,10 +605,14 @@ fn loan_modal(
         let amount = *amount;
 
         async move {
-            match server_fn::<BorrowMoney>(game_id, &borrow_money::Input { amount }).await {
+            let result = server_fn::<BorrowMoney>(game_id, &borrow_money::Input { amount }).await;
+
+            match &result {
                 Ok(_) => close(),
                 Err(err) => tracing::error!(?err, "failed to borrow money"),
             };
+
+            result
         }
     });
 
@@ -379,6 +656,7 @@ fn loan_modal(
                 }}
 
             </button>
+            <CommandFailure result=borrow_money.value() />
         </div>
     }
 }
@@ -424,8 +702,8 @@ fn card_main(card: Card, on_click: impl FnMut(MouseEvent) + 'static) -> impl Int
 #[component]
 fn card_modal(close: impl Fn() + Copy + Send + Sync + 'static) -> impl IntoView {
     let events = use_events();
-    let player_id = use_session_id();
-    let cards = move || projections::cards_in_hand(&events(), player_id);
+    let player_id = use_player_id();
+    let cards = move || events().cards_in_hand(player_id);
 
     let scroll_ref = NodeRef::<leptos::html::Div>::new();
 
@@ -488,9 +766,9 @@ fn target_modal(
 ) -> impl IntoView {
     let events = use_events();
     let game_id = use_game_id();
-    let player_id = use_session_id();
+    let player_id = use_player_id();
 
-    let race_seed = move || projections::race::race_seed(&events());
+    let race_seed = move || events().race_seed();
 
     let target_count = match card.target_kind() {
         TargetKind::Player | TargetKind::Monster => 1,
@@ -538,11 +816,11 @@ fn target_modal(
         move || targets().unwrap_or_default().len() >= target_count && !target_selected()
     };
 
-    let base_monsters =
-        leptos::prelude::Memo::new(move |_| projections::monsters(&events(), race_seed()));
+    let base_monsters = leptos::prelude::Memo::new(move |_| events().monsters(race_seed()));
 
-    let base_odds =
-        leptos::prelude::Memo::new(move |_| projections::odds(&base_monsters(), race_seed()));
+    let base_odds = leptos::prelude::Memo::new(move |_| {
+        projections::odds(&base_monsters(), events().track(), race_seed())
+    });
 
     let target_view = move || match card.target_kind() {
         TargetKind::Monster => base_monsters()
@@ -551,15 +829,17 @@ fn target_modal(
                 let base_odd = base_odds().odds(monster.uuid) * 100.0;
 
                 let mut simulate_card = events();
-                simulate_card.push_back(Event::PlayedCard {
+                simulate_card.apply(Event::PlayedCard {
                     session_id: player_id,
                     card: card,
                     target: Target::Monster(monster.uuid),
                 });
 
-                let affected_monsters = projections::monsters(&simulate_card, race_seed());
+                let affected_monsters = simulate_card.monsters(race_seed());
                 let affected_odds =
-                    projections::odds(&affected_monsters, race_seed()).odds(monster.uuid) * 100.0;
+                    projections::odds(&affected_monsters, simulate_card.track(), race_seed())
+                        .odds(monster.uuid)
+                        * 100.0;
 
                 let difference = affected_odds - base_odd;
 
@@ -577,9 +857,11 @@ fn target_modal(
             })
             .collect::<Vec<AnyView>>(),
         TargetKind::Player | TargetKind::MultiplePlayers(_) => {
-            let account_balances = projections::all_account_balances(&events());
+            let account_balances = events().all_account_balances().clone();
 
-            let mut players = projections::players(&events())
+            let mut players = events()
+                .players()
+                .clone()
                 .into_iter()
                 .filter(|(target_id, _)| *target_id != player_id)
                 .map(|(target_id, info)| {
@@ -617,7 +899,7 @@ fn target_modal(
 
     let play_card = Action::new(move |_: &()| async move {
         let Some(target) = targets.get_untracked() else {
-            return;
+            return Ok(());
         };
 
         let target = match card.target_kind() {
@@ -626,10 +908,14 @@ fn target_modal(
             TargetKind::MultiplePlayers(_) => Target::MultiplePlayers(target),
         };
 
-        match server_fn::<PlayCard>(game_id, &play_card::Input { card, target }).await {
+        let result = server_fn::<PlayCard>(game_id, &play_card::Input { card, target }).await;
+
+        match &result {
             Ok(_) => done(),
             Err(err) => tracing::error!(?err, "failed to play card"),
         };
+
+        result
     });
 
     view! {
@@ -650,6 +936,7 @@ fn target_modal(
 
                     "Confirm"
                 </button>
+                <CommandFailure result=play_card.value() />
             </div>
         </div>
     }
@@ -0,0 +1,19 @@
+use leptos::prelude::*;
+
+use crate::utils::use_game_id;
+
+// Shown to players who joined after the game started until they're seated at the next round
+#[component]
+pub fn queued() -> impl IntoView {
+    let game_id = use_game_id();
+
+    view! {
+        <div class="pre-game-container space-around">
+            <h1>"You're in!"</h1>
+            <h2>"You'll get a seat when the next round starts"</h2>
+            <a class="button" href=format!("/watch/{game_id}")>
+                "Watch in the meantime"
+            </a>
+        </div>
+    }
+}
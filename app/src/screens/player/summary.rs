@@ -1,29 +1,65 @@
 use leptos::prelude::*;
-use shared::models::projections;
+use shared::models::projections::Transaction;
 
-use crate::utils::{use_events, use_session_id};
+use crate::utils::{use_events, use_player_id};
 
 #[component]
 pub fn summary() -> impl IntoView {
     let events = use_events();
-    let player_id = use_session_id();
+    let player_id = use_player_id();
 
     let balance = move || {
-        projections::all_account_balances(&events())
+        events()
+            .all_account_balances()
             .get(&player_id)
             .copied()
             .unwrap_or_default()
     };
 
-    let debt = Memo::new(move |_| projections::debt(&events(), player_id));
+    let debt = Memo::new(move |_| events().debt(player_id));
 
     let winnings = Memo::new(move |_| {
-        projections::winnings(&events())
+        events()
+            .winnings()
             .get(&player_id)
             .copied()
             .unwrap_or_default()
     });
 
+    let statement = Memo::new(move |_| {
+        let events = events();
+        let round = events.round();
+
+        events
+            .statement(player_id)
+            .into_iter()
+            .filter(|entry| entry.round == round)
+            .map(|entry| {
+                let name = |player| {
+                    events
+                        .player_info(player)
+                        .map(|info| info.name)
+                        .unwrap_or_default()
+                };
+
+                let label = match entry.transaction {
+                    Transaction::TheftTransfer { counterparty } if entry.amount < 0 => {
+                        format!("Stolen by {}", name(counterparty))
+                    }
+                    Transaction::TheftTransfer { counterparty } => {
+                        format!("Stolen from {}", name(counterparty))
+                    }
+                    Transaction::CrystalsGift { from } => format!("Gift from {}", name(from)),
+                    Transaction::RivalBonus { rival } => format!("Rival {} lost", name(rival)),
+                    Transaction::CardPurchase { card } => format!("Bought {}", card.name()),
+                    transaction => transaction.label().to_string(),
+                };
+
+                (label, entry.amount)
+            })
+            .collect::<Vec<_>>()
+    });
+
     let symbol = move || if winnings() >= 0 { "+" } else { "-" };
     let image = move || if winnings() >= 0 { "📈" } else { "📉" };
 
@@ -41,6 +77,37 @@ pub fn summary() -> impl IntoView {
                     <div>"Score:"</div>
                     <div>"💎 " {move || balance() - (debt() as i32)}</div>
                 </div>
+                <Show when=move || !statement().is_empty()>
+                    <h2>"Statement"</h2>
+                    <div class="statement-table">
+                        {move || {
+                            statement()
+                                .into_iter()
+                                .map(|(label, amount)| {
+                                    view! {
+                                        <div>{label}</div>
+                                        <div>{format!("💎 {:+}", amount)}</div>
+                                    }
+                                })
+                                .collect_view()
+                        }}
+                    </div>
+                </Show>
+                {move || {
+                    events()
+                        .race_verified()
+                        .map(|verified| {
+                            view! {
+                                <p class="fairness" title=events().reveal().map(|reveal| reveal.to_string())>
+                                    {if verified {
+                                        "✅ Race seed matches the commitment"
+                                    } else {
+                                        "⚠️ Race seed does not match the commitment"
+                                    }}
+                                </p>
+                            }
+                        })
+                }}
             </div>
         </div>
     }
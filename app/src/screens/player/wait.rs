@@ -1,28 +1,28 @@
 use leptos::prelude::*;
 use leptos_use::*;
-use shared::models::projections;
+use shared::time::SystemClock;
 
 use crate::{
     screens::player::VictimModal,
-    utils::{use_events, use_session_id},
+    utils::{use_events, use_player_id},
 };
 
 #[component]
 pub fn wait() -> impl IntoView {
     let events = use_events();
-    let player_id = use_session_id();
+    let player_id = use_player_id();
 
     let UseIntervalReturn { counter, .. } = use_interval(1000);
 
     let time = Signal::derive(move || {
         counter();
-        projections::time_left_in_pregame(&events())
+        events().time_left_in_pregame(&SystemClock)
     });
 
     let (victim_modal, set_victim_modal) = signal(None);
 
     Effect::new(move |_| {
-        if let Some(card) = projections::victim_of_card(&events(), player_id) {
+        if let Some(card) = events().victim_of_card(player_id) {
             set_victim_modal(Some(card))
         }
     });
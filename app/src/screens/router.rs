@@ -5,13 +5,11 @@ use leptos_router::{
 };
 
 use crate::{
+    components::molecules::HostControls,
     screens::{game_wrapper::GameConnectionWrapper, host, main_menu::MainMenu, player},
-    utils::{send_game_event, use_events, use_session_id},
-};
-use shared::models::{
-    events::{Event, EventStream},
-    projections,
+    utils::{send_game_event, use_events, use_player_id},
 };
+use shared::models::events::{Event, EventStream};
 
 #[component]
 pub fn send_events_to_bevy() -> impl IntoView {
@@ -22,35 +20,59 @@ pub fn send_events_to_bevy() -> impl IntoView {
 
         // This is really inefficient sending the entire event
         // But I'm about to remove comms between the UI and the game
-        send_game_event(EventStream::Events(Vec::from_iter(events.into_iter())));
+        send_game_event(EventStream::Events(Vec::from_iter(events.events().clone())));
     });
 }
 
+// Loads the bevy game and hands it any events that arrived while it was starting up
+#[component]
+fn load_game() -> impl IntoView {
+    view! {
+        <script type="module">
+            "
+            import init, { sendGameEvent as innerSendGameEvent } from '/pkg/game.js';
+
+            init().finally(() => {
+                globalThis['innerSendGameEvent'] = innerSendGameEvent;
+                console.log('Module initialised, flushing pending events')
+                console.log(pendingEvents);
+                while (pendingEvents.length > 0) {
+                    let event = pendingEvents.shift();
+                    innerSendGameEvent(event);
+                }
+            });
+            "
+        </script>
+    }
+}
+
+// Players who joined late wait here until they're given a seat at the next round
+#[component]
+fn seated(children: ChildrenFn) -> impl IntoView {
+    let events = use_events();
+    let player_id = use_player_id();
+
+    let queued = Memo::new(move |_| events().is_queued(player_id));
+
+    view! {
+        <Show when=move || !queued() fallback=player::Queued>
+            {children()}
+        </Show>
+    }
+}
+
 #[component]
 pub fn router() -> impl IntoView {
     view! {
         <LeptosRouter>
             <Routes fallback=move || ()>
                 <Route path=StaticSegment("") view=MainMenu/>
+                <Route path=StaticSegment("host") view=host::NewGame/>
                 <Route
                     path=(StaticSegment("host"), ParamSegment("game_id"))
                     view=|| {
                         view! {
-                            <script type="module">
-                                "
-                                import init, { sendGameEvent as innerSendGameEvent } from '/pkg/game.js';
-
-                                init().finally(() => {
-                                    globalThis['innerSendGameEvent'] = innerSendGameEvent;
-                                    console.log('Module initialised, flushing pending events')
-                                    console.log(pendingEvents);
-                                    while (pendingEvents.length > 0) {
-                                        let event = pendingEvents.shift();
-                                        innerSendGameEvent(event);
-                                    }
-                                });
-                                "
-                            </script>
+                            <LoadGame/>
                             <GameConnectionWrapper>
                                 <SendEventsToBevy/>
                                 <GameStateRouter
@@ -60,29 +82,52 @@ pub fn router() -> impl IntoView {
                                     wait=|| {}
                                     summary=host::Results
                                 />
+                                <HostControls/>
                             </GameConnectionWrapper>
                         }
                     }
                 />
 
-                <Route path=StaticSegment("play") view=player::Join/>
+                // Spectators get the same view as the TV, without the host controls
                 <Route
-                    path=(StaticSegment("play"), ParamSegment("game_id"))
+                    path=(StaticSegment("watch"), ParamSegment("game_id"))
                     view=|| {
                         view! {
+                            <LoadGame/>
                             <GameConnectionWrapper>
+                                <SendEventsToBevy/>
                                 <GameStateRouter
-                                    lobby=player::Lobby
-                                    pre_game=player::PreGame
-                                    race=player::Race
-                                    wait=player::Wait
-                                    summary=player::Summary
+                                    lobby=host::Lobby
+                                    pre_game=host::PreGame
+                                    race=host::Race
+                                    wait=|| {}
+                                    summary=host::Results
                                 />
                             </GameConnectionWrapper>
                         }
                     }
                 />
 
+                <Route path=StaticSegment("play") view=player::Join/>
+                <Route
+                    path=(StaticSegment("play"), ParamSegment("game_id"))
+                    view=|| {
+                        view! {
+                            <GameConnectionWrapper>
+                                <Seated>
+                                    <GameStateRouter
+                                        lobby=player::Lobby
+                                        pre_game=player::PreGame
+                                        race=player::Race
+                                        wait=player::Wait
+                                        summary=player::Summary
+                                    />
+                                </Seated>
+                            </GameConnectionWrapper>
+                        }
+                    }
+                />
+
             </Routes>
         </LeptosRouter>
     }
@@ -132,7 +177,7 @@ where
     SummaryIV: IntoView + 'static,
 {
     let events = use_events();
-    let player_id = use_session_id();
+    let player_id = use_player_id();
 
     let state = Memo::new(move |_| {
         let events = events();
@@ -150,7 +195,7 @@ where
             })
             .unwrap_or_default();
 
-        if GameState::PreGame == game_state && projections::player_has_bet(&events, player_id) {
+        if GameState::PreGame == game_state && events.player_is_ready_to_race(player_id) {
             game_state = GameState::Wait;
         }
 
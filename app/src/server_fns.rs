@@ -1,24 +1,47 @@
 use leptos::prelude::*;
-use shared::models::commands::{API, CommandHandler};
+use shared::models::commands::{API, CommandError, CommandHandler};
 use shared::models::game_code::GameCode;
 use std::future::Future;
 
+pub type CommandResult = Result<(), ServerFnError<CommandError>>;
+
+// What to tell the player when a command fails, refusals explain themselves
+pub fn error_message(err: &ServerFnError<CommandError>) -> String {
+    match err {
+        ServerFnError::WrappedServerError(refusal) => refusal.message().to_string(),
+        _ => "Something went wrong, please try again".to_string(),
+    }
+}
+
 #[cfg(all(target_arch = "wasm32", feature = "hydrate"))]
 pub fn server_fn<C: CommandHandler + API>(
     game_id: GameCode,
     input: &C::Input,
-) -> impl use<C> + Future<Output = Result<(), ServerFnError>> + Send + 'static {
+) -> impl use<C> + Future<Output = CommandResult> + Send + 'static {
     use gloo_net::http::Request;
     use worker::send::SendFuture;
 
     let req = Request::post(&C::url(game_id)).json(input);
 
     SendFuture::new(async {
-        let response = req?.send().await?;
+        let request_error = |err: gloo_net::Error| ServerFnError::Request(err.to_string());
+
+        let response = req
+            .map_err(request_error)?
+            .send()
+            .await
+            .map_err(request_error)?;
         let status_code = response.status();
 
         if status_code != 200 {
-            let error_body = response.text().await?;
+            let error_body = response.text().await.map_err(request_error)?;
+
+            // 4xx responses carry the reason the command was refused
+            if (400..500).contains(&status_code) {
+                if let Ok(refusal) = error_body.parse::<CommandError>() {
+                    return Err(ServerFnError::WrappedServerError(refusal));
+                }
+            }
 
             return Err(ServerFnError::ServerError(format!(
                 "expected 200, got {}, body: {}",
@@ -36,6 +59,6 @@ pub fn server_fn<C: CommandHandler + API>(
 pub fn server_fn<C: CommandHandler + API>(
     game_id: GameCode,
     input: &C::Input,
-) -> impl use<C> + Future<Output = Result<(), ServerFnError>> + Send + 'static {
+) -> impl use<C> + Future<Output = CommandResult> + Send + 'static {
     async { std::future::pending().await }
 }
@@ -12,7 +12,7 @@ pub mod use_websocket;
 pub use use_game_id::*;
 pub use use_websocket::*;
 
-pub fn get_session_id() -> Option<Uuid> {
+pub fn get_player_id() -> Option<Uuid> {
     let cookie_str = web_sys::window()?
         .document()?
         .dyn_into::<web_sys::HtmlDocument>()
@@ -22,20 +22,20 @@ pub fn get_session_id() -> Option<Uuid> {
 
     let cookie = Cookie::split_parse(&cookie_str)
         .filter_map(|it| it.ok())
-        .find(|it| it.name() == "session_id")?;
+        .find(|it| it.name() == "player_id")?;
 
     Uuid::parse_str(cookie.value()).ok()
 }
 
 #[derive(Clone, Copy)]
-struct SessionID(Uuid);
+struct PlayerID(Uuid);
 
-pub fn provide_session_id(session_id: Uuid) {
-    provide_context(SessionID(session_id))
+pub fn provide_player_id(player_id: Uuid) {
+    provide_context(PlayerID(player_id))
 }
 
-pub fn use_session_id() -> Uuid {
-    use_context::<SessionID>().unwrap().0
+pub fn use_player_id() -> Uuid {
+    use_context::<PlayerID>().unwrap().0
 }
 
 #[wasm_bindgen]
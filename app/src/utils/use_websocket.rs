@@ -1,6 +1,5 @@
-use im::vector::Vector;
 use leptos::prelude::*;
-use shared::models::{events::Event, game_code::GameCode};
+use shared::models::{game_code::GameCode, projections::GameView};
 
 #[derive(Debug, Clone, PartialEq)]
 #[allow(dead_code)]
@@ -36,9 +35,7 @@ async fn sleep(ms: i32) {
 }
 
 #[cfg(feature = "hydrate")]
-pub fn create_event_signal(
-    game_id: GameCode,
-) -> (ReadSignal<Connection>, ReadSignal<Vector<Event>>) {
+pub fn create_event_signal(game_id: GameCode) -> (ReadSignal<Connection>, ReadSignal<GameView>) {
     use futures_util::StreamExt;
     use gloo_net::websocket::{futures::WebSocket, Message};
     use leptos::{server_fn::error::NoCustomError, *};
@@ -53,7 +50,7 @@ pub fn create_event_signal(
     };
 
     let (connection, set_connection) = signal(Connection::Connecting);
-    let (events, set_events) = signal(Vector::new());
+    let (events, set_events) = signal(GameView::default());
 
     spawn_local(async move {
         let mut count = 0;
@@ -61,7 +58,7 @@ pub fn create_event_signal(
             let result: Result<(), ServerFnError> = try {
                 let mut socket = WebSocket::open(&url)?;
 
-                set_events(Vector::new());
+                set_events(GameView::default());
 
                 while let Some(msg) = socket.next().await {
                     count = 0;
@@ -80,11 +77,13 @@ pub fn create_event_signal(
                     match event {
                         EventStream::Events(new_events) => {
                             tracing::info!(?new_events, "got new events");
-                            set_events.update(|events| events.append(new_events.into()))
+                            set_events.update(|events| {
+                                new_events.into_iter().for_each(|event| events.apply(event))
+                            })
                         }
                         EventStream::Event(event) => {
                             tracing::info!(?event, "got new event");
-                            set_events.update(|events| events.push_back(event))
+                            set_events.update(|events| events.apply(event))
                         }
                     }
 
@@ -119,24 +118,22 @@ pub fn create_event_signal(
 
 #[cfg(feature = "ssr")]
 #[allow(unused_variables)]
-pub fn create_event_signal(
-    game_id: GameCode,
-) -> (ReadSignal<Connection>, ReadSignal<Vector<Event>>) {
+pub fn create_event_signal(game_id: GameCode) -> (ReadSignal<Connection>, ReadSignal<GameView>) {
     use leptos::prelude::*;
 
     let (connection, _) = signal(Connection::Connecting);
-    let (events, _) = signal(Vector::new());
+    let (events, _) = signal(GameView::default());
 
     (connection, events)
 }
 
 #[derive(Clone)]
-struct EventsContainer(Signal<Vector<Event>>);
+struct EventsContainer(Signal<GameView>);
 
-pub fn provide_events(signal: Signal<Vector<Event>>) {
+pub fn provide_events(signal: Signal<GameView>) {
     provide_context(EventsContainer(signal));
 }
 
-pub fn use_events() -> Signal<Vector<Event>> {
+pub fn use_events() -> Signal<GameView> {
     use_context::<EventsContainer>().unwrap().0
 }
@@ -5,14 +5,13 @@ use std::sync::{
 
 use bevy::{log::tracing, prelude::*, utils::synccell::SyncCell};
 
-use im::Vector;
-use shared::models::{events::Event, events::EventStream};
+use shared::models::{events::EventStream, projections::GameView};
 
 pub struct EventStreamPlugin;
 
 impl Plugin for EventStreamPlugin {
     fn build(&self, app: &mut App) {
-        app.insert_resource(GameEvents(Vector::new()))
+        app.insert_resource(GameEvents(GameView::default()))
             .add_systems(Startup, EventReceiver::init)
             .add_systems(Update, EventReceiver::read);
 
@@ -38,7 +37,7 @@ static EVENT_CHANNEL: LazyLock<EventChannel<EventStream>> = LazyLock::new(|| {
 // #[derive(Resource)]
 // pub struct Seed(pub u32);
 #[derive(Resource, Deref, DerefMut)]
-pub struct GameEvents(pub Vector<Event>);
+pub struct GameEvents(pub GameView);
 
 #[derive(Resource)]
 pub struct EventReceiver(SyncCell<Receiver<EventStream>>);
@@ -55,8 +54,8 @@ impl EventReceiver {
             tracing::info!(?new_events, "game has recieved events from frontend",);
 
             match new_events {
-                EventStream::Events(new_events) => **events = Vector::from(new_events),
-                EventStream::Event(new_event) => events.push_back(new_event),
+                EventStream::Events(new_events) => **events = GameView::from_iter(new_events),
+                EventStream::Event(new_event) => events.apply(new_event),
             }
         }
     }
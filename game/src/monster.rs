@@ -108,10 +108,10 @@ pub fn init_animation(
         };
 
         commands.entity(entity).insert((NamedAnimations {
-            idle: get_timed_animation(monster_info.idle_animation),
-            jump: get_timed_animation(monster_info.jump_animation),
-            dance: get_timed_animation(monster_info.dance_animation),
-            death: get_timed_animation(monster_info.death_animation),
+            idle: get_timed_animation(&monster_info.idle_animation),
+            jump: get_timed_animation(&monster_info.jump_animation),
+            dance: get_timed_animation(&monster_info.dance_animation),
+            death: get_timed_animation(&monster_info.death_animation),
         },));
 
         let graph_handle = graphs.add(graph);
@@ -253,7 +253,7 @@ fn spawn_monster(
         .ok_or("game assets haven't loaded yet")
         .unwrap()
         .models
-        .get(monster.blueprint_name)
+        .get(monster.blueprint_name.as_ref())
         .ok_or_else(|| {
             format!(
                 "failed to find asset for monster: {}, available models: {:?}",
@@ -278,14 +278,14 @@ fn spawn_monster(
     transform.scale *= monster.scale;
 
     commands.spawn((
-        Name::from(monster.name),
+        Name::new(monster.name.clone()),
         MonsterBundle {
             id: MonsterID(*id),
             behaviour: *behaviour,
             start: Start(start.unwrap_or(transform)),
             ..default()
         },
-        MonsterInfo(*monster),
+        MonsterInfo(monster.clone()),
         RaceTimer::default(),
         MonsterGltf(handle.clone()),
         SceneRoot(scene.scenes[0].clone()),
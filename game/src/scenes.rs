@@ -264,3 +264,33 @@ fn scene_manager(events: Res<GameEvents>, mut next_state: ResMut<NextState<Scene
 
     next_state.set(state);
 }
+
+// The scenes are authored with a spawn point per lane for the smallest field, when there are more
+// runners than spawn points the lanes are spread evenly between the first and last spawn point
+pub fn lane_transforms(
+    mut spawn_points: Vec<(usize, Transform)>,
+    lanes: usize,
+) -> Vec<(usize, Transform)> {
+    spawn_points.sort_by_key(|(id, _)| *id);
+
+    if spawn_points.len() >= lanes || spawn_points.len() < 2 {
+        return spawn_points
+            .into_iter()
+            .filter(|(id, _)| (1..=lanes).contains(id))
+            .collect();
+    }
+
+    let (_, first) = spawn_points[0];
+    let (_, last) = spawn_points[spawn_points.len() - 1];
+
+    (0..lanes)
+        .map(|lane| {
+            let mut transform = first;
+            transform.translation = first
+                .translation
+                .lerp(last.translation, lane as f32 / (lanes - 1) as f32);
+
+            (lane + 1, transform)
+        })
+        .collect()
+}
@@ -1,10 +1,9 @@
 use bevy::prelude::*;
-use shared::models::projections;
 
 use crate::{
     event_stream::GameEvents,
     monster::{MonsterBehaviour, SpawnMonster},
-    scenes::{SceneState, pregame::PreGameSpawnPoint},
+    scenes::{SceneState, lane_transforms, pregame::PreGameSpawnPoint},
 };
 
 pub struct LobbyPlugin;
@@ -49,24 +48,20 @@ pub fn spawn_racers(
     mut commands: Commands,
     spawn_points: Query<(&PreGameSpawnPoint, &Transform), Added<PreGameSpawnPoint>>,
 ) {
-    let monsters =
-        projections::monsters(&events, projections::race::race_seed_for_round(&events, 1));
+    let monsters = events.monsters(events.race_seed_for_round(1));
 
-    spawn_points
-        .into_iter()
-        .for_each(move |(spawn_point, transform)| {
-            let monster = monsters
-                .get(spawn_point.id - 1)
-                .ok_or_else(|| format!("failed to find spawn point for monster: {spawn_point:?}"))
-                .copied()
-                .unwrap();
+    let spawn_points = spawn_points
+        .iter()
+        .map(|(spawn_point, transform)| (spawn_point.id, *transform))
+        .collect();
 
-            commands.trigger(SpawnMonster {
-                id: spawn_point.id,
-                transform: *transform,
-                monster,
-                behaviour: MonsterBehaviour::Dancing,
-                ..default()
-            })
-        });
+    for (id, transform) in lane_transforms(spawn_points, monsters.len()) {
+        commands.trigger(SpawnMonster {
+            id,
+            transform,
+            monster: monsters[id - 1].clone(),
+            behaviour: MonsterBehaviour::Dancing,
+            ..default()
+        })
+    }
 }
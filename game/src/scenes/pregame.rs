@@ -1,5 +1,5 @@
 use bevy::prelude::*;
-use shared::models::projections;
+use shared::time::SystemClock;
 
 use crate::{
     event_stream::GameEvents,
@@ -7,7 +7,7 @@ use crate::{
     music::PlayPreGameCountdown,
 };
 
-use super::{SceneMetadata, SceneState};
+use super::{SceneMetadata, SceneState, lane_transforms};
 
 pub struct PreGamePlugin;
 
@@ -80,26 +80,23 @@ fn spawn_monsters(
 ) {
     commands.trigger(DespawnAllMonsters);
 
-    let seed = projections::race::race_seed(&game_events);
-    let monsters = projections::monsters(&game_events, seed);
-
-    spawn_points
-        .into_iter()
-        .for_each(|(spawn_point, transform)| {
-            let monster = monsters
-                .get(spawn_point.id - 1)
-                .ok_or_else(|| "failed to find race point for monster".to_string())
-                .copied()
-                .unwrap();
-
-            commands.trigger(SpawnMonster {
-                transform: *transform,
-                monster,
-                behaviour: MonsterBehaviour::Idle,
-                id: spawn_point.id,
-                ..default()
-            })
-        });
+    let seed = game_events.race_seed();
+    let monsters = game_events.monsters(seed);
+
+    let spawn_points = spawn_points
+        .iter()
+        .map(|(spawn_point, transform)| (spawn_point.id, *transform))
+        .collect();
+
+    for (id, transform) in lane_transforms(spawn_points, monsters.len()) {
+        commands.trigger(SpawnMonster {
+            transform,
+            monster: monsters[id - 1].clone(),
+            behaviour: MonsterBehaviour::Idle,
+            id,
+            ..default()
+        })
+    }
 }
 
 fn init_pregame(
@@ -121,7 +118,7 @@ fn init_pregame(
 
     projection.fov = 0.4;
 
-    if let Some(time) = projections::time_left_in_pregame(&game_events) {
+    if let Some(time) = game_events.time_left_in_pregame(&SystemClock) {
         commands.spawn((
             StateScoped(SceneState::PreGame),
             CountdownTimer(Timer::from_seconds((time - 10) as f32, TimerMode::Once)),
@@ -12,7 +12,7 @@ use crate::{
 };
 
 use super::{
-    RaceState, SceneMetadata,
+    RaceState, SceneMetadata, SceneState, lane_transforms,
     pregame::{PreGameCamera, PreGameSpawnPoint},
 };
 
@@ -20,7 +20,8 @@ pub struct RacePlugin;
 
 impl Plugin for RacePlugin {
     fn build(&self, app: &mut App) {
-        app.register_type::<RaceSpawnPoint>()
+        app.enable_state_scoped_entities::<SceneState>()
+            .register_type::<RaceSpawnPoint>()
             .register_type::<RaceStartCamera>()
             .add_systems(Update, spawn_race_spawn_point_on_scene_load)
             .add_systems(OnEnter(RaceState::PreRace), init_pre_race)
@@ -75,26 +76,23 @@ fn init_pre_race(
 ) {
     commands.trigger(DespawnAllMonsters);
 
-    let seed = projections::race::race_seed(&game_events);
-    let monsters = projections::monsters(&game_events, seed);
-
-    spawn_points
-        .into_iter()
-        .for_each(|(spawn_point, transform)| {
-            let monster = monsters
-                .get(spawn_point.id - 1)
-                .ok_or_else(|| "failed to find race point for monster".to_string())
-                .copied()
-                .unwrap();
-
-            commands.trigger(SpawnMonster {
-                transform: *transform,
-                monster,
-                behaviour: MonsterBehaviour::Idle,
-                id: spawn_point.id,
-                ..default()
-            })
-        });
+    let seed = game_events.race_seed();
+    let monsters = game_events.monsters(seed);
+
+    let spawn_points = spawn_points
+        .iter()
+        .map(|(spawn_point, transform)| (spawn_point.id, *transform))
+        .collect();
+
+    for (id, transform) in lane_transforms(spawn_points, monsters.len()) {
+        commands.trigger(SpawnMonster {
+            transform,
+            monster: monsters[id - 1].clone(),
+            behaviour: MonsterBehaviour::Idle,
+            id,
+            ..default()
+        })
+    }
 
     let position = position.single().unwrap();
     let (mut camera, mut projection) = camera.single_mut().unwrap();
@@ -109,7 +107,29 @@ fn init_pre_race(
 
     projection.fov = 0.4;
 
-    let pre_race_duration = projections::pre_race_duration(&game_events);
+    let track = game_events.track();
+
+    commands
+        .spawn((
+            StateScoped(SceneState::Race),
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(40.0),
+                width: Val::Percent(100.0),
+                align_items: AlignItems::Center,
+                flex_direction: FlexDirection::Column,
+                ..default()
+            },
+        ))
+        .with_children(|parent| {
+            parent.spawn((Text::new(track.name()), TextFont::from_font_size(80.)));
+            parent.spawn((
+                Text::new(track.description()),
+                TextFont::from_font_size(40.),
+            ));
+        });
+
+    let pre_race_duration = game_events.pre_race_duration();
 
     commands.insert_resource(PreRaceTimer(Timer::new(pre_race_duration, TimerMode::Once)));
 
@@ -154,34 +174,32 @@ fn init_race(
     // Don't know why the rotation coming from blender is fucked up
     camera.rotation = position.rotation * Quat::from_rotation_x(-std::f32::consts::FRAC_PI_2);
 
-    let seed = projections::race::race_seed(&game_events);
-    let monsters = projections::monsters(&game_events, seed);
-    let (results, jump) = projections::race::results(&monsters, seed);
+    let monsters = game_events.monsters(game_events.race_seed());
+    let (results, jump) =
+        projections::race::results(&monsters, game_events.track(), game_events.results_seed());
 
     commands.insert_resource(Race((results, jump)));
 
-    race_points
-        .into_iter()
-        .for_each(|(spawn_point, transform)| {
-            let monster = monsters
-                .get(spawn_point.id - 1)
-                .ok_or_else(|| "failed to find race point for monster".to_string())
-                .copied()
-                .unwrap();
-
-            let start = *transform;
-            let mut transform = *transform;
-            transform.translation.x += monster.starting_position;
-
-            commands.trigger(SpawnMonster {
-                transform,
-                start: Some(start),
-                monster,
-                behaviour: MonsterBehaviour::Idle,
-                id: spawn_point.id,
-                ..default()
-            })
-        });
+    let race_points = race_points
+        .iter()
+        .map(|(spawn_point, transform)| (spawn_point.id, *transform))
+        .collect();
+
+    for (id, start) in lane_transforms(race_points, monsters.len()) {
+        let monster = monsters[id - 1].clone();
+
+        let mut transform = start;
+        transform.translation.x += monster.starting_position;
+
+        commands.trigger(SpawnMonster {
+            transform,
+            start: Some(start),
+            monster,
+            behaviour: MonsterBehaviour::Idle,
+            id,
+            ..default()
+        })
+    }
 }
 
 #[derive(Debug, Component)]
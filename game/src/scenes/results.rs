@@ -1,5 +1,4 @@
 use bevy::{log::tracing, prelude::*};
-use shared::models::projections;
 
 use crate::{
     event_stream::GameEvents,
@@ -91,17 +90,18 @@ fn spawn_monsters(
 ) {
     commands.trigger(DespawnAllMonsters);
 
-    let seed = projections::race::race_seed(&game_events);
-    let monsters = projections::monsters(&game_events, seed);
-    let results = projections::results(&game_events).unwrap();
+    let seed = game_events.race_seed();
+    let monsters = game_events.monsters(seed);
+    let results = game_events.results().unwrap();
 
     spawn_points
         .into_iter()
         .for_each(|(spawn_point, transform)| {
             let uuid = match spawn_point.id {
-                1 => results.first,
-                2 => results.second,
-                3 => results.third,
+                1..=3 => match results.place(spawn_point.id) {
+                    Some(uuid) => uuid,
+                    None => return,
+                },
                 id => {
                     tracing::warn!(?id, "spawn point with unrecognised id encountered");
                     return;
@@ -111,7 +111,7 @@ fn spawn_monsters(
             let Some(monster) = monsters
                 .iter()
                 .find(|monster| monster.uuid == uuid)
-                .copied()
+                .cloned()
             else {
                 tracing::error!(?uuid, "couldn't find monster in race results");
                 return;
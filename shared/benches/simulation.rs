@@ -0,0 +1,67 @@
+// Tracks the cost of simulating races, run with `cargo bench -p shared [filter]`. The odds are
+// computed inside a command handler, so they have to stay well inside the Workers CPU budget.
+use std::{
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+use shared::models::{
+    monsters::MONSTERS,
+    projections::{self, race},
+    tracks::Track,
+};
+
+const WARM_UP: Duration = Duration::from_millis(500);
+
+fn bench(filter: Option<&str>, name: &str, mut f: impl FnMut(u32)) {
+    if filter.is_some_and(|filter| !name.contains(filter)) {
+        return;
+    }
+
+    // Warming up also picks how many iterations to time, roughly another WARM_UP's worth
+    let mut iterations = 0;
+    let start = Instant::now();
+
+    while start.elapsed() < WARM_UP {
+        f(black_box(iterations));
+        iterations += 1;
+    }
+
+    let start = Instant::now();
+
+    for seed in 0..iterations {
+        f(black_box(seed));
+    }
+
+    let elapsed = start.elapsed();
+
+    println!(
+        "{name:<32} {:>12.3?}/iter ({iterations} iterations)",
+        elapsed / iterations
+    );
+}
+
+fn main() {
+    let filter = std::env::args().skip(1).find(|arg| !arg.starts_with('-'));
+    let filter = filter.as_deref();
+
+    for runners in [3, 8] {
+        let monsters = &MONSTERS[..runners];
+
+        bench(filter, &format!("race::results/{runners}"), |seed| {
+            black_box(race::results(monsters, Track::Standard, seed));
+        });
+
+        bench(
+            filter,
+            &format!("race::finishing_order/{runners}"),
+            |seed| {
+                black_box(race::finishing_order(monsters, Track::Standard, seed));
+            },
+        );
+
+        bench(filter, &format!("projections::odds/{runners}"), |seed| {
+            black_box(projections::odds(monsters, Track::Standard, seed));
+        });
+    }
+}
@@ -1,7 +1,13 @@
 pub mod cards;
 pub mod commands;
 pub mod events;
+pub mod fairness;
 pub mod game_code;
 pub mod monsters;
 pub mod process_managers;
 pub mod projections;
+pub mod rng;
+pub mod roster;
+pub mod session;
+pub mod timers;
+pub mod tracks;
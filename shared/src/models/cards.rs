@@ -1,6 +1,9 @@
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::models::monsters::Monster;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum TargetKind {
     Player,
     MultiplePlayers(usize),
@@ -30,91 +33,222 @@ pub enum Card {
     Crystals,
 }
 
+// A change to one of a monster's stats while the card is in play
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Modifier {
+    Strength(i32),
+    Dexterity(i32),
+    StartingPosition(f32),
+}
+
+impl Modifier {
+    pub fn apply(&self, monster: &mut Monster) {
+        match *self {
+            Modifier::Strength(amount) => monster.strength += amount,
+            Modifier::Dexterity(amount) => monster.dexterity += amount,
+            Modifier::StartingPosition(amount) => monster.starting_position += amount,
+        }
+    }
+}
+
+// What a card does to the economy when it's played on another player
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlayerEffect {
+    StealBalance { percent: i32 },
+    GiftCrystals { amount: i32 },
+    StealCards { count: usize },
+    BlockCards,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CardDefinition {
+    pub card: Card,
+    pub name: &'static str,
+    pub icon: &'static str,
+    pub description: &'static str,
+    // {perpetrator} is replaced with the name of the player who played the card
+    pub victim_description: Option<&'static str>,
+    pub target_kind: TargetKind,
+    pub free: bool,
+    pub deck_weight: usize,
+    pub modifiers: &'static [Modifier],
+    // Cards played on the same monster whose modifiers are cancelled out by this one
+    pub counters: &'static [Card],
+    pub player_effect: Option<PlayerEffect>,
+}
+
+const MONSTER_CARD: CardDefinition = CardDefinition {
+    card: Card::Poison,
+    name: "",
+    icon: "",
+    description: "",
+    victim_description: None,
+    target_kind: TargetKind::Monster,
+    free: false,
+    deck_weight: 10,
+    modifiers: &[],
+    counters: &[],
+    player_effect: None,
+};
+
+// Every card in the game, in the same order as the variants of `Card`
+pub const CARDS: [CardDefinition; 12] = [
+    CardDefinition {
+        card: Card::Poison,
+        name: "Poison",
+        icon: "/pkg/icons/poison.svg",
+        description: "-3 Strength",
+        modifiers: &[Modifier::Strength(-3)],
+        ..MONSTER_CARD
+    },
+    CardDefinition {
+        card: Card::ExtraRations,
+        name: "Extra Rations",
+        icon: "/pkg/icons/ramen.svg",
+        description: "+2 Strength",
+        modifiers: &[Modifier::Strength(2)],
+        ..MONSTER_CARD
+    },
+    CardDefinition {
+        card: Card::TasteTester,
+        name: "Taste Tester",
+        icon: "/pkg/icons/taste-tester.svg",
+        description: "Blocks Strength Effects",
+        counters: &[Card::Poison, Card::ExtraRations],
+        ..MONSTER_CARD
+    },
+    CardDefinition {
+        card: Card::PsyBlast,
+        name: "Psy Blast",
+        icon: "/pkg/icons/psyblast.svg",
+        description: "-3 Dexterity",
+        modifiers: &[Modifier::Dexterity(-3)],
+        ..MONSTER_CARD
+    },
+    CardDefinition {
+        card: Card::Meditation,
+        name: "Meditation",
+        icon: "/pkg/icons/meditation.svg",
+        description: "+2 Dexterity",
+        modifiers: &[Modifier::Dexterity(2)],
+        ..MONSTER_CARD
+    },
+    CardDefinition {
+        card: Card::TinfoilHat,
+        name: "Tinfoil Hat",
+        icon: "/pkg/icons/helmet.svg",
+        description: "Blocks Dexterity Effects",
+        counters: &[Card::PsyBlast, Card::Meditation],
+        ..MONSTER_CARD
+    },
+    CardDefinition {
+        card: Card::Nepotism,
+        name: "Nepotism",
+        icon: "/pkg/icons/nepotism.svg",
+        description: "Monster starts race 1m ahead",
+        deck_weight: 3,
+        modifiers: &[Modifier::StartingPosition(1.5)],
+        ..MONSTER_CARD
+    },
+    CardDefinition {
+        card: Card::Theft,
+        name: "Theft",
+        icon: "/pkg/icons/theft.svg",
+        description: "Take 20% of a player's Crystals",
+        victim_description: Some("{perpetrator} stole 20% of your crystals!"),
+        target_kind: TargetKind::Player,
+        deck_weight: 8,
+        player_effect: Some(PlayerEffect::StealBalance { percent: 20 }),
+        ..MONSTER_CARD
+    },
+    CardDefinition {
+        card: Card::Extortion,
+        name: "Extortion",
+        icon: "/pkg/icons/extortion.svg",
+        description: "Take 2 random cards from a player",
+        victim_description: Some("{perpetrator} stole 2 of your cards!"),
+        target_kind: TargetKind::Player,
+        deck_weight: 8,
+        player_effect: Some(PlayerEffect::StealCards { count: 2 }),
+        ..MONSTER_CARD
+    },
+    CardDefinition {
+        card: Card::Stupify,
+        name: "Stupify",
+        icon: "/pkg/icons/stupify.svg",
+        description: "(Free Action) Player must speak loudly and in single syllables",
+        victim_description: Some("You must speak loudly and in single syllables!"),
+        target_kind: TargetKind::Player,
+        free: true,
+        deck_weight: 0,
+        ..MONSTER_CARD
+    },
+    CardDefinition {
+        card: Card::Scrutiny,
+        name: "Scrutiny",
+        icon: "/pkg/icons/scrutiny.svg",
+        description: "Up to 2 players cannot play cards this turn",
+        victim_description: Some("{perpetrator} stopped you from playing cards this turn!"),
+        target_kind: TargetKind::MultiplePlayers(2),
+        deck_weight: 4,
+        player_effect: Some(PlayerEffect::BlockCards),
+        ..MONSTER_CARD
+    },
+    CardDefinition {
+        card: Card::Crystals,
+        name: "Crystals",
+        icon: "/pkg/icons/crystals.svg",
+        description: "(Free Action) Give 1 player 1000 Crystals",
+        victim_description: Some("{perpetrator} gave you 1000 Crystals!"),
+        target_kind: TargetKind::Player,
+        free: true,
+        deck_weight: 5,
+        player_effect: Some(PlayerEffect::GiftCrystals { amount: 1000 }),
+        ..MONSTER_CARD
+    },
+];
+
 impl Card {
+    pub fn definition(&self) -> &'static CardDefinition {
+        &CARDS[*self as usize]
+    }
+
     pub fn target_kind(&self) -> TargetKind {
-        match self {
-            Card::Poison => TargetKind::Monster,
-            Card::TasteTester => TargetKind::Monster,
-            Card::ExtraRations => TargetKind::Monster,
-            Card::PsyBlast => TargetKind::Monster,
-            Card::TinfoilHat => TargetKind::Monster,
-            Card::Nepotism => TargetKind::Monster,
-            Card::Meditation => TargetKind::Monster,
-            Card::Theft => TargetKind::Player,
-            Card::Extortion => TargetKind::Player,
-            Card::Stupify => TargetKind::Player,
-            Card::Scrutiny => TargetKind::MultiplePlayers(2),
-            Card::Crystals => TargetKind::Player,
-        }
+        self.definition().target_kind
     }
 
     pub fn name(&self) -> &'static str {
-        match self {
-            Card::Poison => "Poison",
-            Card::TasteTester => "Taste Tester",
-            Card::ExtraRations => "Extra Rations",
-            Card::PsyBlast => "Psy Blast",
-            Card::TinfoilHat => "Tinfoil Hat",
-            Card::Nepotism => "Nepotism",
-            Card::Meditation => "Meditation",
-            Card::Theft => "Theft",
-            Card::Extortion => "Extortion",
-            Card::Stupify => "Stupify",
-            Card::Scrutiny => "Scrutiny",
-            Card::Crystals => "Crystals",
-        }
+        self.definition().name
     }
 
     pub fn icon(&self) -> &'static str {
-        match self {
-            Card::Poison => "/pkg/icons/poison.svg",
-            Card::TasteTester => "/pkg/icons/taste-tester.svg",
-            Card::ExtraRations => "/pkg/icons/ramen.svg",
-            Card::PsyBlast => "/pkg/icons/psyblast.svg",
-            Card::TinfoilHat => "/pkg/icons/helmet.svg",
-            Card::Nepotism => "/pkg/icons/nepotism.svg",
-            Card::Meditation => "/pkg/icons/meditation.svg",
-            Card::Theft => "/pkg/icons/theft.svg",
-            Card::Extortion => "/pkg/icons/extortion.svg",
-            Card::Stupify => "/pkg/icons/stupify.svg",
-            Card::Scrutiny => "/pkg/icons/scrutiny.svg",
-            Card::Crystals => "/pkg/icons/crystals.svg",
-        }
+        self.definition().icon
     }
 
     pub fn description(&self) -> &'static str {
-        match self {
-            Card::Poison => "-3 Strength",
-            Card::TasteTester => "Blocks Strength Effects",
-            Card::ExtraRations => "+2 Strength",
-            Card::PsyBlast => "-3 Dexterity",
-            Card::TinfoilHat => "Blocks Dexterity Effects",
-            Card::Nepotism => "Monster starts race 1m ahead",
-            Card::Meditation => "+2 Dexterity",
-            Card::Theft => "Take 20% of a player's Crystals",
-            Card::Extortion => "Take 2 random cards from a player",
-            Card::Stupify => "(Free Action) Player must speak loudly and in single syllables",
-            Card::Scrutiny => "Up to 2 players cannot play cards this turn",
-            Card::Crystals => "(Free Action) Give 1 player 1000 Crystals",
-        }
+        self.definition().description
     }
 
     pub fn victim_description(&self, perpetrator: &str) -> String {
-        match self {
-            Card::Theft => format!("{perpetrator} stole 20% of your crystals!"),
-            Card::Extortion => format!("{perpetrator} stole 2 of your cards!"),
-            Card::Stupify => "You must speak loudly and in single syllables!".to_string(),
-            Card::Scrutiny => format!("{perpetrator} stopped you from playing cards this turn!"),
-            Card::Crystals => format!("{perpetrator} gave you 1000 Crystals!"),
-            _ => "N/A".to_owned(),
+        match self.definition().victim_description {
+            Some(description) => description.replace("{perpetrator}", perpetrator),
+            None => "N/A".to_owned(),
         }
     }
 
     pub fn is_free(&self) -> bool {
-        match self {
-            Card::Crystals => true,
-            Card::Stupify => true,
-            _ => false,
+        self.definition().free
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::CARDS;
+
+    #[test]
+    fn registry_is_in_variant_order() {
+        for (index, definition) in CARDS.iter().enumerate() {
+            assert_eq!(definition.card as usize, index, "{}", definition.name);
         }
     }
 }
@@ -1,21 +1,103 @@
-use std::fmt::Display;
+use std::{fmt::Display, str::FromStr};
 
-use im::Vector;
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use uuid::Uuid;
 
-use super::{events::Event, game_code::GameCode};
+use super::{events::Event, game_code::GameCode, projections::GameView};
+use crate::time::Clock;
 
 pub trait CommandHandler {
-    type Input: Serialize + DeserializeOwned + std::fmt::Debug + Send + 'static;
+    type Input: Serialize + DeserializeOwned + std::fmt::Debug + Clone + Send + 'static;
 
     fn handle(
         session_id: Uuid,
-        events: &Vector<Event>,
+        game: &GameView,
         input: Self::Input,
+        clock: &dyn Clock,
     ) -> anyhow::Result<Vec<Event>>;
 }
 
+// The ways a command can be refused. Handlers bail with one of these so the server can answer with
+// the right status and the client can show the player why, anything else is an internal error.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "message")]
+pub enum CommandError {
+    NotFound(String),
+    Forbidden(String),
+    InvalidPhase(String),
+    InsufficientFunds(String),
+    Validation(String),
+    // The game changed while the command was being handled, it's safe to send it again
+    Conflict(String),
+}
+
+impl CommandError {
+    pub fn not_found(message: impl Into<String>) -> Self {
+        CommandError::NotFound(message.into())
+    }
+
+    pub fn forbidden(message: impl Into<String>) -> Self {
+        CommandError::Forbidden(message.into())
+    }
+
+    pub fn invalid_phase(message: impl Into<String>) -> Self {
+        CommandError::InvalidPhase(message.into())
+    }
+
+    pub fn insufficient_funds(message: impl Into<String>) -> Self {
+        CommandError::InsufficientFunds(message.into())
+    }
+
+    pub fn validation(message: impl Into<String>) -> Self {
+        CommandError::Validation(message.into())
+    }
+
+    pub fn conflict(message: impl Into<String>) -> Self {
+        CommandError::Conflict(message.into())
+    }
+
+    pub fn retryable(&self) -> bool {
+        matches!(self, CommandError::Conflict(_))
+    }
+
+    pub fn message(&self) -> &str {
+        match self {
+            CommandError::NotFound(message)
+            | CommandError::Forbidden(message)
+            | CommandError::InvalidPhase(message)
+            | CommandError::InsufficientFunds(message)
+            | CommandError::Validation(message)
+            | CommandError::Conflict(message) => message,
+        }
+    }
+
+    pub fn status_code(&self) -> u16 {
+        match self {
+            CommandError::NotFound(_) => 404,
+            CommandError::Forbidden(_) => 403,
+            CommandError::InvalidPhase(_) | CommandError::Conflict(_) => 409,
+            CommandError::InsufficientFunds(_) | CommandError::Validation(_) => 422,
+        }
+    }
+}
+
+impl Display for CommandError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.message())
+    }
+}
+
+impl std::error::Error for CommandError {}
+
+// Parses the body of a failed command, for `ServerFnError`
+impl FromStr for CommandError {
+    type Err = serde_json::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        serde_json::from_str(s)
+    }
+}
+
 pub trait API {
     fn url(game_id: impl Display) -> String;
 
@@ -35,6 +117,9 @@ pub use create_game::CreateGame;
 pub mod join_game;
 pub use join_game::JoinGame;
 
+pub mod watch_game;
+pub use watch_game::WatchGame;
+
 pub mod change_profile;
 pub use change_profile::ChangeProfile;
 
@@ -47,6 +132,9 @@ pub use start_round::StartRound;
 pub mod place_bets;
 pub use place_bets::PlaceBets;
 
+pub mod ready_to_race;
+pub use ready_to_race::ReadyToRace;
+
 pub mod buy_card;
 pub use buy_card::BuyCard;
 
@@ -65,7 +153,34 @@ pub use finish_game::FinishGame;
 pub mod borrow_money;
 pub use borrow_money::BorrowMoney;
 
-#[derive(Debug, PartialEq, Deserialize, Serialize)]
+pub mod kick_player;
+pub use kick_player::KickPlayer;
+
+pub mod pause_game;
+pub use pause_game::PauseGame;
+
+pub mod resume_game;
+pub use resume_game::ResumeGame;
+
+pub mod extend_timer;
+pub use extend_timer::ExtendTimer;
+
+pub mod void_round;
+pub use void_round::VoidRound;
+
+pub mod end_game;
+pub use end_game::EndGame;
+
+pub mod connect_player;
+pub use connect_player::ConnectPlayer;
+
+pub mod disconnect_player;
+pub use disconnect_player::DisconnectPlayer;
+
+pub mod drop_idle_player;
+pub use drop_idle_player::DropIdlePlayer;
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 pub enum Command {
     CreateGame(create_game::Input),
     JoinGame(join_game::Input),
@@ -78,6 +193,7 @@ pub enum Command {
     PlaceBets(place_bets::Input),
     FinishRace(finish_race::Input),
     FinishGame(finish_game::Input),
+    DropIdlePlayer(drop_idle_player::Input),
 }
 
 impl CommandHandler for Command {
@@ -85,21 +201,81 @@ impl CommandHandler for Command {
 
     fn handle(
         session_id: Uuid,
-        events: &Vector<Event>,
+        game: &GameView,
         input: Self::Input,
+        clock: &dyn Clock,
     ) -> anyhow::Result<Vec<Event>> {
         match input {
-            Command::CreateGame(input) => CreateGame::handle(session_id, events, input),
-            Command::JoinGame(input) => JoinGame::handle(session_id, events, input),
-            Command::ChangeProfile(input) => ChangeProfile::handle(session_id, events, input),
-            Command::ReadyPlayer(input) => ReadyPlayer::handle(session_id, events, input),
-            Command::StartRound(input) => StartRound::handle(session_id, events, input),
-            Command::BuyCard(input) => BuyCard::handle(session_id, events, input),
-            Command::PlayCard(input) => PlayCard::handle(session_id, events, input),
-            Command::StartRace(input) => StartRace::handle(session_id, events, input),
-            Command::PlaceBets(input) => PlaceBets::handle(session_id, events, input),
-            Command::FinishRace(input) => FinishRace::handle(session_id, events, input),
-            Command::FinishGame(input) => FinishGame::handle(session_id, events, input),
+            Command::CreateGame(input) => CreateGame::handle(session_id, game, input, clock),
+            Command::JoinGame(input) => JoinGame::handle(session_id, game, input, clock),
+            Command::ChangeProfile(input) => ChangeProfile::handle(session_id, game, input, clock),
+            Command::ReadyPlayer(input) => ReadyPlayer::handle(session_id, game, input, clock),
+            Command::StartRound(input) => StartRound::handle(session_id, game, input, clock),
+            Command::BuyCard(input) => BuyCard::handle(session_id, game, input, clock),
+            Command::PlayCard(input) => PlayCard::handle(session_id, game, input, clock),
+            Command::StartRace(input) => StartRace::handle(session_id, game, input, clock),
+            Command::PlaceBets(input) => PlaceBets::handle(session_id, game, input, clock),
+            Command::FinishRace(input) => FinishRace::handle(session_id, game, input, clock),
+            Command::FinishGame(input) => FinishGame::handle(session_id, game, input, clock),
+            Command::DropIdlePlayer(input) => {
+                DropIdlePlayer::handle(session_id, game, input, clock)
+            }
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use anyhow::anyhow;
+    use im::vector;
+    use serde_json::json;
+    use uuid::Uuid;
+
+    use crate::models::{
+        events::{Event, Settings},
+        game_code::GameCode,
+        projections::GameView,
+    };
+    use crate::time::SystemClock;
+
+    use super::{BuyCard, CommandError, CommandHandler, StartRound};
+
+    #[test]
+    fn refusals_are_typed() -> anyhow::Result<()> {
+        let game = GameView::from(&vector![Event::GameCreated {
+            game_id: GameCode::random(),
+            settings: Settings::default(),
+            host: Uuid::nil(),
+        }]);
+
+        let refusal = |result: anyhow::Result<Vec<Event>>| -> anyhow::Result<CommandError> {
+            result
+                .err()
+                .ok_or_else(|| anyhow!("failed to fail"))?
+                .downcast::<CommandError>()
+        };
+
+        let error = refusal(BuyCard::handle(Uuid::new_v4(), &game, (), &SystemClock))?;
+        assert_eq!(error, CommandError::not_found("Player does not exist"));
+        assert_eq!(error.status_code(), 404);
+
+        let error = refusal(StartRound::handle(Uuid::new_v4(), &game, (), &SystemClock))?;
+        assert_eq!(error.status_code(), 403);
+
+        assert_eq!(
+            serde_json::to_value(&error)?,
+            json!({ "kind": "Forbidden", "message": "players may not start the round" })
+        );
+        assert_eq!(
+            serde_json::to_string(&error)?.parse::<CommandError>()?,
+            error
+        );
+        assert!(!error.retryable());
+
+        let conflict = CommandError::conflict("the game is busy, please try again");
+        assert_eq!(conflict.status_code(), 409);
+        assert!(conflict.retryable());
+
+        Ok(())
+    }
+}
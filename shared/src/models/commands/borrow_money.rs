@@ -1,14 +1,14 @@
 use std::fmt::Display;
 
-use anyhow::{bail, Result};
-use im::Vector;
+use anyhow::{Result, bail};
 use serde::{Deserialize, Serialize};
 use tracing::instrument;
 use uuid::Uuid;
 
-use crate::models::{events::Event, projections};
+use crate::models::{events::Event, projections::GameView};
+use crate::time::Clock;
 
-use super::{CommandHandler, API};
+use super::{API, CommandError, CommandHandler};
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Input {
@@ -27,46 +27,52 @@ impl API for BorrowMoney {
 impl CommandHandler for BorrowMoney {
     type Input = Input;
 
-    #[instrument(name = "BorrowMoney::handle", skip(events), err)]
+    #[instrument(name = "BorrowMoney::handle", skip(game), err)]
     fn handle(
         session_id: Uuid,
-        events: &Vector<Event>,
+        game: &GameView,
         Input { amount }: Self::Input,
+        _clock: &dyn Clock,
     ) -> Result<Vec<Event>> {
-        if events.len() == 0 {
-            bail!("can't borrow money from a game that doesn't exist");
+        if game.is_empty() {
+            bail!(CommandError::not_found(
+                "can't borrow money from a game that doesn't exist"
+            ));
         }
 
         if amount == 0 {
             return Ok(vec![]);
         }
 
-        if !projections::player_exists(events, session_id) {
-            bail!("only real players can borrow money");
+        if !game.player_exists(session_id) {
+            bail!(CommandError::forbidden(
+                "only real players can borrow money"
+            ));
         }
 
-        let debt = projections::debt(events, session_id) as u32;
-        let Some(balance) = projections::all_account_balances(events)
-            .get(&session_id)
-            .copied()
-        else {
-            bail!("player doesn't exist");
+        let debt = game.debt(session_id);
+        let Some(balance) = game.all_account_balances().get(&session_id).copied() else {
+            bail!(CommandError::not_found("player doesn't exist"));
         };
 
         if amount < 0 && -1 * amount > balance {
-            bail!("cannot payback more money than you have");
+            bail!(CommandError::insufficient_funds(
+                "cannot payback more money than you have"
+            ));
         }
 
         let new_debt = debt as i32 + amount;
 
-        let maximum_debt = projections::maximum_debt(events);
+        let maximum_debt = game.maximum_debt();
 
         if new_debt > maximum_debt {
-            bail!("cannot borrow more than ${maximum_debt}");
+            bail!(CommandError::validation(format!(
+                "cannot borrow more than ${maximum_debt}"
+            )));
         }
 
         if new_debt < 0 {
-            bail!("cannot payback more than you owe");
+            bail!(CommandError::validation("cannot payback more than you owe"));
         }
 
         match amount {
@@ -1,10 +1,10 @@
-use anyhow::{bail, Result};
-use im::Vector;
+use anyhow::{Result, bail};
 use uuid::Uuid;
 
-use crate::models::{events::Event, projections};
+use crate::models::{events::Event, projections::GameView};
+use crate::time::Clock;
 
-use super::{CommandHandler, API};
+use super::{API, CommandError, CommandHandler};
 
 pub type Input = ();
 
@@ -20,22 +20,34 @@ impl API for BuyCard {
 impl CommandHandler for BuyCard {
     type Input = Input;
 
-    fn handle(session_id: Uuid, events: &Vector<Event>, _input: Input) -> Result<Vec<Event>> {
-        if !projections::player_exists(events, session_id) {
-            bail!("Player does not exist");
+    fn handle(
+        session_id: Uuid,
+        game: &GameView,
+        _input: Input,
+        _clock: &dyn Clock,
+    ) -> Result<Vec<Event>> {
+        if !game.player_exists(session_id) {
+            bail!(CommandError::not_found("Player does not exist"));
         }
 
-        if projections::cards_in_hand(events, session_id).len() >= 5 {
-            bail!("Player already has 5 cards in hand");
+        let settings = game.settings();
+
+        if game.cards_in_hand(session_id).len() >= settings.hand_limit {
+            bail!(CommandError::validation(format!(
+                "Player already has {} cards in hand",
+                settings.hand_limit
+            )));
         }
 
-        if projections::account_balance(events, session_id) < 100 {
-            bail!("Player does not have enough money");
+        if game.account_balance(session_id) < settings.card_price {
+            bail!(CommandError::insufficient_funds(
+                "Player does not have enough money"
+            ));
         }
 
         Ok(vec![Event::BoughtCard {
             session_id: session_id,
-            card: projections::draw_n_cards_from_deck::<1>(events, 0)[0],
+            card: game.draw_n_cards_from_deck::<1>()[0],
         }])
     }
 }
@@ -1,17 +1,17 @@
 use std::fmt::Display;
 
-use anyhow::{bail, Result};
-use im::Vector;
+use anyhow::{Result, bail};
 use serde::{Deserialize, Serialize};
 use tracing::instrument;
 use uuid::Uuid;
 
 use crate::models::{
     events::Event,
-    projections::{self, PlayerInfo},
+    projections::{GameView, PlayerInfo},
 };
+use crate::time::Clock;
 
-use super::{CommandHandler, API};
+use super::{API, CommandError, CommandHandler};
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Input {
@@ -34,16 +34,25 @@ impl CommandHandler for ChangeProfile {
     type Input = Input;
 
     #[instrument(skip_all, fields(input), err)]
-    fn handle(session_id: Uuid, events: &Vector<Event>, input: Self::Input) -> Result<Vec<Event>> {
-        if !projections::player_exists(events, session_id) {
-            bail!("cannot modify player that doesn't exist");
+    fn handle(
+        session_id: Uuid,
+        game: &GameView,
+        input: Self::Input,
+        _clock: &dyn Clock,
+    ) -> Result<Vec<Event>> {
+        if !game.player_exists(session_id) {
+            bail!(CommandError::not_found(
+                "cannot modify player that doesn't exist"
+            ));
         }
 
-        if projections::game_has_started(events) {
-            bail!("cannot modify profile after game has started");
+        if game.game_has_started() {
+            bail!(CommandError::invalid_phase(
+                "cannot modify profile after game has started"
+            ));
         }
 
-        match projections::player_info(events, session_id) {
+        match game.player_info(session_id) {
             Some(PlayerInfo { name, .. }) if name == input.name => Ok(vec![]),
             _ => Ok(vec![Event::ChangedProfile {
                 session_id,
@@ -63,7 +72,9 @@ mod test {
         commands::{change_profile, ChangeProfile, CommandHandler},
         events::{Event, Settings},
         game_code::GameCode,
+        projections::GameView,
     };
+    use crate::time::SystemClock;
 
     #[test]
     fn cannot_modify_player_that_doesnt_exist() -> anyhow::Result<()> {
@@ -73,7 +84,8 @@ mod test {
         let events = vector![
             Event::GameCreated {
                 game_id: GameCode::random(),
-                settings: Settings::default()
+                settings: Settings::default(),
+                host: Uuid::nil()
             },
             Event::PlayerJoined {
                 session_id: a,
@@ -84,11 +96,16 @@ mod test {
 
         assert_eq!(
             "cannot modify player that doesn't exist",
-            ChangeProfile::handle(b, &events, change_profile::Input { name: "B".into() })
-                .err()
-                .ok_or_else(|| anyhow!("failed to fail"))?
-                .root_cause()
-                .to_string(),
+            ChangeProfile::handle(
+                b,
+                &GameView::from(&events),
+                change_profile::Input { name: "B".into() },
+                &SystemClock
+            )
+            .err()
+            .ok_or_else(|| anyhow!("failed to fail"))?
+            .root_cause()
+            .to_string(),
         );
 
         Ok(())
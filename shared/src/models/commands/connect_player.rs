@@ -0,0 +1,39 @@
+use anyhow::Result;
+use tracing::instrument;
+use uuid::Uuid;
+
+use super::CommandHandler;
+use crate::models::{
+    events::Event,
+    projections::{GameView, Presence},
+};
+use crate::time::Clock;
+
+pub type Input = ();
+
+// Issued by the server when a player's websocket connects, there's no route for it
+#[derive(Default)]
+pub struct ConnectPlayer;
+
+impl CommandHandler for ConnectPlayer {
+    type Input = ();
+
+    #[instrument(name = "ConnectPlayer::handle", skip(game), err)]
+    fn handle(
+        session_id: Uuid,
+        game: &GameView,
+        _input: Self::Input,
+        _clock: &dyn Clock,
+    ) -> Result<Vec<Event>> {
+        // The host and anyone who hasn't joined yet connect too
+        let Some(info) = game.player_info(session_id) else {
+            return Ok(vec![]);
+        };
+
+        if game.game_finished() || info.presence == Presence::Connected {
+            return Ok(vec![]);
+        }
+
+        Ok(vec![Event::PlayerConnected { session_id }])
+    }
+}
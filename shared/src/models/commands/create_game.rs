@@ -1,14 +1,18 @@
 use std::fmt::Display;
 
 use anyhow::{Result, bail};
-use im::Vector;
 use serde::{Deserialize, Serialize};
 use tracing::instrument;
 use uuid::Uuid;
 
-use crate::models::events::{Event, Settings};
+use crate::models::{
+    events::{Event, Settings},
+    projections::GameView,
+    roster::roster,
+};
+use crate::time::Clock;
 
-use super::{API, CommandHandler, GameCode, HasGameCode};
+use super::{API, CommandError, CommandHandler, GameCode, HasGameCode};
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Input {
@@ -39,14 +43,173 @@ impl CommandHandler for CreateGame {
     type Input = Input;
 
     #[instrument(name = "CreateGame::handle", err)]
-    fn handle(session_id: Uuid, events: &Vector<Event>, input: Self::Input) -> Result<Vec<Event>> {
-        if !events.is_empty() {
-            bail!("create game cannot be called after the game has already been created");
+    fn handle(
+        session_id: Uuid,
+        game: &GameView,
+        input: Self::Input,
+        _clock: &dyn Clock,
+    ) -> Result<Vec<Event>> {
+        if !game.is_empty() {
+            bail!(CommandError::invalid_phase(
+                "create game cannot be called after the game has already been created"
+            ));
         }
 
-        Ok(vec![Event::GameCreated {
+        let settings = input.settings;
+
+        if !(1..=20).contains(&settings.rounds) {
+            bail!(CommandError::validation("rounds must be between 1 and 20"));
+        }
+
+        if !(3..=8).contains(&settings.field_size) {
+            bail!(CommandError::validation(
+                "field size must be between 3 and 8 monsters"
+            ));
+        }
+
+        if !(1..=15).contains(&settings.max_players) {
+            bail!(CommandError::validation(
+                "maximum players must be between 1 and 15"
+            ));
+        }
+
+        if !(1..=10).contains(&settings.hand_limit) {
+            bail!(CommandError::validation(
+                "hand limit must be between 1 and 10"
+            ));
+        }
+
+        if settings.starting_cards > settings.hand_limit {
+            bail!(CommandError::validation(
+                "starting cards cannot exceed the hand limit"
+            ));
+        }
+
+        if !(100..=100_000).contains(&settings.starting_balance) {
+            bail!(CommandError::validation(
+                "starting balance must be between 100 and 100000"
+            ));
+        }
+
+        if !(0..=settings.starting_balance).contains(&settings.card_price) {
+            bail!(CommandError::validation(
+                "card price must be between 0 and the starting balance"
+            ));
+        }
+
+        if !(0.0..=1.0).contains(&settings.interest_rate) {
+            bail!(CommandError::validation(
+                "interest rate must be between 0% and 100%"
+            ));
+        }
+
+        if !(10..=600).contains(&settings.pre_game_timeout) {
+            bail!(CommandError::validation(
+                "pre game timeout must be between 10 and 600 seconds"
+            ));
+        }
+
+        if !(5..=120).contains(&settings.summary_duration) {
+            bail!(CommandError::validation(
+                "summary duration must be between 5 and 120 seconds"
+            ));
+        }
+
+        if !(10..=600).contains(&settings.afk_timeout) {
+            bail!(CommandError::validation(
+                "AFK timeout must be between 10 and 600 seconds"
+            ));
+        }
+
+        let mut events = vec![Event::GameCreated {
             game_id: input.code,
-            settings: input.settings,
-        }])
+            settings,
+            host: session_id,
+        }];
+
+        let roster = roster();
+
+        if !roster.is_built_in() {
+            events.push(Event::RosterChosen {
+                monsters: roster.definitions(),
+            });
+        }
+
+        Ok(events)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use anyhow::anyhow;
+    use uuid::Uuid;
+
+    use crate::models::{
+        commands::{CommandHandler, CreateGame, create_game},
+        events::Settings,
+        game_code::GameCode,
+        projections::GameView,
+    };
+    use crate::time::SystemClock;
+
+    #[test]
+    fn settings_are_range_checked() -> anyhow::Result<()> {
+        let create = |settings| {
+            CreateGame::handle(
+                Uuid::new_v4(),
+                &GameView::default(),
+                create_game::Input {
+                    code: GameCode::random(),
+                    settings,
+                },
+                &SystemClock,
+            )
+        };
+
+        assert!(create(Settings::default()).is_ok());
+
+        assert_eq!(
+            "starting cards cannot exceed the hand limit",
+            create(Settings {
+                starting_cards: 4,
+                hand_limit: 3,
+                ..Default::default()
+            })
+            .err()
+            .ok_or_else(|| anyhow!("failed to fail"))?
+            .root_cause()
+            .to_string(),
+        );
+
+        assert!(
+            create(Settings {
+                max_players: 16,
+                ..Default::default()
+            })
+            .is_err()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn the_creator_hosts_the_game() -> anyhow::Result<()> {
+        let [host, player] = [Uuid::new_v4(), Uuid::new_v4()];
+
+        let game = GameView::from_iter(CreateGame::handle(
+            host,
+            &GameView::default(),
+            create_game::Input {
+                code: GameCode::random(),
+                settings: Settings::default(),
+            },
+            &SystemClock,
+        )?);
+
+        assert!(game.is_host(host));
+        assert!(!game.is_host(player));
+        assert!(!game.is_host(Uuid::nil()));
+
+        Ok(())
     }
 }
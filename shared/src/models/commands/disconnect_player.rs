@@ -0,0 +1,97 @@
+use anyhow::Result;
+use tracing::instrument;
+use uuid::Uuid;
+
+use super::CommandHandler;
+use crate::models::{
+    events::Event,
+    projections::{GameView, Presence},
+};
+use crate::time::Clock;
+
+pub type Input = ();
+
+// Issued by the server when a player's websocket closes, there's no route for it
+#[derive(Default)]
+pub struct DisconnectPlayer;
+
+impl CommandHandler for DisconnectPlayer {
+    type Input = ();
+
+    #[instrument(name = "DisconnectPlayer::handle", skip(game), err)]
+    fn handle(
+        session_id: Uuid,
+        game: &GameView,
+        _input: Self::Input,
+        clock: &dyn Clock,
+    ) -> Result<Vec<Event>> {
+        let Some(info) = game.player_info(session_id) else {
+            return Ok(vec![]);
+        };
+
+        if game.game_finished() || info.presence != Presence::Connected {
+            return Ok(vec![]);
+        }
+
+        Ok(vec![Event::PlayerDisconnected {
+            session_id,
+            time: clock.timestamp(),
+        }])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use im::vector;
+    use uuid::Uuid;
+
+    use crate::models::{
+        commands::{CommandHandler, ConnectPlayer, DisconnectPlayer},
+        events::Event,
+        projections::{GameView, Presence},
+    };
+    use crate::time::SystemClock;
+
+    #[test]
+    fn presence_follows_the_websocket() -> anyhow::Result<()> {
+        let a = Uuid::new_v4();
+
+        let mut game = GameView::from(&vector![
+            Event::new_game(),
+            Event::PlayerJoined {
+                session_id: a,
+                name: "A".into(),
+                initial_cards: vec![],
+            },
+        ]);
+
+        // Already connected from joining, and the host isn't a player
+        assert_eq!(ConnectPlayer::handle(a, &game, (), &SystemClock)?, vec![]);
+        assert_eq!(
+            DisconnectPlayer::handle(Uuid::new_v4(), &game, (), &SystemClock)?,
+            vec![]
+        );
+
+        for event in DisconnectPlayer::handle(a, &game, (), &SystemClock)? {
+            game.apply(event);
+        }
+        assert!(matches!(
+            game.player_info(a).map(|info| info.presence),
+            Some(Presence::Away(_))
+        ));
+        assert_eq!(
+            DisconnectPlayer::handle(a, &game, (), &SystemClock)?,
+            vec![]
+        );
+
+        for event in ConnectPlayer::handle(a, &game, (), &SystemClock)? {
+            game.apply(event);
+        }
+        assert_eq!(
+            game.player_info(a).map(|info| info.presence),
+            Some(Presence::Connected)
+        );
+
+        Ok(())
+    }
+}
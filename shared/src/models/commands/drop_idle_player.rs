@@ -0,0 +1,49 @@
+use anyhow::{Result, bail};
+use serde::{Deserialize, Serialize};
+use tracing::instrument;
+use uuid::Uuid;
+
+use super::{CommandError, CommandHandler};
+use crate::{
+    models::{events::Event, projections::GameView, session::SYSTEM},
+    time::*,
+};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Input {
+    pub session_id: Uuid,
+}
+
+pub struct DropIdlePlayer;
+
+impl CommandHandler for DropIdlePlayer {
+    type Input = Input;
+
+    #[instrument(name = "DropIdlePlayer::handle", skip(game), err)]
+    fn handle(
+        session_id: Uuid,
+        game: &GameView,
+        input: Self::Input,
+        clock: &dyn Clock,
+    ) -> Result<Vec<Event>> {
+        if session_id != SYSTEM {
+            bail!(CommandError::forbidden(
+                "players may not drop other players"
+            ));
+        }
+
+        let Some(deadline) = game.afk_deadline(input.session_id) else {
+            bail!(CommandError::invalid_phase("player is not away"));
+        };
+
+        if clock.now() < deadline {
+            bail!(CommandError::invalid_phase(
+                "player hasn't been away for long enough"
+            ));
+        }
+
+        Ok(vec![Event::PlayerLeft {
+            session_id: input.session_id,
+        }])
+    }
+}
@@ -0,0 +1,53 @@
+use std::fmt::Display;
+
+use anyhow::{Result, bail};
+use tracing::instrument;
+use uuid::Uuid;
+
+use super::{API, CommandError, CommandHandler};
+use crate::models::{events::Event, projections::GameView};
+use crate::time::Clock;
+
+pub type Input = ();
+
+#[derive(Default)]
+pub struct EndGame;
+
+impl API for EndGame {
+    fn url(game_id: impl Display) -> String {
+        format!("/api/object/game/by_code/{}/commands/end_game", game_id)
+    }
+}
+
+impl CommandHandler for EndGame {
+    type Input = ();
+
+    #[instrument(name = "EndGame::handle", skip(game), err)]
+    fn handle(
+        session_id: Uuid,
+        game: &GameView,
+        _input: Self::Input,
+        clock: &dyn Clock,
+    ) -> Result<Vec<Event>> {
+        if !game.is_host(session_id) {
+            bail!(CommandError::forbidden("only the host can end the game"));
+        }
+
+        if game.game_finished() {
+            bail!(CommandError::invalid_phase("the game is already over"));
+        }
+
+        let mut events = vec![];
+
+        // Nobody should lose their stake on a race that's never going to finish
+        if game.currently_betting().is_some() || game.currently_racing().is_some() {
+            events.push(Event::RoundVoided {
+                time: clock.timestamp(),
+            });
+        }
+
+        events.push(Event::GameFinished);
+
+        Ok(events)
+    }
+}
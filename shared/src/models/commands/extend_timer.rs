@@ -0,0 +1,56 @@
+use std::fmt::Display;
+
+use anyhow::{Result, bail};
+use serde::{Deserialize, Serialize};
+use tracing::instrument;
+use uuid::Uuid;
+
+use super::{API, CommandError, CommandHandler};
+use crate::models::{events::Event, projections::GameView};
+use crate::time::Clock;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Input {
+    pub seconds: u32,
+}
+
+#[derive(Default)]
+pub struct ExtendTimer;
+
+impl API for ExtendTimer {
+    fn url(game_id: impl Display) -> String {
+        format!("/api/object/game/by_code/{}/commands/extend_timer", game_id)
+    }
+}
+
+impl CommandHandler for ExtendTimer {
+    type Input = Input;
+
+    #[instrument(name = "ExtendTimer::handle", skip(game), err)]
+    fn handle(
+        session_id: Uuid,
+        game: &GameView,
+        input: Self::Input,
+        _clock: &dyn Clock,
+    ) -> Result<Vec<Event>> {
+        if !game.is_host(session_id) {
+            bail!(CommandError::forbidden(
+                "only the host can extend the timer"
+            ));
+        }
+
+        if game.currently_betting().is_none() || game.pregame_deadline().is_none() {
+            bail!(CommandError::invalid_phase("there is no timer running"));
+        }
+
+        if !(1..=300).contains(&input.seconds) {
+            bail!(CommandError::validation(
+                "the timer can be extended by between 1 and 300 seconds"
+            ));
+        }
+
+        Ok(vec![Event::TimerExtended {
+            seconds: input.seconds,
+        }])
+    }
+}
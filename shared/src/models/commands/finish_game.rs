@@ -1,10 +1,10 @@
 use anyhow::bail;
 use tracing::instrument;
-use uuid::Uuid;
 
-use crate::models::{events::Event, projections};
+use crate::models::{events::Event, projections::GameView, session::SYSTEM};
+use crate::time::Clock;
 
-use super::CommandHandler;
+use super::{CommandError, CommandHandler};
 
 pub type Input = ();
 
@@ -13,24 +13,63 @@ pub struct FinishGame;
 impl CommandHandler for FinishGame {
     type Input = ();
 
-    #[instrument(skip(events), err)]
+    #[instrument(skip(game), err)]
     fn handle(
         session_id: uuid::Uuid,
-        events: &im::Vector<Event>,
+        game: &GameView,
         input: Self::Input,
+        _clock: &dyn Clock,
     ) -> anyhow::Result<Vec<Event>> {
-        if session_id != Uuid::nil() {
-            bail!("players may not finish the game");
+        if session_id != SYSTEM {
+            bail!(CommandError::forbidden("players may not finish the game"));
         }
 
-        if !projections::game_finished(events) {
-            bail!("game is not finished");
+        if !game.game_finished() {
+            bail!(CommandError::invalid_phase("game is not finished"));
         }
 
-        if matches!(events.last(), Some(Event::GameFinished)) {
+        if game.finish_recorded() {
             return Ok(vec![]);
         }
 
         Ok(vec![Event::GameFinished])
     }
 }
+
+#[cfg(test)]
+mod test {
+    use im::vector;
+    use uuid::Uuid;
+
+    use crate::models::{
+        commands::{CommandHandler, FinishGame},
+        events::Event,
+        process_managers::run_processors,
+        projections::GameView,
+        session::SYSTEM,
+    };
+    use crate::time::FixedClock;
+
+    #[test]
+    fn the_game_is_only_finished_once() -> anyhow::Result<()> {
+        let clock = FixedClock::at(1_000);
+
+        let game = GameView::from(&vector![
+            Event::new_game(),
+            Event::GameFinished,
+            Event::SpectatorJoined {
+                session_id: Uuid::new_v4(),
+                name: "Late".into(),
+            },
+        ]);
+
+        assert_eq!(FinishGame::handle(SYSTEM, &game, (), &clock)?, vec![]);
+        assert!(
+            !run_processors(&game, &clock)?
+                .0
+                .contains(&Event::GameFinished)
+        );
+
+        Ok(())
+    }
+}
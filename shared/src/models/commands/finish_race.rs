@@ -1,10 +1,14 @@
 use anyhow::bail;
 use tracing::instrument;
-use uuid::Uuid;
 
-use crate::models::{events::Event, projections};
+use crate::models::{
+    events::Event,
+    projections::{GameView, race},
+    session::SYSTEM,
+};
+use crate::time::Clock;
 
-use super::CommandHandler;
+use super::{CommandError, CommandHandler};
 
 pub type Input = ();
 
@@ -13,26 +17,28 @@ pub struct FinishRace;
 impl CommandHandler for FinishRace {
     type Input = ();
 
-    #[instrument(skip(events), err)]
+    #[instrument(skip(game), err)]
     fn handle(
         session_id: uuid::Uuid,
-        events: &im::Vector<Event>,
+        game: &GameView,
         input: Self::Input,
+        clock: &dyn Clock,
     ) -> anyhow::Result<Vec<Event>> {
-        if session_id != Uuid::nil() {
-            bail!("players may not finish the race");
+        if session_id != SYSTEM {
+            bail!(CommandError::forbidden("players may not finish the race"));
         }
 
-        if projections::currently_racing(events).is_none() {
-            bail!("race can only finish if its in progress");
+        if game.currently_racing().is_none() {
+            bail!(CommandError::invalid_phase(
+                "race can only finish if its in progress"
+            ));
         }
 
-        let race_seed = projections::race::race_seed(events);
-        let monsters = projections::monsters(events, race_seed);
-        let (results, _) = projections::race::results(&monsters, race_seed);
+        let monsters = game.monsters(game.race_seed());
+        let (results, _) = race::results(&monsters, game.track(), game.results_seed());
 
         Ok(vec![Event::RaceFinished {
-            time: Event::now(),
+            time: clock.timestamp(),
             results,
         }])
     }
@@ -45,11 +51,15 @@ mod test {
     use uuid::Uuid;
 
     use crate::models::{
-        commands::CommandHandler,
+        commands::{CommandHandler, StartRace, StartRound},
         events::{Event, Settings},
+        fairness::{self, random_secret},
         game_code::GameCode,
-        projections::race::RaceResults,
+        projections::{GameView, race::RaceResults},
+        session::SYSTEM,
+        tracks::Track,
     };
+    use crate::time::SystemClock;
 
     use super::FinishRace;
 
@@ -60,7 +70,8 @@ mod test {
         let events = vector![
             Event::GameCreated {
                 game_id: GameCode::random(),
-                settings: Settings::default()
+                settings: Settings::default(),
+                host: Uuid::nil()
             },
             Event::PlayerJoined {
                 session_id: player,
@@ -72,12 +83,14 @@ mod test {
                 time: 0,
                 odds: None,
                 enemies: None,
+                commitment: None,
+                track: Track::Standard,
             }
         ];
 
         assert_eq!(
             "players may not finish the race",
-            FinishRace::handle(player, &events, ())
+            FinishRace::handle(player, &GameView::from(&events), (), &SystemClock)
                 .err()
                 .ok_or_else(|| anyhow!("failed to fail"))?
                 .root_cause()
@@ -94,7 +107,8 @@ mod test {
         let events = vector![
             Event::GameCreated {
                 game_id: GameCode::random(),
-                settings: Settings::default()
+                settings: Settings::default(),
+                host: Uuid::nil()
             },
             Event::PlayerJoined {
                 session_id: player,
@@ -106,21 +120,24 @@ mod test {
                 time: 0,
                 odds: None,
                 enemies: None,
+                commitment: None,
+                track: Track::Standard,
+            },
+            Event::RaceStarted {
+                time: 0,
+                reveal: None
             },
-            Event::RaceStarted { time: 0 },
             Event::RaceFinished {
                 time: 0,
                 results: RaceResults {
-                    first: Uuid::new_v4(),
-                    second: Uuid::new_v4(),
-                    third: Uuid::new_v4(),
+                    placings: vec![Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4()]
                 }
             }
         ];
 
         assert_eq!(
             "race can only finish if its in progress",
-            FinishRace::handle(Uuid::nil(), &events, ())
+            FinishRace::handle(SYSTEM, &GameView::from(&events), (), &SystemClock)
                 .err()
                 .ok_or_else(|| anyhow!("failed to fail"))?
                 .root_cause()
@@ -137,7 +154,8 @@ mod test {
         let events = vector![
             Event::GameCreated {
                 game_id: GameCode::random(),
-                settings: Settings::default()
+                settings: Settings::default(),
+                host: Uuid::nil()
             },
             Event::PlayerJoined {
                 session_id: player,
@@ -149,12 +167,17 @@ mod test {
                 time: 0,
                 odds: None,
                 enemies: None,
+                commitment: None,
+                track: Track::Standard,
+            },
+            Event::RaceStarted {
+                time: 0,
+                reveal: None
             },
-            Event::RaceStarted { time: 0 },
         ];
 
         if !matches!(
-            FinishRace::handle(Uuid::nil(), &events, ())?.last(),
+            FinishRace::handle(SYSTEM, &GameView::from(&events), (), &SystemClock)?.last(),
             Some(Event::RaceFinished { .. }),
         ) {
             bail!("didn't finish race");
@@ -162,4 +185,54 @@ mod test {
 
         Ok(())
     }
+
+    #[test]
+    fn committed_seeds_are_verified() -> anyhow::Result<()> {
+        let player = Uuid::new_v4();
+
+        let mut view = GameView::default().with_secret(random_secret());
+
+        for event in [
+            Event::GameCreated {
+                game_id: GameCode::random(),
+                settings: Settings::default(),
+                host: Uuid::nil(),
+            },
+            Event::PlayerJoined {
+                session_id: player,
+                name: "example".into(),
+                initial_cards: vec![],
+            },
+            Event::PlayerReady { session_id: player },
+        ] {
+            view.apply(event);
+        }
+
+        for command in [StartRound::handle, StartRace::handle, FinishRace::handle] {
+            for event in command(SYSTEM, &view, (), &SystemClock)? {
+                view.apply(event);
+            }
+        }
+
+        let reveal = view
+            .reveal()
+            .ok_or_else(|| anyhow!("seed wasn't revealed"))?;
+
+        assert!(fairness::verify(view.commitment().unwrap(), reveal));
+        assert_eq!(view.race_verified(), Some(true));
+
+        // A client replaying the log doesn't know the secret but can still check the race
+        let client = GameView::from(view.events());
+        assert_eq!(client.race_verified(), Some(true));
+        assert_eq!(client.reveal_for_round(1), None);
+
+        // Rigging the result after the bets are in is detected
+        let mut rigged = view.events().clone();
+        if let Some(Event::RaceFinished { results, .. }) = rigged.back_mut() {
+            results.placings.reverse();
+        }
+        assert_eq!(GameView::from(&rigged).race_verified(), Some(false));
+
+        Ok(())
+    }
 }
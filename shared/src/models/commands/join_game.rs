@@ -1,14 +1,14 @@
 use std::fmt::Display;
 
-use anyhow::{bail, Result};
-use im::Vector;
+use anyhow::{Result, bail};
 use serde::{Deserialize, Serialize};
 use tracing::instrument;
 use uuid::Uuid;
 
-use crate::models::{events::Event, game_code::GameCode, projections};
+use crate::models::{events::Event, game_code::GameCode, projections::GameView};
+use crate::time::Clock;
 
-use super::{CommandHandler, HasGameCode, API};
+use super::{API, CommandError, CommandHandler, HasGameCode};
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Input {
@@ -39,27 +39,146 @@ impl CommandHandler for JoinGame {
     type Input = Input;
 
     #[instrument(skip_all, fields(input), err)]
-    fn handle(session_id: Uuid, events: &Vector<Event>, input: Self::Input) -> Result<Vec<Event>> {
-        if events.len() == 0 {
-            bail!("cannot join game that doesn't exist");
+    fn handle(
+        session_id: Uuid,
+        game: &GameView,
+        input: Self::Input,
+        _clock: &dyn Clock,
+    ) -> Result<Vec<Event>> {
+        if game.is_empty() {
+            bail!(CommandError::not_found(
+                "cannot join game that doesn't exist"
+            ));
         }
 
-        if projections::player_exists(events, session_id) {
+        if game.player_exists(session_id) {
             return Ok(vec![]);
         }
 
-        if projections::game_has_started(events) {
-            bail!("cannot join after game has already started");
+        if game.was_kicked(session_id) {
+            bail!(CommandError::forbidden(
+                "you have been removed from this game by the host"
+            ));
         }
 
-        if projections::player_count(events) >= 15 {
-            bail!("maximum number of players reached");
+        if game.game_has_started() && (!game.settings().late_join || game.game_finished()) {
+            bail!(CommandError::invalid_phase(
+                "cannot join after game has already started"
+            ));
+        }
+
+        if game.is_queued(session_id) {
+            return Ok(vec![]);
+        }
+
+        if game.player_count() + game.queued_players().len() >= game.settings().max_players {
+            bail!(CommandError::validation(
+                "maximum number of players reached"
+            ));
+        }
+
+        // Late joiners watch until `StartRound` gives them a seat
+        if game.game_has_started() {
+            return Ok(vec![Event::PlayerQueued {
+                session_id,
+                name: input.name,
+            }]);
         }
 
         Ok(vec![Event::PlayerJoined {
             name: input.name,
             session_id,
-            initial_cards: projections::initial_cards(events, session_id),
+            initial_cards: game.initial_cards(),
         }])
     }
 }
+
+#[cfg(test)]
+mod test {
+    use im::vector;
+    use uuid::Uuid;
+
+    use crate::models::{
+        commands::{Command, CommandHandler, JoinGame, join_game},
+        events::{Event, Settings},
+        game_code::GameCode,
+        projections::{GameView, Transaction},
+        session::SYSTEM,
+    };
+    use crate::time::SystemClock;
+
+    #[test]
+    fn late_joiners_are_seated_at_the_next_round() -> anyhow::Result<()> {
+        let [a, b, late] = [Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4()];
+        let code = GameCode::random();
+
+        let game = |late_join| {
+            GameView::from(&vector![
+                Event::GameCreated {
+                    game_id: code,
+                    settings: Settings {
+                        late_join,
+                        ..Default::default()
+                    },
+                    host: Uuid::nil(),
+                },
+                Event::PlayerJoined {
+                    session_id: a,
+                    name: "A".into(),
+                    initial_cards: vec![],
+                },
+                Event::PlayerJoined {
+                    session_id: b,
+                    name: "B".into(),
+                    initial_cards: vec![],
+                },
+                Event::BorrowedMoney {
+                    session_id: b,
+                    amount: 500,
+                },
+                Event::PlayerReady { session_id: a },
+                Event::PlayerReady { session_id: b },
+                Event::start_round_at(0),
+            ])
+        };
+
+        let join = |game: &GameView| {
+            JoinGame::handle(
+                late,
+                game,
+                join_game::Input {
+                    name: "Late".into(),
+                    code,
+                },
+                &SystemClock,
+            )
+        };
+
+        assert!(join(&game(false)).is_err());
+
+        let mut game = game(true);
+
+        for event in join(&game)? {
+            game.apply(event);
+        }
+
+        assert!(game.is_queued(late));
+        assert!(!game.player_exists(late));
+        assert_eq!(join(&game)?, vec![]);
+
+        for event in Command::handle(SYSTEM, &game, Command::StartRound(()), &SystemClock)? {
+            game.apply(event);
+        }
+
+        assert!(game.player_exists(late));
+        assert!(!game.is_spectator(late));
+        assert_eq!(game.round(), 2);
+        assert_eq!(game.account_balance(late), 1250);
+        assert_eq!(
+            game.statement(late).first().map(|entry| entry.transaction),
+            Some(Transaction::CatchUpBalance)
+        );
+
+        Ok(())
+    }
+}
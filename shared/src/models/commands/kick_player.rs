@@ -0,0 +1,208 @@
+use std::fmt::Display;
+
+use anyhow::{Result, bail};
+use serde::{Deserialize, Serialize};
+use tracing::instrument;
+use uuid::Uuid;
+
+use super::{API, CommandError, CommandHandler};
+use crate::models::{events::Event, projections::GameView};
+use crate::time::Clock;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Input {
+    pub session_id: Uuid,
+}
+
+#[derive(Default)]
+pub struct KickPlayer;
+
+impl API for KickPlayer {
+    fn url(game_id: impl Display) -> String {
+        format!("/api/object/game/by_code/{}/commands/kick_player", game_id)
+    }
+}
+
+impl CommandHandler for KickPlayer {
+    type Input = Input;
+
+    #[instrument(name = "KickPlayer::handle", skip(game), err)]
+    fn handle(
+        session_id: Uuid,
+        game: &GameView,
+        input: Self::Input,
+        _clock: &dyn Clock,
+    ) -> Result<Vec<Event>> {
+        if !game.is_host(session_id) {
+            bail!(CommandError::forbidden("only the host can kick players"));
+        }
+
+        if !game.player_exists(input.session_id) {
+            bail!(CommandError::not_found("player does not exist"));
+        }
+
+        if game.game_finished() {
+            bail!(CommandError::invalid_phase("the game is already over"));
+        }
+
+        Ok(vec![Event::PlayerKicked {
+            session_id: input.session_id,
+        }])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use anyhow::anyhow;
+    use im::vector;
+    use uuid::Uuid;
+
+    use crate::models::{
+        commands::{CommandError, CommandHandler, JoinGame, KickPlayer, join_game, kick_player},
+        events::{Event, Payout, Settings},
+        game_code::GameCode,
+        process_managers::run_processors,
+        projections::{GameView, Transaction, race::RaceResults},
+        timers::Timer,
+        tracks::Track,
+    };
+    use crate::time::SystemClock;
+
+    #[test]
+    fn kicked_players_stop_holding_up_the_lobby() -> anyhow::Result<()> {
+        let [host, a, b] = [Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4()];
+        let code = GameCode::random();
+
+        let mut game = GameView::from(&vector![
+            Event::GameCreated {
+                game_id: code,
+                settings: Settings::default(),
+                host,
+            },
+            Event::PlayerJoined {
+                session_id: a,
+                name: "A".into(),
+                initial_cards: vec![],
+            },
+            Event::PlayerJoined {
+                session_id: b,
+                name: "B".into(),
+                initial_cards: vec![],
+            },
+            Event::PlayerReady { session_id: a },
+        ]);
+
+        let kick = |session_id, game: &GameView| {
+            KickPlayer::handle(
+                session_id,
+                game,
+                kick_player::Input { session_id: b },
+                &SystemClock,
+            )
+        };
+
+        assert_eq!(
+            kick(a, &game)
+                .err()
+                .ok_or_else(|| anyhow!("failed to fail"))?
+                .downcast::<CommandError>()?
+                .status_code(),
+            403
+        );
+
+        for event in kick(host, &game)? {
+            game.apply(event);
+        }
+
+        assert!(!game.player_exists(b));
+        assert_eq!(game.all_account_balances().len(), 1);
+        assert!(matches!(
+            &run_processors(&game, &SystemClock)?.0[..],
+            [
+                Event::RoundStarted { .. },
+                Event::TimerScheduled {
+                    timer: Timer::StartRace,
+                    ..
+                }
+            ]
+        ));
+
+        let rejoin = JoinGame::handle(
+            b,
+            &game,
+            join_game::Input {
+                name: "B".into(),
+                code,
+            },
+            &SystemClock,
+        );
+        assert!(rejoin.is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn kicked_rivals_dont_pay_out_a_rival_bonus() -> anyhow::Result<()> {
+        let [host, a, b] = [Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4()];
+
+        let mut game = GameView::from(&vector![
+            Event::GameCreated {
+                game_id: GameCode::random(),
+                settings: Settings {
+                    payout: Payout::Odds,
+                    ..Settings::default()
+                },
+                host,
+            },
+            Event::PlayerJoined {
+                session_id: a,
+                name: "A".into(),
+                initial_cards: vec![],
+            },
+            Event::PlayerJoined {
+                session_id: b,
+                name: "B".into(),
+                initial_cards: vec![],
+            },
+            Event::PlayerReady { session_id: a },
+            Event::PlayerReady { session_id: b },
+            Event::RoundStarted {
+                time: 0,
+                odds: None,
+                enemies: Some([(a, b), (b, a)].into_iter().collect()),
+                commitment: None,
+                track: Track::Standard,
+            },
+        ]);
+
+        for event in KickPlayer::handle(
+            host,
+            &game,
+            kick_player::Input { session_id: b },
+            &SystemClock,
+        )? {
+            game.apply(event);
+        }
+
+        game.apply(Event::start_race_at(0));
+        game.apply(Event::RaceFinished {
+            time: 0,
+            results: RaceResults {
+                placings: game
+                    .monsters(game.race_seed())
+                    .iter()
+                    .map(|it| it.uuid)
+                    .collect(),
+            },
+        });
+
+        assert!(
+            !game
+                .statement(a)
+                .iter()
+                .any(|entry| matches!(entry.transaction, Transaction::RivalBonus { .. }))
+        );
+
+        Ok(())
+    }
+}
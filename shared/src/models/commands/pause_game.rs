@@ -0,0 +1,133 @@
+use std::fmt::Display;
+
+use anyhow::{Result, bail};
+use tracing::instrument;
+use uuid::Uuid;
+
+use super::{API, CommandError, CommandHandler};
+use crate::models::{events::Event, projections::GameView};
+use crate::time::Clock;
+
+pub type Input = ();
+
+#[derive(Default)]
+pub struct PauseGame;
+
+impl API for PauseGame {
+    fn url(game_id: impl Display) -> String {
+        format!("/api/object/game/by_code/{}/commands/pause_game", game_id)
+    }
+}
+
+impl CommandHandler for PauseGame {
+    type Input = ();
+
+    #[instrument(name = "PauseGame::handle", skip(game), err)]
+    fn handle(
+        session_id: Uuid,
+        game: &GameView,
+        _input: Self::Input,
+        clock: &dyn Clock,
+    ) -> Result<Vec<Event>> {
+        if !game.is_host(session_id) {
+            bail!(CommandError::forbidden("only the host can pause the game"));
+        }
+
+        if game.currently_betting().is_none() {
+            bail!(CommandError::invalid_phase(
+                "the game can only be paused while players are betting"
+            ));
+        }
+
+        if game.paused() {
+            return Ok(vec![]);
+        }
+
+        Ok(vec![Event::GamePaused {
+            time: clock.timestamp(),
+        }])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use im::vector;
+    use uuid::Uuid;
+
+    use crate::models::{
+        commands::{CommandHandler, ExtendTimer, PauseGame, extend_timer},
+        events::{Event, Settings},
+        game_code::GameCode,
+        process_managers::{Alarm, run_processors},
+        projections::GameView,
+        timers::Timer,
+        tracks::Track,
+    };
+    use crate::time::{Clock, Duration, FixedClock};
+
+    #[test]
+    fn the_pregame_timer_stops_while_paused() -> anyhow::Result<()> {
+        let [host, a] = [Uuid::new_v4(), Uuid::new_v4()];
+        let clock = FixedClock::at(1_000);
+        let now = clock.timestamp();
+
+        let round = |time| Event::RoundStarted {
+            time,
+            odds: None,
+            enemies: None,
+            commitment: None,
+            track: Track::Standard,
+        };
+
+        // The second round has a timer, it ran out 10 seconds ago
+        let mut game = GameView::from(&vector![
+            Event::GameCreated {
+                game_id: GameCode::random(),
+                settings: Settings::default(),
+                host,
+            },
+            Event::PlayerJoined {
+                session_id: a,
+                name: "A".into(),
+                initial_cards: vec![],
+            },
+            Event::PlayerReady { session_id: a },
+            round(now - 100),
+            round(now - 100),
+        ]);
+
+        assert!(PauseGame::handle(a, &game, (), &clock).is_err());
+        assert!(matches!(
+            &PauseGame::handle(host, &game, (), &clock)?[..],
+            [Event::GamePaused { .. }]
+        ));
+
+        game.apply(Event::GamePaused { time: now - 95 });
+
+        assert!(game.paused());
+        assert_eq!(game.time_left_in_pregame(&clock), Some(85));
+        assert_eq!(run_processors(&game, &clock)?, (vec![], None));
+
+        game.apply(Event::GameResumed { time: now });
+
+        // The pause pushed the deadline back
+        let (events, alarm) = run_processors(&game, &clock)?;
+        assert!(matches!(
+            &events[..],
+            [Event::TimerScheduled {
+                timer: Timer::StartRace,
+                ..
+            }]
+        ));
+        assert_eq!(alarm, Some(Alarm(Duration::from_secs(85))));
+
+        for event in ExtendTimer::handle(host, &game, extend_timer::Input { seconds: 30 }, &clock)?
+        {
+            game.apply(event);
+        }
+
+        assert_eq!(game.time_left_in_pregame(&clock), Some(115));
+
+        Ok(())
+    }
+}
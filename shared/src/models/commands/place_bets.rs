@@ -1,22 +1,23 @@
 use std::fmt::Display;
 
 use anyhow::{Result, bail};
-use im::Vector;
 use serde::{Deserialize, Serialize};
 use tracing::instrument;
 use uuid::Uuid;
 
 use crate::models::{
-    events::{Event, PlacedBet},
-    projections::{self},
+    events::{BetKind, Event, PlacedBet},
+    projections::GameView,
 };
+use crate::time::Clock;
 
-use super::{API, CommandHandler};
+use super::{API, CommandError, CommandHandler};
 
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct Bet {
     pub monster_id: Uuid,
     pub amount: i32,
+    pub kind: BetKind,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -36,21 +37,40 @@ impl API for PlaceBets {
 impl CommandHandler for PlaceBets {
     type Input = Input;
 
-    #[instrument(skip(events), err)]
-    fn handle(session_id: Uuid, events: &Vector<Event>, input: Self::Input) -> Result<Vec<Event>> {
-        if !projections::game_has_started(events) {
-            bail!("cannot place a bet if the game hasn't started");
+    #[instrument(skip(game), err)]
+    fn handle(
+        session_id: Uuid,
+        game: &GameView,
+        input: Self::Input,
+        _clock: &dyn Clock,
+    ) -> Result<Vec<Event>> {
+        // Spectators don't have a seat, even an empty bet would give them a stake in the pool
+        if !game.player_exists(session_id) {
+            bail!(CommandError::not_found(
+                "cannot place a bet for a player that doesn't exist"
+            ));
         }
 
-        if projections::currently_betting(events).is_none() {
-            bail!("cannot place a bet if betting is not in progress");
+        if !game.game_has_started() {
+            bail!(CommandError::invalid_phase(
+                "cannot place a bet if the game hasn't started"
+            ));
+        }
+
+        if game.currently_betting().is_none() {
+            bail!(CommandError::invalid_phase(
+                "cannot place a bet if betting is not in progress"
+            ));
         }
 
         if input.bets.iter().any(|it| it.amount < 0) {
-            bail!("cannot place a bet with a value less than 0");
+            bail!(CommandError::validation(
+                "cannot place a bet with a value less than 0"
+            ));
         }
 
-        let account_balance = projections::all_account_balances(events)
+        let account_balance = game
+            .all_account_balances()
             .get(&session_id)
             .cloned()
             .unwrap_or_default();
@@ -58,22 +78,38 @@ impl CommandHandler for PlaceBets {
         let total = input.bets.iter().map(|it| it.amount).sum::<i32>();
 
         if total > account_balance {
-            bail!("cannot place a bet with a total value greater than your balance");
+            bail!(CommandError::insufficient_funds(
+                "cannot place a bet with a total value greater than your balance"
+            ));
         }
 
-        // if total < projections::minimum_bet(events) {
+        // if total < game.minimum_bet() {
         //     bail!("bet cannot be less than the minimum bet");
         // }
 
-        let race_seed = projections::race::race_seed(events);
-        let monsters = projections::monsters(events, race_seed);
+        let race_seed = game.race_seed();
+        let monsters = game.monsters(race_seed);
 
         for bet in input.bets.iter() {
-            if !monsters
+            let mut runners = bet.kind.runners();
+            runners.push(bet.monster_id);
+
+            if !runners
                 .iter()
-                .any(|monster| bet.monster_id == monster.uuid)
+                .all(|runner| monsters.iter().any(|monster| monster.uuid == *runner))
             {
-                bail!("failed to find monster corresponding to bet");
+                bail!(CommandError::not_found(
+                    "failed to find monster corresponding to bet"
+                ));
+            }
+
+            runners.sort();
+            runners.dedup();
+
+            if runners.len() != bet.kind.runners().len() + 1 {
+                bail!(CommandError::validation(
+                    "a combination bet cannot name the same monster twice"
+                ));
             }
         }
 
@@ -86,6 +122,7 @@ impl CommandHandler for PlaceBets {
                     session_id,
                     monster_id: bet.monster_id,
                     amount: bet.amount,
+                    kind: bet.kind,
                 })
             })
             .collect();
@@ -1,5 +1,4 @@
-use anyhow::{bail, Result};
-use im::Vector;
+use anyhow::{Result, bail};
 use serde::{Deserialize, Serialize};
 use tracing::instrument;
 use uuid::Uuid;
@@ -7,10 +6,11 @@ use uuid::Uuid;
 use crate::models::{
     cards::{Card, Target},
     events::Event,
-    projections,
+    projections::GameView,
 };
+use crate::time::Clock;
 
-use super::{CommandHandler, API};
+use super::{API, CommandError, CommandHandler};
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Input {
@@ -30,27 +30,32 @@ impl API for PlayCard {
 impl CommandHandler for PlayCard {
     type Input = Input;
 
-    #[instrument(skip(events), err)]
-    fn handle(session_id: Uuid, events: &Vector<Event>, input: Input) -> Result<Vec<Event>> {
-        if !projections::player_exists(events, session_id) {
-            bail!("Player does not exist");
+    #[instrument(skip(game), err)]
+    fn handle(
+        session_id: Uuid,
+        game: &GameView,
+        input: Input,
+        _clock: &dyn Clock,
+    ) -> Result<Vec<Event>> {
+        if !game.player_exists(session_id) {
+            bail!(CommandError::not_found("Player does not exist"));
         }
 
-        if !projections::cards_in_hand(events, session_id)
-            .into_iter()
-            .find(|card| *card == input.card)
-            .is_some()
-        {
-            tracing::warn!(cards = ?projections::cards_in_hand(events, session_id));
-            bail!("Player does not have card in hand");
+        if !game.cards_in_hand(session_id).contains(&input.card) {
+            tracing::warn!(cards = ?game.cards_in_hand(session_id));
+            bail!(CommandError::validation(
+                "Player does not have card in hand"
+            ));
         }
 
-        if !projections::can_play_more_cards(events, session_id) {
-            bail!("Player already played card this round");
+        if !game.can_play_more_cards(session_id) {
+            bail!(CommandError::validation(
+                "Player already played card this round"
+            ));
         }
 
-        if !projections::valid_target_for_card(events, session_id, input.target.clone()) {
-            bail!("Invalid target for card");
+        if !game.valid_target_for_card(session_id, input.target.clone()) {
+            bail!(CommandError::validation("Invalid target for card"));
         }
 
         Ok(vec![Event::PlayedCard {
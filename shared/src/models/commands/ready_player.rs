@@ -1,9 +1,9 @@
 use std::fmt::Display;
 
-use super::{CommandHandler, API};
-use crate::models::{events::Event, projections};
-use anyhow::{bail, Result};
-use im::Vector;
+use super::{API, CommandError, CommandHandler};
+use crate::models::{events::Event, projections::GameView};
+use crate::time::Clock;
+use anyhow::{Result, bail};
 use uuid::Uuid;
 
 pub type Input = ();
@@ -20,13 +20,22 @@ impl API for ReadyPlayer {
 impl CommandHandler for ReadyPlayer {
     type Input = ();
 
-    fn handle(session_id: Uuid, events: &Vector<Event>, _input: Self::Input) -> Result<Vec<Event>> {
-        if !projections::player_exists(events, session_id) {
-            bail!("cannot ready a player that doesn't exist");
+    fn handle(
+        session_id: Uuid,
+        game: &GameView,
+        _input: Self::Input,
+        _clock: &dyn Clock,
+    ) -> Result<Vec<Event>> {
+        if !game.player_exists(session_id) {
+            bail!(CommandError::not_found(
+                "cannot ready a player that doesn't exist"
+            ));
         }
 
-        if projections::game_has_started(events) {
-            bail!("cannot ready a player after game has started");
+        if game.game_has_started() {
+            bail!(CommandError::invalid_phase(
+                "cannot ready a player after game has started"
+            ));
         }
 
         let events = vec![Event::PlayerReady { session_id }];
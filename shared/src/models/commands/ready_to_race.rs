@@ -0,0 +1,116 @@
+use std::fmt::Display;
+
+use anyhow::{Result, bail};
+use tracing::instrument;
+use uuid::Uuid;
+
+use super::{API, CommandError, CommandHandler};
+use crate::models::{events::Event, projections::GameView};
+use crate::time::Clock;
+
+pub type Input = ();
+
+#[derive(Default)]
+pub struct ReadyToRace;
+
+impl API for ReadyToRace {
+    fn url(game_id: impl Display) -> String {
+        format!(
+            "/api/object/game/by_code/{}/commands/ready_to_race",
+            game_id
+        )
+    }
+}
+
+impl CommandHandler for ReadyToRace {
+    type Input = ();
+
+    #[instrument(name = "ReadyToRace::handle", skip(game), err)]
+    fn handle(
+        session_id: Uuid,
+        game: &GameView,
+        _input: Self::Input,
+        _clock: &dyn Clock,
+    ) -> Result<Vec<Event>> {
+        if !game.player_exists(session_id) {
+            bail!(CommandError::not_found(
+                "cannot ready a player that doesn't exist"
+            ));
+        }
+
+        if game.currently_betting().is_none() {
+            bail!(CommandError::invalid_phase(
+                "cannot get ready to race if betting is not in progress"
+            ));
+        }
+
+        if game.player_is_ready_to_race(session_id) {
+            return Ok(vec![]);
+        }
+
+        Ok(vec![Event::PlayerReadyToRace { session_id }])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use im::vector;
+    use uuid::Uuid;
+
+    use crate::models::{
+        commands::{CommandHandler, ReadyToRace},
+        events::{BetKind, Event, PlacedBet},
+        process_managers::run_processors,
+        projections::GameView,
+    };
+    use crate::time::{Clock, FixedClock};
+
+    #[test]
+    fn the_race_starts_once_everyone_is_ready() -> anyhow::Result<()> {
+        let [a, b] = [Uuid::new_v4(), Uuid::new_v4()];
+        let clock = FixedClock::at(1_000);
+
+        let mut game = GameView::from(&vector![
+            Event::new_game(),
+            Event::PlayerJoined {
+                session_id: a,
+                name: "A".into(),
+                initial_cards: vec![],
+            },
+            Event::PlayerJoined {
+                session_id: b,
+                name: "B".into(),
+                initial_cards: vec![],
+            },
+            Event::PlayerReady { session_id: a },
+            Event::PlayerReady { session_id: b },
+        ]);
+
+        assert!(ReadyToRace::handle(a, &game, (), &clock).is_err());
+
+        game.apply(Event::start_round_at(clock.timestamp()));
+        game.apply(Event::PlacedBet(PlacedBet {
+            session_id: a,
+            monster_id: Uuid::new_v4(),
+            amount: 100,
+            kind: BetKind::Win,
+        }));
+
+        // Having bet already counts
+        assert_eq!(ReadyToRace::handle(a, &game, (), &clock)?, vec![]);
+        assert!(ReadyToRace::handle(Uuid::new_v4(), &game, (), &clock).is_err());
+
+        for event in ReadyToRace::handle(b, &game, (), &clock)? {
+            game.apply(event);
+        }
+
+        assert!(!game.player_has_bet(b));
+        assert!(game.all_players_ready_to_race());
+        assert!(matches!(
+            &run_processors(&game, &clock)?.0[..],
+            [Event::RaceStarted { .. }, ..]
+        ));
+
+        Ok(())
+    }
+}
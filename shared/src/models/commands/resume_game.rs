@@ -0,0 +1,44 @@
+use std::fmt::Display;
+
+use anyhow::{Result, bail};
+use tracing::instrument;
+use uuid::Uuid;
+
+use super::{API, CommandError, CommandHandler};
+use crate::models::{events::Event, projections::GameView};
+use crate::time::Clock;
+
+pub type Input = ();
+
+#[derive(Default)]
+pub struct ResumeGame;
+
+impl API for ResumeGame {
+    fn url(game_id: impl Display) -> String {
+        format!("/api/object/game/by_code/{}/commands/resume_game", game_id)
+    }
+}
+
+impl CommandHandler for ResumeGame {
+    type Input = ();
+
+    #[instrument(name = "ResumeGame::handle", skip(game), err)]
+    fn handle(
+        session_id: Uuid,
+        game: &GameView,
+        _input: Self::Input,
+        clock: &dyn Clock,
+    ) -> Result<Vec<Event>> {
+        if !game.is_host(session_id) {
+            bail!(CommandError::forbidden("only the host can resume the game"));
+        }
+
+        if !game.paused() {
+            return Ok(vec![]);
+        }
+
+        Ok(vec![Event::GameResumed {
+            time: clock.timestamp(),
+        }])
+    }
+}
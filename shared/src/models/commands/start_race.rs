@@ -1,35 +1,57 @@
+use std::fmt::Display;
+
 use anyhow::bail;
 use tracing::instrument;
-use uuid::Uuid;
 
-use super::CommandHandler;
-use crate::models::events::Event;
+use super::{API, CommandError, CommandHandler};
+use crate::models::{events::Event, projections::GameView, session::SYSTEM};
 use crate::time::*;
 
 pub type Input = ();
 
 pub struct StartRace;
 
+// The host can start the race without waiting for the timer or the last few bets
+impl API for StartRace {
+    fn url(game_id: impl Display) -> String {
+        format!("/api/object/game/by_code/{}/commands/start_race", game_id)
+    }
+}
+
 impl CommandHandler for StartRace {
     type Input = ();
 
     #[instrument(name = "StartRace::handle", err)]
     fn handle(
         session_id: uuid::Uuid,
-        events: &im::Vector<Event>,
+        game: &GameView,
         input: Self::Input,
+        clock: &dyn Clock,
     ) -> anyhow::Result<Vec<Event>> {
-        if session_id != Uuid::nil() {
-            bail!("players may not start the race");
+        if session_id != SYSTEM && !game.is_host(session_id) {
+            bail!(CommandError::forbidden("players may not start the race"));
+        }
+
+        if game.currently_betting().is_none() {
+            bail!(CommandError::invalid_phase(
+                "the race can only start while players are betting"
+            ));
         }
 
         //TODO: can we enforce this condition if the server needs to trigger a timeout?
-        // if !projections::all_players_have_bet(events) {
+        // if !game.all_players_have_bet() {
         //     bail!("race can only start if all players have bet");
         // }
 
+        // Betting is over so the seed can be revealed, the clients need it to replay the race
+        let reveal = match game.commitment() {
+            Some(_) => game.reveal_for_round(game.round()),
+            None => None,
+        };
+
         Ok(vec![Event::RaceStarted {
-            time: SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs() as u32,
+            time: clock.timestamp(),
+            reveal,
         }])
     }
 }
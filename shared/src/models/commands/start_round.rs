@@ -1,13 +1,16 @@
 use anyhow::bail;
 use tracing::instrument;
-use uuid::Uuid;
 
 use crate::{
-    models::{events::Event, projections},
+    models::{
+        events::Event,
+        projections::{self, GameView},
+        session::SYSTEM,
+    },
     time::*,
 };
 
-use super::CommandHandler;
+use super::{CommandError, CommandHandler};
 
 pub type Input = ();
 
@@ -19,25 +22,55 @@ impl CommandHandler for StartRound {
     #[instrument(name = "StartRound::handle", err)]
     fn handle(
         session_id: uuid::Uuid,
-        events: &im::Vector<Event>,
+        game: &GameView,
         input: Self::Input,
+        clock: &dyn Clock,
     ) -> anyhow::Result<Vec<Event>> {
-        if session_id != Uuid::nil() {
-            bail!("players may not start the round");
+        if session_id != SYSTEM {
+            bail!(CommandError::forbidden("players may not start the round"));
         }
 
         //TODO: Can server can override??
-        if !projections::all_players_ready(events) {
-            bail!("round can only start if all players are ready");
+        if !game.all_players_ready() {
+            bail!(CommandError::invalid_phase(
+                "round can only start if all players are ready"
+            ));
         }
 
-        let seed = projections::race::race_seed_for_round(events, projections::round(events) + 1);
-        let monsters = projections::monsters(events, seed);
+        // Late joiners are seated first so they're dealt in and given a rival
+        let mut game = game.clone();
+        let mut events = vec![];
 
-        Ok(vec![Event::RoundStarted {
-            time: SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs() as u32,
-            odds: Some(projections::odds(&monsters, seed)),
-            enemies: Some(projections::all_enemies(events)),
-        }])
+        for (session_id, name) in game.queued_players().clone() {
+            if game.player_count() >= game.settings().max_players {
+                break;
+            }
+
+            let event = Event::LatePlayerJoined {
+                session_id,
+                name,
+                initial_cards: game.initial_cards(),
+                balance: game.catch_up_balance(),
+            };
+
+            game.apply(event.clone());
+            events.push(event);
+        }
+
+        let round = game.round() + 1;
+        let monsters = game.monsters(game.race_seed_for_round(round));
+        let track = game.track_for_round(round);
+
+        events.push(Event::RoundStarted {
+            time: clock.timestamp(),
+            odds: Some(projections::odds(&monsters, track, game.odds_seed(round))),
+            enemies: Some(game.all_enemies()),
+            commitment: game
+                .reveal_for_round(round)
+                .map(|reveal| reveal.commitment()),
+            track,
+        });
+
+        Ok(events)
     }
 }
@@ -0,0 +1,128 @@
+use std::fmt::Display;
+
+use anyhow::{Result, bail};
+use tracing::instrument;
+use uuid::Uuid;
+
+use super::{API, CommandError, CommandHandler};
+use crate::models::{events::Event, projections::GameView};
+use crate::time::Clock;
+
+pub type Input = ();
+
+#[derive(Default)]
+pub struct VoidRound;
+
+impl API for VoidRound {
+    fn url(game_id: impl Display) -> String {
+        format!("/api/object/game/by_code/{}/commands/void_round", game_id)
+    }
+}
+
+impl CommandHandler for VoidRound {
+    type Input = ();
+
+    #[instrument(name = "VoidRound::handle", skip(game), err)]
+    fn handle(
+        session_id: Uuid,
+        game: &GameView,
+        _input: Self::Input,
+        clock: &dyn Clock,
+    ) -> Result<Vec<Event>> {
+        if !game.is_host(session_id) {
+            bail!(CommandError::forbidden("only the host can void the round"));
+        }
+
+        if game.currently_betting().is_none() && game.currently_racing().is_none() {
+            bail!(CommandError::invalid_phase(
+                "there is no round in progress to void"
+            ));
+        }
+
+        Ok(vec![Event::RoundVoided {
+            time: clock.timestamp(),
+        }])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use im::vector;
+    use uuid::Uuid;
+
+    use crate::models::{
+        commands::{CommandHandler, EndGame, VoidRound},
+        events::{BetKind, Event, PlacedBet, Settings},
+        game_code::GameCode,
+        process_managers::run_processors,
+        projections::{GameView, Transaction},
+        timers::Timer,
+    };
+    use crate::time::{Clock, FixedClock};
+
+    #[test]
+    fn voided_rounds_are_refunded_and_replayed() -> anyhow::Result<()> {
+        let [host, a] = [Uuid::new_v4(), Uuid::new_v4()];
+        let clock = FixedClock::at(1_000);
+
+        let mut game = GameView::from(&vector![
+            Event::GameCreated {
+                game_id: GameCode::random(),
+                settings: Settings::default(),
+                host,
+            },
+            Event::PlayerJoined {
+                session_id: a,
+                name: "A".into(),
+                initial_cards: vec![],
+            },
+            Event::PlayerReady { session_id: a },
+            Event::start_round_at(clock.timestamp()),
+            Event::PlacedBet(PlacedBet {
+                session_id: a,
+                monster_id: Uuid::new_v4(),
+                amount: 100,
+                kind: BetKind::Win,
+            }),
+            Event::start_race_at(clock.timestamp()),
+        ]);
+
+        assert_eq!(game.account_balance(a), 900);
+        assert!(VoidRound::handle(a, &game, (), &clock).is_err());
+
+        for event in VoidRound::handle(host, &game, (), &clock)? {
+            game.apply(event);
+        }
+
+        assert_eq!(game.account_balance(a), 1000);
+        assert!(matches!(
+            game.statement(a).last().map(|entry| entry.transaction),
+            Some(Transaction::Refund { .. })
+        ));
+        assert_eq!(game.currently_racing(), None);
+
+        let (events, _) = run_processors(&game, &clock)?;
+        assert!(matches!(
+            &events[..],
+            [
+                Event::RoundStarted { .. },
+                Event::TimerScheduled {
+                    timer: Timer::StartRace,
+                    ..
+                }
+            ]
+        ));
+
+        for event in events {
+            game.apply(event);
+        }
+
+        assert_eq!(game.round(), 2);
+        assert!(matches!(
+            &EndGame::handle(host, &game, (), &clock)?[..],
+            [Event::RoundVoided { .. }, Event::GameFinished]
+        ));
+
+        Ok(())
+    }
+}
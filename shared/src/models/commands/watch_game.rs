@@ -0,0 +1,147 @@
+use std::fmt::Display;
+
+use anyhow::{Result, bail};
+use serde::{Deserialize, Serialize};
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::models::{events::Event, game_code::GameCode, projections::GameView};
+use crate::time::Clock;
+
+use super::{API, CommandError, CommandHandler, HasGameCode};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Input {
+    pub name: String,
+    pub code: GameCode,
+}
+
+#[derive(Default)]
+pub struct WatchGame;
+
+impl HasGameCode for Input {
+    fn game_code(&self) -> GameCode {
+        self.code
+    }
+}
+
+impl API for WatchGame {
+    fn url(game_id: impl Display) -> String {
+        format!("/api/object/game/by_code/{}/command/watch_game", game_id)
+    }
+
+    fn redirect(game_id: impl Display) -> Option<String> {
+        Some(format!("/watch/{}", game_id))
+    }
+}
+
+impl CommandHandler for WatchGame {
+    type Input = Input;
+
+    #[instrument(skip_all, fields(input), err)]
+    fn handle(
+        session_id: Uuid,
+        game: &GameView,
+        input: Self::Input,
+        _clock: &dyn Clock,
+    ) -> Result<Vec<Event>> {
+        if game.is_empty() {
+            bail!(CommandError::not_found(
+                "cannot watch a game that doesn't exist"
+            ));
+        }
+
+        if game.was_kicked(session_id) {
+            bail!(CommandError::forbidden(
+                "you have been removed from this game by the host"
+            ));
+        }
+
+        // Players can already see everything a spectator can
+        if game.player_exists(session_id) || game.is_spectator(session_id) {
+            return Ok(vec![]);
+        }
+
+        Ok(vec![Event::SpectatorJoined {
+            session_id,
+            name: input.name,
+        }])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use anyhow::anyhow;
+    use im::vector;
+    use uuid::Uuid;
+
+    use crate::models::{
+        commands::{CommandError, CommandHandler, PlaceBets, WatchGame, place_bets, watch_game},
+        events::{Event, Settings},
+        game_code::GameCode,
+        projections::GameView,
+    };
+    use crate::time::SystemClock;
+
+    #[test]
+    fn spectators_can_join_any_time_but_not_bet() -> anyhow::Result<()> {
+        let [a, spectator] = [Uuid::new_v4(), Uuid::new_v4()];
+        let code = GameCode::random();
+
+        let mut game = GameView::from(&vector![
+            Event::GameCreated {
+                game_id: code,
+                settings: Settings::default(),
+                host: Uuid::nil(),
+            },
+            Event::PlayerJoined {
+                session_id: a,
+                name: "A".into(),
+                initial_cards: vec![],
+            },
+            Event::PlayerReady { session_id: a },
+            Event::start_round_at(0),
+        ]);
+
+        for event in WatchGame::handle(
+            spectator,
+            &game,
+            watch_game::Input {
+                name: "Watcher".into(),
+                code,
+            },
+            &SystemClock,
+        )? {
+            game.apply(event);
+        }
+
+        assert!(game.is_spectator(spectator));
+        assert!(!game.player_exists(spectator));
+
+        let monster_id = game.monsters(game.race_seed())[0].uuid;
+
+        let bet = PlaceBets::handle(
+            spectator,
+            &game,
+            place_bets::Input {
+                bets: vec![place_bets::Bet {
+                    monster_id,
+                    amount: 0,
+                    kind: Default::default(),
+                }],
+            },
+            &SystemClock,
+        );
+
+        assert_eq!(
+            bet.err()
+                .ok_or_else(|| anyhow!("failed to fail"))?
+                .downcast::<CommandError>()?
+                .status_code(),
+            404
+        );
+        assert!(game.all_account_balances().get(&spectator).is_none());
+
+        Ok(())
+    }
+}
@@ -1,29 +1,107 @@
 use std::{collections::HashMap, ops::Deref};
 
+use anyhow::{Result, bail};
 use macros::serde_wasm_bindgen;
 use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
 use uuid::Uuid;
 
 use super::{
     cards::{Card, Target},
+    fairness::Digest,
     game_code::GameCode,
     projections::race::RaceResults,
+    roster::MonsterDefinition,
+    session,
+    timers::Timer,
+    tracks::Track,
 };
 
 use crate::time::*;
 
+// The combination bets name the monsters that have to come after the bet's monster, which always
+// has to come first
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum BetKind {
+    #[default]
+    Win,
+    Place,
+    Show,
+    Exacta {
+        second: Uuid,
+    },
+    Trifecta {
+        second: Uuid,
+        third: Uuid,
+    },
+}
+
+impl BetKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            BetKind::Win => "Win",
+            BetKind::Place => "Place",
+            BetKind::Show => "Show",
+            BetKind::Exacta { .. } => "Exacta",
+            BetKind::Trifecta { .. } => "Trifecta",
+        }
+    }
+
+    // The other monsters named by a combination bet
+    pub fn runners(&self) -> Vec<Uuid> {
+        match *self {
+            BetKind::Exacta { second } => vec![second],
+            BetKind::Trifecta { second, third } => vec![second, third],
+            _ => vec![],
+        }
+    }
+
+    pub fn max_payout(&self) -> f32 {
+        match self {
+            BetKind::Win | BetKind::Place | BetKind::Show => PAYOUT_MAX,
+            BetKind::Exacta { .. } => 5.0 * PAYOUT_MAX,
+            BetKind::Trifecta { .. } => 25.0 * PAYOUT_MAX,
+        }
+    }
+
+    pub fn wins(&self, monster_id: Uuid, results: &RaceResults) -> bool {
+        let position = results.position(monster_id);
+
+        match *self {
+            BetKind::Win => position == Some(1),
+            BetKind::Place => position.is_some_and(|position| position <= 2),
+            BetKind::Show => position.is_some_and(|position| position <= 3),
+            BetKind::Exacta { second } => {
+                position == Some(1) && results.position(second) == Some(2)
+            }
+            BetKind::Trifecta { second, third } => {
+                position == Some(1)
+                    && results.position(second) == Some(2)
+                    && results.position(third) == Some(3)
+            }
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Hash)]
 pub struct PlacedBet {
     pub session_id: Uuid,
     pub monster_id: Uuid,
     pub amount: i32,
+    pub kind: BetKind,
+}
+
+impl PlacedBet {
+    pub fn won(&self, results: &RaceResults) -> bool {
+        self.kind.wins(self.monster_id, results)
+    }
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Deserialize, Serialize)]
-pub struct Odds(pub [(Uuid, f32); 3]);
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct Odds(pub Vec<(Uuid, f32)>);
 
 impl Deref for Odds {
-    type Target = [(Uuid, f32); 3];
+    type Target = [(Uuid, f32)];
     fn deref(&self) -> &Self::Target {
         &self.0
     }
@@ -31,11 +109,58 @@ impl Deref for Odds {
 
 pub trait OddsExt {
     fn odds(&self, monster_id: Uuid) -> f32;
-    fn payout(&self, monster_id: Uuid) -> f32;
+    fn probability(&self, monster_id: Uuid, kind: BetKind) -> f32;
+    fn bet_payout(&self, monster_id: Uuid, kind: BetKind) -> f32;
+
+    fn payout(&self, monster_id: Uuid) -> f32 {
+        self.bet_payout(monster_id, BetKind::Win)
+    }
 }
 
 const PAYOUT_MAX: f32 = 10.0;
 
+impl Odds {
+    // Every monster in the field is as likely to win as any other
+    pub fn even(monsters: impl IntoIterator<Item = Uuid>) -> Self {
+        let monsters = monsters.into_iter().collect::<Vec<_>>();
+        let odds = 1. / monsters.len() as f32;
+
+        Odds(
+            monsters
+                .into_iter()
+                .map(|monster| (monster, odds))
+                .collect(),
+        )
+    }
+
+    // The race simulation draws the finishing order by weight without replacement, so the chance of
+    // a particular order follows from the win odds (the Harville formula)
+    fn order(&self, order: &[Uuid]) -> f32 {
+        let mut remaining = 1.0;
+        let mut probability = 1.0;
+
+        for monster_id in order {
+            if remaining <= 0.0 {
+                return 0.0;
+            }
+
+            let odds = self.odds(*monster_id);
+
+            probability *= odds / remaining;
+            remaining -= odds;
+        }
+
+        probability
+    }
+
+    fn others(&self, monster_id: Uuid) -> impl Iterator<Item = Uuid> + '_ {
+        self.0
+            .iter()
+            .map(|(id, _)| *id)
+            .filter(move |id| *id != monster_id)
+    }
+}
+
 impl OddsExt for Odds {
     fn odds(&self, monster_id: Uuid) -> f32 {
         self.0
@@ -45,29 +170,38 @@ impl OddsExt for Odds {
             .copied()
             .unwrap_or_else(|| {
                 tracing::warn!(?monster_id, "no odds found for monster");
-                1. / 3.0
+                1. / self.0.len().max(1) as f32
             })
     }
 
-    fn payout(&self, monster_id: Uuid) -> f32 {
-        f32::min(1.0 / self.odds(monster_id), PAYOUT_MAX)
-    }
-}
-
-impl OddsExt for Option<Odds> {
-    fn odds(&self, monster_id: Uuid) -> f32 {
-        self.map(|inner| inner.odds(monster_id)).unwrap_or_else(|| {
-            tracing::warn!("getting default odds");
-            1. / 3.
-        })
+    fn probability(&self, monster_id: Uuid, kind: BetKind) -> f32 {
+        match kind {
+            BetKind::Win => self.odds(monster_id),
+            BetKind::Place => {
+                self.odds(monster_id)
+                    + self
+                        .others(monster_id)
+                        .map(|first| self.order(&[first, monster_id]))
+                        .sum::<f32>()
+            }
+            BetKind::Show => {
+                self.probability(monster_id, BetKind::Place)
+                    + self
+                        .others(monster_id)
+                        .flat_map(|first| {
+                            self.others(monster_id)
+                                .filter(move |second| *second != first)
+                                .map(move |second| self.order(&[first, second, monster_id]))
+                        })
+                        .sum::<f32>()
+            }
+            BetKind::Exacta { second } => self.order(&[monster_id, second]),
+            BetKind::Trifecta { second, third } => self.order(&[monster_id, second, third]),
+        }
     }
 
-    fn payout(&self, monster_id: Uuid) -> f32 {
-        self.map(|inner| inner.payout(monster_id))
-            .unwrap_or_else(|| {
-                tracing::warn!("getting default payout");
-                3.0
-            })
+    fn bet_payout(&self, monster_id: Uuid, kind: BetKind) -> f32 {
+        f32::min(1.0 / self.probability(monster_id, kind), kind.max_payout())
     }
 }
 
@@ -92,6 +226,20 @@ pub struct Settings {
     pub payout: Payout,
     pub starting_cards: usize,
     pub rounds: usize,
+    pub starting_balance: i32,
+    pub card_price: i32,
+    pub hand_limit: usize,
+    pub max_players: usize,
+    pub interest_rate: f32,
+    pub pre_game_timeout: u32,
+    pub summary_duration: u32,
+    pub field_size: usize,
+    // How long a player can be disconnected before the game stops waiting for them
+    pub afk_timeout: u32,
+    // Whether players who join after the game has started get a seat at the next round. Left out
+    // by the host's form when the box isn't ticked.
+    #[serde(default)]
+    pub late_join: bool,
 }
 
 impl Default for Settings {
@@ -100,6 +248,16 @@ impl Default for Settings {
             payout: Payout::default(),
             starting_cards: 3,
             rounds: 5,
+            starting_balance: 1000,
+            card_price: 100,
+            hand_limit: 5,
+            max_players: 15,
+            interest_rate: 0.051,
+            pre_game_timeout: 90,
+            summary_duration: 15,
+            field_size: 3,
+            afk_timeout: 60,
+            late_join: false,
         }
     }
 }
@@ -109,13 +267,13 @@ impl Default for Settings {
 pub enum Event {
     GameCreated {
         game_id: GameCode,
-        #[serde(default)]
         settings: Settings,
+        // The session that created the game from the TV, the only one allowed to use the host controls
+        host: Uuid,
     },
     PlayerJoined {
         session_id: Uuid,
         name: String,
-        #[serde(default)]
         initial_cards: Vec<Card>,
     },
     ChangedProfile {
@@ -125,10 +283,43 @@ pub enum Event {
     PlayerReady {
         session_id: Uuid,
     },
+    PlayerKicked {
+        session_id: Uuid,
+    },
+    // Presence is driven by the player's websocket, a player who stays disconnected for the AFK
+    // timeout has left and is no longer waited on
+    PlayerConnected {
+        session_id: Uuid,
+    },
+    PlayerDisconnected {
+        session_id: Uuid,
+        time: u32,
+    },
+    PlayerLeft {
+        session_id: Uuid,
+    },
+    // Spectators can watch at any point but have no balance, hand or bets
+    SpectatorJoined {
+        session_id: Uuid,
+        name: String,
+    },
+    // Joined after the game started, they watch until they're seated at the next round
+    PlayerQueued {
+        session_id: Uuid,
+        name: String,
+    },
+    LatePlayerJoined {
+        session_id: Uuid,
+        name: String,
+        initial_cards: Vec<Card>,
+        balance: i32,
+    },
     RoundStarted {
         time: u32,
         odds: Option<Odds>,
         enemies: Option<HashMap<Uuid, Uuid>>,
+        commitment: Option<Digest>,
+        track: Track,
     },
     BoughtCard {
         session_id: Uuid,
@@ -148,13 +339,45 @@ pub enum Event {
         amount: u32,
     },
     PlacedBet(PlacedBet),
+    // The player is done for this round, with or without a bet
+    PlayerReadyToRace {
+        session_id: Uuid,
+    },
+    GamePaused {
+        time: u32,
+    },
+    GameResumed {
+        time: u32,
+    },
+    TimerExtended {
+        seconds: u32,
+    },
     RaceStarted {
         time: u32,
+        reveal: Option<Digest>,
     },
     RaceFinished {
         time: u32,
         results: RaceResults,
     },
+    // Durable wake ups for the process managers, the server re-arms its alarm from these whenever it
+    // starts
+    TimerScheduled {
+        timer: Timer,
+        at: u32,
+    },
+    TimerFired {
+        timer: Timer,
+    },
+    // Follows `GameCreated` when the server has a custom monster roster, so every client picks the
+    // racers from the same monsters. Games without one use the built-in monsters.
+    RosterChosen {
+        monsters: Vec<MonsterDefinition>,
+    },
+    // The round was called off by the host before the race finished, every bet is refunded
+    RoundVoided {
+        time: u32,
+    },
     GameFinished,
 }
 
@@ -163,25 +386,638 @@ impl Event {
         Event::GameCreated {
             game_id: GameCode::random(),
             settings: Settings::default(),
+            host: Uuid::nil(),
         }
     }
 
-    pub fn start_round_now() -> Event {
+    pub fn start_round_at(time: u32) -> Event {
         Event::RoundStarted {
-            time: Event::now(),
+            time,
             odds: None,
             enemies: None,
+            commitment: None,
+            track: Track::Standard,
+        }
+    }
+
+    pub fn start_race_at(time: u32) -> Event {
+        Event::RaceStarted { time, reveal: None }
+    }
+
+    // Presence changes, timers and the roster don't move the game on, they shouldn't hide the event
+    // that did
+    pub fn is_bookkeeping(&self) -> bool {
+        matches!(
+            self,
+            Event::PlayerConnected { .. }
+                | Event::PlayerDisconnected { .. }
+                | Event::PlayerLeft { .. }
+                | Event::TimerScheduled { .. }
+                | Event::TimerFired { .. }
+                | Event::RosterChosen { .. }
+        )
+    }
+}
+
+pub const SCHEMA_VERSION: u32 = 10;
+
+// Who caused an event to be written, system commands issued by the process managers use the nil
+// session id. Since v8 every `session_id` in the log is the player's public id, never their token.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct Origin {
+    pub session_id: Uuid,
+    pub command: String,
+}
+
+impl Origin {
+    pub fn new(session_id: Uuid, command: impl Into<String>) -> Self {
+        Self {
+            session_id,
+            command: command.into(),
         }
     }
 
-    pub fn start_race_now() -> Event {
-        Event::RaceStarted { time: Event::now() }
+    pub fn system() -> Self {
+        Self::new(session::SYSTEM, "ProcessManager")
+    }
+}
+
+// The persisted form of an event, the game logs store these rather than bare events so they can be
+// migrated forward with `EventEnvelope::upcast` when the shape of `Event` changes.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EventEnvelope {
+    pub version: u32,
+    pub sequence: usize,
+    pub timestamp: u32,
+    pub origin: Origin,
+    pub event: Event,
+}
+
+type Upcaster = fn(Value) -> Result<Value>;
+
+// UPCASTERS[n] migrates a serialized envelope from schema version n to n + 1
+const UPCASTERS: [Upcaster; SCHEMA_VERSION as usize] = [
+    upcast_v0, upcast_v1, upcast_v2, upcast_v3, upcast_v4, upcast_v5, upcast_v6, upcast_v7,
+    upcast_v8, upcast_v9,
+];
+
+impl EventEnvelope {
+    pub fn new(sequence: usize, origin: Origin, event: Event, clock: &dyn Clock) -> Self {
+        Self {
+            version: SCHEMA_VERSION,
+            sequence,
+            timestamp: clock.timestamp(),
+            origin,
+            event,
+        }
     }
 
-    pub fn now() -> u32 {
-        SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap()
-            .as_secs() as u32
+    pub fn upcast(sequence: usize, value: Value) -> Result<Self> {
+        let mut value = match value {
+            Value::Object(map) if map.contains_key("version") && map.contains_key("event") => {
+                Value::Object(map)
+            }
+            // Logs written before the envelope existed contain bare events
+            event => json!({
+                "version": 0,
+                "sequence": sequence,
+                "timestamp": 0,
+                "origin": Origin::default(),
+                "event": event,
+            }),
+        };
+
+        loop {
+            let Some(version) = value["version"].as_u64() else {
+                bail!("event envelope is missing a schema version");
+            };
+
+            match version as u32 {
+                SCHEMA_VERSION => break,
+                version if version > SCHEMA_VERSION => {
+                    bail!("event schema version {version} is newer than {SCHEMA_VERSION}")
+                }
+                version => {
+                    value = UPCASTERS[version as usize](value)?;
+                    value["version"] = json!(version + 1);
+                }
+            }
+        }
+
+        Ok(serde_json::from_value(value)?)
+    }
+}
+
+fn insert_default(event: &mut Value, variant: &str, field: &str, default: Value) {
+    if let Some(Value::Object(fields)) = event.get_mut(variant) {
+        fields.entry(field).or_insert(default);
+    }
+}
+
+// v0 events were stored without an envelope and may predate `settings` and `initial_cards`
+fn upcast_v0(mut value: Value) -> Result<Value> {
+    let event = &mut value["event"];
+
+    insert_default(
+        event,
+        "GameCreated",
+        "settings",
+        serde_json::to_value(Settings::default())?,
+    );
+    insert_default(event, "PlayerJoined", "initial_cards", Value::Array(vec![]));
+
+    Ok(value)
+}
+
+fn insert_default_settings(event: &mut Value) -> Result<()> {
+    let Value::Object(defaults) = serde_json::to_value(Settings::default())? else {
+        bail!("settings did not serialize to an object");
+    };
+
+    if let Some(game_created) = event.get_mut("GameCreated") {
+        for (field, default) in defaults {
+            insert_default(game_created, "settings", &field, default);
+        }
+    }
+
+    Ok(())
+}
+
+// v1 settings only had the payout, starting cards and rounds, everything else was hard coded
+fn upcast_v1(mut value: Value) -> Result<Value> {
+    insert_default_settings(&mut value["event"])?;
+
+    Ok(value)
+}
+
+// v2 races always had three runners with the results stored as first, second and third
+fn upcast_v2(mut value: Value) -> Result<Value> {
+    insert_default_settings(&mut value["event"])?;
+
+    if let Some(Value::Object(results)) = value["event"]
+        .get_mut("RaceFinished")
+        .and_then(|race_finished| race_finished.get_mut("results"))
+        && !results.contains_key("placings")
+    {
+        let placings = ["first", "second", "third"]
+            .iter()
+            .filter_map(|place| results.remove(*place))
+            .collect();
+
+        results.insert("placings".into(), Value::Array(placings));
+    }
+
+    Ok(value)
+}
+
+// v3 bets were all bets on the monster to win
+fn upcast_v3(mut value: Value) -> Result<Value> {
+    insert_default(
+        &mut value["event"],
+        "PlacedBet",
+        "kind",
+        json!(BetKind::Win),
+    );
+
+    Ok(value)
+}
+
+// v4 race seeds were derived from the game code and weren't committed to
+fn upcast_v4(mut value: Value) -> Result<Value> {
+    let event = &mut value["event"];
+
+    insert_default(event, "RoundStarted", "commitment", Value::Null);
+    insert_default(event, "RaceStarted", "reveal", Value::Null);
+
+    Ok(value)
+}
+
+// v5 races were all run on the standard track
+fn upcast_v5(mut value: Value) -> Result<Value> {
+    insert_default(
+        &mut value["event"],
+        "RoundStarted",
+        "track",
+        json!(Track::Standard),
+    );
+
+    Ok(value)
+}
+
+// v6 didn't record the host, it's whoever sent the command that created the game. Logs from before
+// origins were recorded end up with the nil session, so nobody can use the host controls.
+fn upcast_v6(mut value: Value) -> Result<Value> {
+    let host = value["origin"]["session_id"].clone();
+
+    insert_default(&mut value["event"], "GameCreated", "host", host);
+
+    Ok(value)
+}
+
+fn derive_player_id(id: Option<&mut Value>) -> Result<()> {
+    if let Some(Value::String(id)) = id {
+        let token = Uuid::parse_str(id)?;
+
+        // The system's own commands weren't made with a token
+        if token != session::SYSTEM {
+            *id = session::legacy_player_id(token).to_string();
+        }
+    }
+
+    Ok(())
+}
+
+// v7 players were identified by their session token, which is also their cookie, so anyone watching
+// the events could act as them. They're now identified by an id derived from the old token in a way no
+// new token can produce, so the old cookie can't be replayed to act as them.
+fn upcast_v7(mut value: Value) -> Result<Value> {
+    derive_player_id(value["origin"].get_mut("session_id"))?;
+
+    // Unit variants like `GameFinished` are plain strings
+    let Value::Object(event) = &mut value["event"] else {
+        return Ok(value);
+    };
+
+    for (variant, fields) in event.iter_mut() {
+        match variant.as_str() {
+            "GameCreated" => derive_player_id(fields.get_mut("host"))?,
+            "RoundStarted" => {
+                if let Some(Value::Object(enemies)) = fields.get_mut("enemies") {
+                    *enemies = std::mem::take(enemies)
+                        .into_iter()
+                        .map(|(player, mut enemy)| {
+                            derive_player_id(Some(&mut enemy))?;
+
+                            Ok((
+                                session::legacy_player_id(Uuid::parse_str(&player)?).to_string(),
+                                enemy,
+                            ))
+                        })
+                        .collect::<Result<_>>()?;
+                }
+            }
+            _ => {
+                derive_player_id(fields.get_mut("session_id"))?;
+
+                if let Some(Value::Object(target)) = fields.get_mut("target") {
+                    derive_player_id(target.get_mut("Player"))?;
+
+                    if let Some(Value::Array(targets)) = target.get_mut("MultiplePlayers") {
+                        for target in targets {
+                            derive_player_id(Some(target))?;
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(value)
+}
+
+// v8 settings had no AFK timeout, disconnected players were waited on forever
+fn upcast_v8(mut value: Value) -> Result<Value> {
+    insert_default_settings(&mut value["event"])?;
+
+    Ok(value)
+}
+
+// v9 games couldn't be joined once they had started
+fn upcast_v9(mut value: Value) -> Result<Value> {
+    insert_default_settings(&mut value["event"])?;
+
+    Ok(value)
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+    use uuid::Uuid;
+
+    use anyhow::bail;
+
+    use super::{
+        BetKind, Card, Event, EventEnvelope, Odds, OddsExt, Origin, Payout, PlacedBet, RaceResults,
+        SCHEMA_VERSION, Settings, Target, Track,
+    };
+    use crate::models::session::legacy_player_id;
+    use crate::time::FixedClock;
+
+    #[test]
+    fn bare_events_are_upcast() -> anyhow::Result<()> {
+        let envelope = EventEnvelope::upcast(0, json!({ "GameCreated": { "game_id": "ABCDEF" } }))?;
+
+        assert_eq!(envelope.version, SCHEMA_VERSION);
+        assert_eq!(envelope.sequence, 0);
+        assert_eq!(
+            envelope.event,
+            Event::GameCreated {
+                game_id: "ABCDEF".try_into()?,
+                settings: Settings::default(),
+                host: Uuid::nil(),
+            }
+        );
+
+        let player = Uuid::new_v4();
+        let envelope = EventEnvelope::upcast(
+            1,
+            json!({ "PlayerJoined": { "session_id": player, "name": "Alice" } }),
+        )?;
+
+        assert_eq!(
+            envelope.event,
+            Event::PlayerJoined {
+                session_id: legacy_player_id(player),
+                name: "Alice".into(),
+                initial_cards: vec![],
+            }
+        );
+
+        assert_eq!(
+            EventEnvelope::upcast(2, json!("GameFinished"))?.event,
+            Event::GameFinished
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn v1_settings_are_filled_with_defaults() -> anyhow::Result<()> {
+        let value = json!({
+            "version": 1,
+            "sequence": 0,
+            "timestamp": 0,
+            "origin": Origin::default(),
+            "event": {
+                "GameCreated": {
+                    "game_id": "ABCDEF",
+                    "settings": { "payout": "Pool", "starting_cards": 0, "rounds": 3 },
+                },
+            },
+        });
+
+        assert_eq!(
+            EventEnvelope::upcast(0, value)?.event,
+            Event::GameCreated {
+                game_id: "ABCDEF".try_into()?,
+                settings: Settings {
+                    payout: Payout::Pool,
+                    starting_cards: 0,
+                    rounds: 3,
+                    ..Default::default()
+                },
+                host: Uuid::nil(),
+            }
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn v2_results_become_placings() -> anyhow::Result<()> {
+        let [first, second, third] = [Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4()];
+
+        let value = json!({
+            "version": 2,
+            "sequence": 0,
+            "timestamp": 0,
+            "origin": Origin::default(),
+            "event": {
+                "RaceFinished": {
+                    "time": 0,
+                    "results": { "first": first, "second": second, "third": third },
+                },
+            },
+        });
+
+        assert_eq!(
+            EventEnvelope::upcast(0, value)?.event,
+            Event::RaceFinished {
+                time: 0,
+                results: RaceResults {
+                    placings: vec![first, second, third]
+                },
+            }
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn v3_bets_are_win_bets() -> anyhow::Result<()> {
+        let [session_id, monster_id] = [Uuid::new_v4(), Uuid::new_v4()];
+
+        let value = json!({
+            "version": 3,
+            "sequence": 0,
+            "timestamp": 0,
+            "origin": Origin::default(),
+            "event": {
+                "PlacedBet": { "session_id": session_id, "monster_id": monster_id, "amount": 100 },
+            },
+        });
+
+        assert_eq!(
+            EventEnvelope::upcast(0, value)?.event,
+            Event::PlacedBet(PlacedBet {
+                session_id: legacy_player_id(session_id),
+                monster_id,
+                amount: 100,
+                kind: BetKind::Win,
+            })
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn v5_rounds_are_on_the_standard_track() -> anyhow::Result<()> {
+        let value = json!({
+            "version": 5,
+            "sequence": 0,
+            "timestamp": 0,
+            "origin": Origin::default(),
+            "event": {
+                "RoundStarted": { "time": 0, "odds": null, "enemies": null, "commitment": null },
+            },
+        });
+
+        assert_eq!(
+            EventEnvelope::upcast(0, value)?.event,
+            Event::RoundStarted {
+                time: 0,
+                odds: None,
+                enemies: None,
+                commitment: None,
+                track: Track::Standard,
+            }
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn v6_games_are_hosted_by_their_creator() -> anyhow::Result<()> {
+        let host = Uuid::new_v4();
+
+        let value = json!({
+            "version": 6,
+            "sequence": 0,
+            "timestamp": 0,
+            "origin": Origin::new(host, "CreateGame"),
+            "event": {
+                "GameCreated": { "game_id": "ABCDEF", "settings": Settings::default() },
+            },
+        });
+
+        assert_eq!(
+            EventEnvelope::upcast(0, value)?.event,
+            Event::GameCreated {
+                game_id: "ABCDEF".try_into()?,
+                settings: Settings::default(),
+                host: legacy_player_id(host),
+            }
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn v7_players_are_identified_by_their_player_id() -> anyhow::Result<()> {
+        let [alice, bob] = [Uuid::new_v4(), Uuid::new_v4()];
+
+        let envelope = |event| {
+            json!({
+                "version": 7,
+                "sequence": 0,
+                "timestamp": 0,
+                "origin": Origin::new(alice, "PlayCard"),
+                "event": event,
+            })
+        };
+
+        let played = EventEnvelope::upcast(
+            0,
+            envelope(json!({
+                "PlayedCard": { "session_id": alice, "card": "Poison", "target": { "Player": bob } },
+            })),
+        )?;
+
+        assert_eq!(played.origin.session_id, legacy_player_id(alice));
+        assert_eq!(
+            played.event,
+            Event::PlayedCard {
+                session_id: legacy_player_id(alice),
+                card: Card::Poison,
+                target: Target::Player(legacy_player_id(bob)),
+            }
+        );
+
+        let round = EventEnvelope::upcast(
+            0,
+            envelope(json!({
+                "RoundStarted": {
+                    "time": 0,
+                    "odds": null,
+                    "enemies": { alice.to_string(): bob, bob.to_string(): alice },
+                    "commitment": null,
+                    "track": "Standard",
+                },
+            })),
+        )?;
+
+        let Event::RoundStarted {
+            enemies: Some(enemies),
+            ..
+        } = round.event
+        else {
+            bail!("expected the round's enemies");
+        };
+
+        assert_eq!(enemies[&legacy_player_id(alice)], legacy_player_id(bob));
+        assert_eq!(enemies[&legacy_player_id(bob)], legacy_player_id(alice));
+
+        assert_eq!(
+            EventEnvelope::upcast(0, envelope(json!("GameFinished")))?.event,
+            Event::GameFinished
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn combination_odds_are_consistent() {
+        let monsters = [
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+        ];
+        let odds = Odds(monsters.iter().copied().zip([0.4, 0.3, 0.2, 0.1]).collect());
+
+        let mut exactas = 0.0;
+
+        for first in monsters {
+            let win = odds.probability(first, BetKind::Win);
+            let place = odds.probability(first, BetKind::Place);
+            let show = odds.probability(first, BetKind::Show);
+
+            assert!(win <= place && place <= show && show <= 1.0);
+
+            for second in monsters.into_iter().filter(|second| *second != first) {
+                exactas += odds.probability(first, BetKind::Exacta { second });
+            }
+        }
+
+        // Exactly two monsters place in every race
+        let sum_of_places = monsters
+            .iter()
+            .map(|monster| odds.probability(*monster, BetKind::Place))
+            .sum::<f32>();
+
+        assert!((exactas - 1.0).abs() < 1e-4);
+        assert!((sum_of_places - 2.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn missing_odds_fall_back_to_an_even_field() {
+        let monsters = (0..5).map(|_| Uuid::new_v4()).collect::<Vec<_>>();
+        let odds = Odds::even(monsters.iter().copied());
+
+        assert!(odds.iter().all(|(_, odds)| *odds == 0.2));
+        assert_eq!(odds.payout(monsters[0]), 5.0);
+        assert_eq!(odds.odds(Uuid::new_v4()), 0.2);
+    }
+
+    #[test]
+    fn current_envelopes_round_trip() -> anyhow::Result<()> {
+        let envelope = EventEnvelope::new(
+            3,
+            Origin::new(Uuid::new_v4(), "ReadyPlayer"),
+            Event::PlayerReady {
+                session_id: Uuid::new_v4(),
+            },
+            &FixedClock::at(1_000),
+        );
+
+        assert_eq!(
+            EventEnvelope::upcast(3, serde_json::to_value(&envelope)?)?,
+            envelope
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn newer_versions_are_rejected() {
+        let value = json!({
+            "version": SCHEMA_VERSION + 1,
+            "sequence": 0,
+            "timestamp": 0,
+            "origin": Origin::default(),
+            "event": "GameFinished",
+        });
+
+        assert!(EventEnvelope::upcast(0, value).is_err());
     }
 }
@@ -0,0 +1,127 @@
+use std::fmt::{Debug, Display};
+
+use anyhow::bail;
+use serde::{Deserialize, Serialize};
+
+use super::{
+    game_code::GameCode,
+    rng::{self, Stream},
+};
+
+// The server keeps a secret for every game that never appears in the event log. When a round
+// starts the hash of that round's reveal is published, and the reveal itself is published once
+// betting has closed. The race is run from a seed taken from the reveal, so nobody can work out the
+// winner ahead of time and the server can't pick a different outcome after seeing the bets.
+pub type Secret = [u8; 32];
+
+pub fn random_secret() -> Secret {
+    rand::random()
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Digest([u8; 32]);
+
+impl Digest {
+    pub fn reveal(secret: &Secret, game_id: GameCode, round: u32) -> Self {
+        Digest(rng::seed(secret, game_id, Stream::Race, round as u64))
+    }
+
+    pub fn commitment(&self) -> Self {
+        Digest(blake3::hash(&self.0).into())
+    }
+
+    pub fn seed(&self) -> u32 {
+        u32::from_le_bytes([self.0[0], self.0[1], self.0[2], self.0[3]])
+    }
+}
+
+// Checks that a revealed value is the one that was committed to before any bets were placed
+pub fn verify(commitment: Digest, reveal: Digest) -> bool {
+    reveal.commitment() == commitment
+}
+
+impl Display for Digest {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.iter().try_for_each(|byte| write!(f, "{byte:02x}"))
+    }
+}
+
+impl Debug for Digest {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Digest").field(&self.to_string()).finish()
+    }
+}
+
+impl TryFrom<&str> for Digest {
+    type Error = anyhow::Error;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        if value.len() != 64 || !value.is_ascii() {
+            bail!("failed to convert from String to Digest, expected 64 hex characters");
+        }
+
+        let mut bytes = [0; 32];
+
+        for (byte, chunk) in bytes.iter_mut().zip(value.as_bytes().chunks(2)) {
+            *byte = u8::from_str_radix(std::str::from_utf8(chunk)?, 16)?;
+        }
+
+        Ok(Digest(bytes))
+    }
+}
+
+impl Serialize for Digest {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Digest {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        String::deserialize(deserializer)?
+            .as_str()
+            .try_into()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::models::game_code::GameCode;
+
+    use super::{Digest, random_secret, verify};
+
+    #[test]
+    fn reveals_match_their_commitments() {
+        let secret = random_secret();
+        let game_id = GameCode::random();
+
+        let reveal = Digest::reveal(&secret, game_id, 1);
+        let other = Digest::reveal(&secret, game_id, 2);
+
+        assert!(verify(reveal.commitment(), reveal));
+        assert!(!verify(reveal.commitment(), other));
+        assert!(!verify(
+            reveal.commitment(),
+            Digest::reveal(&random_secret(), game_id, 1)
+        ));
+    }
+
+    #[test]
+    fn digests_round_trip_as_hex() -> anyhow::Result<()> {
+        let digest = Digest::reveal(&random_secret(), GameCode::random(), 1);
+        let json = serde_json::to_string(&digest)?;
+
+        assert_eq!(json.len(), 64 + 2);
+        assert_eq!(serde_json::from_str::<Digest>(&json)?, digest);
+        assert!(serde_json::from_str::<Digest>("\"not hex\"").is_err());
+
+        Ok(())
+    }
+}
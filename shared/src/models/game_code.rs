@@ -26,6 +26,14 @@ impl GameCode {
     }
 }
 
+// Stands in for the code of a game that hasn't been created yet, so projections over an empty log
+// still give the same answer every time
+impl Default for GameCode {
+    fn default() -> Self {
+        GameCode(*b"AAAAAA")
+    }
+}
+
 pub fn generate_game_code() -> GameCode {
     GameCode(
         Uniform::from(b'A'..=b'Z')
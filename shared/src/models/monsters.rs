@@ -1,9 +1,11 @@
+use std::borrow::Cow;
+
 use uuid::Uuid;
 
-#[derive(Debug, Copy, Clone, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
 pub struct Monster {
-    pub name: &'static str,
-    pub blueprint_name: &'static str,
+    pub name: Cow<'static, str>,
+    pub blueprint_name: Cow<'static, str>,
     pub uuid: Uuid,
 
     // How fast they jump
@@ -13,10 +15,10 @@ pub struct Monster {
 
     pub starting_position: f32,
 
-    pub idle_animation: &'static str,
-    pub jump_animation: &'static str,
-    pub dance_animation: &'static str,
-    pub death_animation: &'static str,
+    pub idle_animation: Cow<'static, str>,
+    pub jump_animation: Cow<'static, str>,
+    pub dance_animation: Cow<'static, str>,
+    pub death_animation: Cow<'static, str>,
 
     pub jump_delay: f32,
     pub jump_end: f32,
@@ -26,8 +28,8 @@ pub struct Monster {
 
 impl Monster {
     const DEFAULT: Monster = Monster {
-        name: "Unnamed",
-        blueprint_name: "no_blueprint_set",
+        name: Cow::Borrowed("Unnamed"),
+        blueprint_name: Cow::Borrowed("no_blueprint_set"),
         uuid: Uuid::from_u128(0),
         dexterity: 5,
         strength: 5,
@@ -35,10 +37,10 @@ impl Monster {
         scale: 1.0,
         jump_delay: 0.3,
         jump_end: 0.1,
-        idle_animation: "CharacterArmature|Idle",
-        jump_animation: "CharacterArmature|Jump",
-        dance_animation: "CharacterArmature|Dance",
-        death_animation: "CharacterArmature|Death",
+        idle_animation: Cow::Borrowed("CharacterArmature|Idle"),
+        jump_animation: Cow::Borrowed("CharacterArmature|Jump"),
+        dance_animation: Cow::Borrowed("CharacterArmature|Dance"),
+        death_animation: Cow::Borrowed("CharacterArmature|Death"),
     };
 }
 
@@ -50,102 +52,102 @@ impl Default for Monster {
 
 pub const MONSTERS: [Monster; 11] = [
     Monster {
-        name: "Cactoro",
+        name: Cow::Borrowed("Cactoro"),
         uuid: Uuid::from_u128(0xb19768d8fce94b66a2d7ea84799c0101u128),
-        blueprint_name: "library/Monster_Cactoro.glb",
+        blueprint_name: Cow::Borrowed("library/Monster_Cactoro.glb"),
         dexterity: 5,
         strength: 4,
         ..Monster::DEFAULT
     },
     Monster {
-        name: "Purglehorn",
+        name: Cow::Borrowed("Purglehorn"),
         uuid: Uuid::from_u128(0x99a7c5d8c06744eeb856df9d6b04c4e8u128),
-        blueprint_name: "library/Monster_Alien.glb",
+        blueprint_name: Cow::Borrowed("library/Monster_Alien.glb"),
         dexterity: 5,
         strength: 5,
         ..Monster::DEFAULT
     },
     Monster {
-        name: "Mawshroom",
+        name: Cow::Borrowed("Mawshroom"),
         uuid: Uuid::from_u128(0xf8a2f4560fa44e89b915f0b0de101a1au128),
-        blueprint_name: "library/Monster_Mushnub.glb",
+        blueprint_name: Cow::Borrowed("library/Monster_Mushnub.glb"),
         dexterity: 1,
         strength: 9,
         ..Monster::DEFAULT
     },
     Monster {
-        name: "Mechapanda",
+        name: Cow::Borrowed("Mechapanda"),
         uuid: Uuid::from_u128(0x0ef5f3373cea4c9ca6655bd3e7bc4c63u128),
-        blueprint_name: "library/Monster_Mech.glb",
+        blueprint_name: Cow::Borrowed("library/Monster_Mech.glb"),
         dexterity: 4,
         strength: 7,
-        idle_animation: "RobotArmature|Idle",
-        jump_animation: "RobotArmature|Jump",
-        dance_animation: "RobotArmature|Dance",
-        death_animation: "RobotArmature|Death",
+        idle_animation: Cow::Borrowed("RobotArmature|Idle"),
+        jump_animation: Cow::Borrowed("RobotArmature|Jump"),
+        dance_animation: Cow::Borrowed("RobotArmature|Dance"),
+        death_animation: Cow::Borrowed("RobotArmature|Death"),
         ..Monster::DEFAULT
     },
     Monster {
-        name: "Finflare",
+        name: Cow::Borrowed("Finflare"),
         uuid: Uuid::from_u128(0x6cb10197a7234cf980f7fb957f7eb9f1u128),
-        blueprint_name: "library/Monster_Fish.glb",
+        blueprint_name: Cow::Borrowed("library/Monster_Fish.glb"),
         dexterity: 7,
         strength: 3,
         ..Monster::DEFAULT
     },
     Monster {
-        name: "Green Spiky Thing",
+        name: Cow::Borrowed("Green Spiky Thing"),
         uuid: Uuid::from_u128(0xcbde634a2d3648f383b3c7e45cc864b7u128),
-        blueprint_name: "library/Monster_Green_Spiky.glb",
+        blueprint_name: Cow::Borrowed("library/Monster_Green_Spiky.glb"),
         dexterity: 3,
         strength: 7,
         ..Monster::DEFAULT
     },
     Monster {
-        name: "Cranius",
+        name: Cow::Borrowed("Cranius"),
         uuid: Uuid::from_u128(0x73c68289e1334859a0f4e45883076e10u128),
-        blueprint_name: "library/Monster_Pink_Slime.glb",
+        blueprint_name: Cow::Borrowed("library/Monster_Pink_Slime.glb"),
         dexterity: 9,
         strength: 1,
         ..Monster::DEFAULT
     },
     Monster {
-        name: "Cluckerhead",
+        name: Cow::Borrowed("Cluckerhead"),
         uuid: Uuid::from_u128(0x9f987f8ff320446e8930740aca46954fu128),
-        blueprint_name: "library/Monster_Chicken.glb",
+        blueprint_name: Cow::Borrowed("library/Monster_Chicken.glb"),
         dexterity: 6,
         strength: 3,
         ..Monster::DEFAULT
     },
     Monster {
-        name: "Fangmaw",
+        name: Cow::Borrowed("Fangmaw"),
         uuid: Uuid::from_u128(0xb4775b5b2e1f42debe985d3d7890db0du128),
-        blueprint_name: "library/Monster_Yeti.glb",
+        blueprint_name: Cow::Borrowed("library/Monster_Yeti.glb"),
         dexterity: 5,
         strength: 5,
         ..Monster::DEFAULT
     },
     Monster {
-        name: "Dave",
+        name: Cow::Borrowed("Dave"),
         uuid: Uuid::from_u128(0x331539f0d6934d09b6de219bd3767ede),
-        blueprint_name: "library/Monster_Dave.glb",
+        blueprint_name: Cow::Borrowed("library/Monster_Dave.glb"),
         dexterity: 0,
         strength: 10,
-        jump_animation: "CharacterArmature|Roll",
-        dance_animation: "CharacterArmature|Wave",
+        jump_animation: Cow::Borrowed("CharacterArmature|Roll"),
+        dance_animation: Cow::Borrowed("CharacterArmature|Wave"),
         scale: 2.5,
         jump_delay: 0.001,
         jump_end: 0.001,
         ..Monster::DEFAULT
     },
     Monster {
-        name: "Zombone",
+        name: Cow::Borrowed("Zombone"),
         uuid: Uuid::from_u128(0x766848319d864928b6c4ac3bff3b9897),
-        blueprint_name: "library/Monster_Zombie.glb",
+        blueprint_name: Cow::Borrowed("library/Monster_Zombie.glb"),
         dexterity: 5,
         strength: 5,
-        jump_animation: "CharacterArmature|Crawl",
-        dance_animation: "CharacterArmature|HitReact",
+        jump_animation: Cow::Borrowed("CharacterArmature|Crawl"),
+        dance_animation: Cow::Borrowed("CharacterArmature|HitReact"),
         scale: 2.5,
         jump_delay: 0.05,
         jump_end: 0.0001,
@@ -1,7 +1,7 @@
 use anyhow::Result;
+use drop_idle_players::DropIdlePlayers;
 use finish_game::FinishGame;
 use finish_race::FinishRace;
-use im::Vector;
 use start_game::StartGame;
 use start_race::StartRace;
 use start_round::StartRound;
@@ -9,48 +9,72 @@ use start_round::StartRound;
 use super::{
     commands::{Command, CommandHandler},
     events::Event,
+    projections::GameView,
+    session,
+    timers::Timer,
 };
 use crate::time::*;
 
+pub mod drop_idle_players;
 pub mod finish_game;
 pub mod finish_race;
 pub mod start_game;
 pub mod start_race;
 pub mod start_round;
 
-const PROCESS_MANAGERS: [&'static dyn ProcessManager; 5] = [
+const PROCESS_MANAGERS: [&'static dyn ProcessManager; 6] = [
+    &DropIdlePlayers,
     &StartGame,
     &StartRace,
     &FinishRace,
     &StartRound,
     &FinishGame,
 ];
-const ALARMS: [&'static dyn AlarmProcessor; 3] = [&StartRace, &FinishRace, &StartRound];
+const ALARMS: [&'static dyn AlarmProcessor; 4] =
+    [&StartRace, &FinishRace, &StartRound, &DropIdlePlayers];
 
 pub trait ProcessManager: Send + Sync + 'static {
-    fn process(&self, events: &Vector<Event>) -> Option<Command>;
+    fn process(&self, game: &GameView, clock: &dyn Clock) -> Option<Command>;
 }
 
-pub trait AlarmProcessor: Send + Send + 'static {
-    fn alarm(&self, events: &Vector<Event>) -> Option<Alarm>;
+// Process managers that act once a deadline passes say when they next need to be woken up, the
+// deadline is recorded as a named timer in the event log
+pub trait AlarmProcessor: Send + Sync + 'static {
+    fn alarm(&self, game: &GameView) -> Option<(Timer, SystemTime)>;
 }
 
+// How long until the earliest pending timer, there's a single alarm per game that's re-armed from
+// the event log every time the process managers run
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Alarm(pub Duration);
 
-pub fn run_processors(events: &Vector<Event>) -> Result<(Vec<Event>, Option<Alarm>)> {
-    let mut events = events.clone();
+pub fn run_processors(game: &GameView, clock: &dyn Clock) -> Result<(Vec<Event>, Option<Alarm>)> {
+    // Nothing moves on by itself while the host has the game paused
+    if game.paused() {
+        return Ok((vec![], None));
+    }
+
+    let now = clock.now();
+
+    let mut game = game.clone();
     let mut output = vec![];
-    let mut output_alarm = None;
+
+    // A due timer has done its job by waking the game up, the process managers check the time
+    // themselves
+    for timer in game.due_timers(now) {
+        let event = Event::TimerFired { timer };
+        game.apply(event.clone());
+        output.push(event);
+    }
 
     'outer: loop {
         for process_manager in PROCESS_MANAGERS {
-            let Some(command) = process_manager.process(&events) else {
+            let Some(command) = process_manager.process(&game, clock) else {
                 continue;
             };
 
-            for event in Command::handle(uuid::Uuid::nil(), &events, command)? {
-                events.push_back(event.clone());
+            for event in Command::handle(session::SYSTEM, &game, command, clock)? {
+                game.apply(event.clone());
                 output.push(event);
             }
 
@@ -58,38 +82,58 @@ pub fn run_processors(events: &Vector<Event>) -> Result<(Vec<Event>, Option<Alar
             continue 'outer;
         }
 
-        for alarm in ALARMS {
-            let Some(alarm) = alarm.alarm(&events) else {
-                continue;
-            };
+        // No processor triggered a command, stop now
+        break;
+    }
 
-            if output_alarm.is_some() {
-                tracing::warn!("multiple alarms output in a single processor step");
-            }
+    for alarm in ALARMS {
+        let Some((timer, deadline)) = alarm.alarm(&game) else {
+            continue;
+        };
 
-            output_alarm = Some(alarm);
+        // Rounded up so the timer never fires before the deadline. Deadlines that have already
+        // passed were acted on above.
+        let at = deadline
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs_f64()
+            .ceil() as u32;
+
+        if at as u64 <= now.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs()
+            || game.pending_timers().get(&timer) == Some(&at)
+        {
+            continue;
         }
 
-        // No processor triggered a command, stop now
-        break;
+        let event = Event::TimerScheduled { timer, at };
+        game.apply(event.clone());
+        output.push(event);
     }
 
-    Ok((output, output_alarm))
+    let alarm = game
+        .next_timer()
+        .map(|(_, at)| Alarm(at.duration_since(now).unwrap_or_default()));
+
+    Ok((output, alarm))
 }
 
 #[cfg(test)]
 mod test {
 
     use anyhow::bail;
-    use im::Vector;
+    use im::{OrdMap, Vector};
     use uuid::Uuid;
 
     use crate::{
         models::{
             events::Event,
             process_managers::{Alarm, run_processors},
+            projections::GameView,
+            timers::Timer,
+            tracks::Track,
         },
         test::init_tracing,
+        time::{Clock, Duration, FixedClock, ManualClock},
     };
 
     #[test]
@@ -107,8 +151,14 @@ mod test {
         ]);
 
         if !matches!(
-            &run_processors(&events)?.0[..],
-            [Event::RoundStarted { .. }]
+            &run_processors(&GameView::from(&events), &FixedClock::at(1_000))?.0[..],
+            [
+                Event::RoundStarted { .. },
+                Event::TimerScheduled {
+                    timer: Timer::StartRace,
+                    at: 1_090,
+                }
+            ]
         ) {
             bail!("didn't match");
         };
@@ -134,15 +184,22 @@ mod test {
                 time: 0,
                 odds: None,
                 enemies: None,
+                commitment: None,
+                track: Track::Standard,
             },
             Event::RoundStarted {
                 time: 0,
                 odds: None,
                 enemies: None,
+                commitment: None,
+                track: Track::Standard,
             },
         ]);
 
-        assert!(matches!(run_processors(&events)?.1, Some(Alarm(_)),));
+        assert_eq!(
+            run_processors(&GameView::from(&events), &FixedClock::at(30))?.1,
+            Some(Alarm(Duration::from_secs(60))),
+        );
 
         Ok(())
     }
@@ -150,6 +207,7 @@ mod test {
     #[test]
     fn race_alarm_set() -> anyhow::Result<()> {
         let a = Uuid::new_v4();
+        let clock = FixedClock::at(1_000);
 
         let events = Vector::from_iter([
             Event::new_game(),
@@ -159,11 +217,14 @@ mod test {
                 initial_cards: vec![],
             },
             Event::PlayerReady { session_id: a },
-            Event::start_round_now(),
-            Event::start_race_now(),
+            Event::start_round_at(clock.timestamp()),
+            Event::start_race_at(clock.timestamp()),
         ]);
 
-        if !matches!(run_processors(&events)?.1, Some(Alarm(_))) {
+        if !matches!(
+            run_processors(&GameView::from(&events), &clock)?.1,
+            Some(Alarm(_))
+        ) {
             bail!("race alarm didn't set");
         }
 
@@ -173,10 +234,10 @@ mod test {
     #[test]
     fn race_finishes_automatically() -> anyhow::Result<()> {
         let a = Uuid::new_v4();
+        let clock = ManualClock::at(1_000);
+        let now = clock.timestamp();
 
-        let now = Event::now();
-
-        let events = Vector::from_iter([
+        let mut game = GameView::from(&Vector::from_iter([
             Event::new_game(),
             Event::PlayerJoined {
                 session_id: a,
@@ -188,17 +249,123 @@ mod test {
                 time: now,
                 odds: None,
                 enemies: None,
+                commitment: None,
+                track: Track::Standard,
             },
-            Event::RaceStarted { time: now - 60 },
-        ]);
+            Event::RaceStarted {
+                time: now,
+                reveal: None,
+            },
+        ]));
+
+        let (events, alarm) = run_processors(&game, &clock)?;
+        assert!(matches!(
+            &events[..],
+            [Event::TimerScheduled {
+                timer: Timer::FinishRace,
+                ..
+            }]
+        ));
+
+        for event in events {
+            game.apply(event);
+        }
+
+        let Some(Alarm(duration)) = alarm else {
+            bail!("race alarm didn't set");
+        };
+        clock.advance(duration);
 
         if !matches!(
-            &run_processors(&events)?.0[..],
-            [Event::RaceFinished { .. }]
+            &run_processors(&game, &clock)?.0[..],
+            [
+                Event::TimerFired {
+                    timer: Timer::FinishRace
+                },
+                Event::RaceFinished { .. },
+                Event::TimerScheduled {
+                    timer: Timer::StartRound,
+                    ..
+                }
+            ]
         ) {
             bail!("race didn't finish");
         }
 
         Ok(())
     }
+
+    #[test]
+    fn several_timers_can_be_pending() -> anyhow::Result<()> {
+        let [a, b] = [Uuid::new_v4(), Uuid::new_v4()];
+        let clock = ManualClock::at(1_000);
+        let now = clock.timestamp();
+
+        let mut game = GameView::from(&Vector::from_iter([
+            Event::new_game(),
+            Event::PlayerJoined {
+                session_id: a,
+                name: "A".into(),
+                initial_cards: vec![],
+            },
+            Event::PlayerJoined {
+                session_id: b,
+                name: "B".into(),
+                initial_cards: vec![],
+            },
+            Event::PlayerReady { session_id: a },
+            Event::PlayerReady { session_id: b },
+            Event::start_round_at(now),
+            Event::start_round_at(now),
+            Event::PlayerDisconnected {
+                session_id: b,
+                time: now,
+            },
+        ]));
+
+        let (events, alarm) = run_processors(&game, &clock)?;
+
+        for event in events {
+            game.apply(event);
+        }
+
+        let settings = game.settings();
+        assert_eq!(
+            game.pending_timers().clone(),
+            OrdMap::from_iter([
+                (Timer::StartRace, now + settings.pre_game_timeout),
+                (Timer::DropIdlePlayers, now + settings.afk_timeout),
+            ])
+        );
+        // The alarm is for whichever is due first
+        assert_eq!(
+            alarm,
+            Some(Alarm(Duration::from_secs(settings.afk_timeout as u64)))
+        );
+
+        // Nothing changed so nothing is scheduled again
+        assert_eq!(run_processors(&game, &clock)?.0, vec![]);
+
+        clock.advance(Duration::from_secs(settings.afk_timeout as u64));
+
+        let (events, alarm) = run_processors(&game, &clock)?;
+        assert_eq!(
+            events[..2],
+            [
+                Event::TimerFired {
+                    timer: Timer::DropIdlePlayers
+                },
+                Event::PlayerLeft { session_id: b },
+            ]
+        );
+        // The race timer is still pending
+        assert_eq!(
+            alarm,
+            Some(Alarm(Duration::from_secs(
+                (settings.pre_game_timeout - settings.afk_timeout) as u64
+            )))
+        );
+
+        Ok(())
+    }
 }
@@ -0,0 +1,125 @@
+use super::{AlarmProcessor, ProcessManager};
+use crate::models::{
+    commands::{Command, drop_idle_player},
+    projections::GameView,
+    timers::Timer,
+};
+use crate::time::*;
+
+// Players whose phone has been disconnected for longer than the AFK timeout stop being waited on,
+// so one dead battery can't stall the lobby or the betting
+pub struct DropIdlePlayers;
+
+impl AlarmProcessor for DropIdlePlayers {
+    fn alarm(&self, game: &GameView) -> Option<(Timer, SystemTime)> {
+        if game.game_finished() {
+            return None;
+        }
+
+        let (_, deadline) = game.next_afk_deadline()?;
+
+        Some((Timer::DropIdlePlayers, deadline))
+    }
+}
+
+impl ProcessManager for DropIdlePlayers {
+    fn process(&self, game: &GameView, clock: &dyn Clock) -> Option<Command> {
+        if game.game_finished() {
+            return None;
+        }
+
+        let (session_id, deadline) = game.next_afk_deadline()?;
+
+        (clock.now() >= deadline).then_some(Command::DropIdlePlayer(drop_idle_player::Input {
+            session_id,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use im::vector;
+    use uuid::Uuid;
+
+    use crate::models::{
+        events::{BetKind, Event, PlacedBet},
+        process_managers::{Alarm, run_processors},
+        projections::GameView,
+        timers::Timer,
+    };
+    use crate::time::{Clock, Duration, ManualClock};
+
+    #[test]
+    fn players_who_stay_away_stop_holding_up_the_game() -> anyhow::Result<()> {
+        let [a, b] = [Uuid::new_v4(), Uuid::new_v4()];
+        let clock = ManualClock::at(1_000);
+
+        let mut game = GameView::from(&vector![
+            Event::new_game(),
+            Event::PlayerJoined {
+                session_id: a,
+                name: "A".into(),
+                initial_cards: vec![],
+            },
+            Event::PlayerJoined {
+                session_id: b,
+                name: "B".into(),
+                initial_cards: vec![],
+            },
+            Event::PlayerReady { session_id: a },
+            Event::PlayerDisconnected {
+                session_id: b,
+                time: clock.timestamp(),
+            },
+        ]);
+
+        // Still within the AFK timeout, wake up when it runs out
+        let (events, alarm) = run_processors(&game, &clock)?;
+        assert!(matches!(
+            &events[..],
+            [Event::TimerScheduled {
+                timer: Timer::DropIdlePlayers,
+                ..
+            }]
+        ));
+        assert_eq!(alarm, Some(Alarm(Duration::from_secs(60))));
+
+        for event in events {
+            game.apply(event);
+        }
+
+        let mut away = game.clone();
+        clock.advance(Duration::from_secs(60));
+
+        let (events, _) = run_processors(&away, &clock)?;
+        assert!(matches!(
+            &events[..],
+            [
+                Event::TimerFired { .. },
+                Event::PlayerLeft { session_id },
+                Event::RoundStarted { .. },
+                ..
+            ] if *session_id == b
+        ));
+
+        for event in events {
+            away.apply(event);
+        }
+
+        // Nobody waits for them to bet either
+        away.apply(Event::PlacedBet(PlacedBet {
+            session_id: a,
+            monster_id: Uuid::new_v4(),
+            amount: 100,
+            kind: BetKind::Win,
+        }));
+        assert!(away.all_players_have_bet());
+
+        // Coming back puts them back in the game
+        game.apply(Event::PlayerConnected { session_id: b });
+        assert_eq!(game.next_afk_deadline(), None);
+        assert!(!game.all_players_ready());
+
+        Ok(())
+    }
+}
@@ -1,20 +1,16 @@
-use im::Vector;
-
 use super::ProcessManager;
-use crate::models::projections;
-use crate::models::{commands::Command, events::Event};
+use crate::models::{commands::Command, projections::GameView};
+use crate::time::Clock;
 
 pub struct FinishGame;
 
-pub const SUMMARY_DURATION: f32 = 15.0;
-
 impl ProcessManager for FinishGame {
-    fn process(&self, events: &Vector<Event>) -> Option<Command> {
-        if matches!(events.last(), Some(Event::GameFinished)) {
+    fn process(&self, game: &GameView, _clock: &dyn Clock) -> Option<Command> {
+        if game.finish_recorded() {
             return None;
         }
 
-        if projections::game_finished(events) {
+        if game.game_finished() {
             return Some(Command::FinishGame(()));
         }
 
@@ -1,37 +1,35 @@
-use im::Vector;
-
-use super::{Alarm, AlarmProcessor, ProcessManager};
-use crate::models::{commands::Command, events::Event, projections};
+use super::{AlarmProcessor, ProcessManager};
+use crate::models::{commands::Command, projections::GameView, timers::Timer};
 use crate::time::*;
 
 pub struct FinishRace;
 
 impl AlarmProcessor for FinishRace {
-    fn alarm(&self, events: &Vector<Event>) -> Option<Alarm> {
-        if !matches!(events.last(), Some(Event::RaceStarted { .. })) {
-            return None;
-        }
+    fn alarm(&self, game: &GameView) -> Option<(Timer, SystemTime)> {
+        let start = game.currently_racing()?;
 
-        let duration = projections::pre_race_duration(events)
-            + Duration::from_secs_f32(projections::race::race_duration(events));
+        let duration = game.pre_race_duration() + Duration::from_secs_f32(game.race_duration());
 
-        Some(Alarm(duration))
+        Some((
+            Timer::FinishRace,
+            UNIX_EPOCH + Duration::from_secs(start as u64) + duration,
+        ))
     }
 }
 
 impl ProcessManager for FinishRace {
-    fn process(&self, events: &Vector<Event>) -> Option<Command> {
-        let Some(start) = projections::currently_racing(events) else {
+    fn process(&self, game: &GameView, clock: &dyn Clock) -> Option<Command> {
+        let Some(start) = game.currently_racing() else {
             tracing::debug!("no race in progress");
             return None;
         };
 
-        let duration = projections::pre_race_duration(events)
-            + Duration::from_secs_f32(projections::race::race_duration(events) - 1.);
+        let duration =
+            game.pre_race_duration() + Duration::from_secs_f32(game.race_duration() - 1.);
 
-        tracing::debug!(?duration, now = ?SystemTime::now(), ?start);
+        tracing::debug!(?duration, now = ?clock.now(), ?start);
 
-        if SystemTime::now() >= UNIX_EPOCH + Duration::from_secs(start as u64) + duration {
+        if clock.now() >= UNIX_EPOCH + Duration::from_secs(start as u64) + duration {
             return Some(Command::FinishRace(()));
         }
 
@@ -48,11 +46,14 @@ mod test {
     use crate::{
         models::{
             commands::Command,
-            events::{Event, PlacedBet, Settings},
+            events::{BetKind, Event, PlacedBet, Settings},
             game_code::GameCode,
             process_managers::ProcessManager,
+            projections::GameView,
+            tracks::Track,
         },
         test::init_tracing,
+        time::FixedClock,
     };
 
     use super::FinishRace;
@@ -61,10 +62,14 @@ mod test {
     fn race_finish_durability() -> Result<()> {
         init_tracing();
 
+        // A minute after the race started, well past the end of it
+        let clock = FixedClock::at(1_060);
+
         let events = vector![
             Event::GameCreated {
                 game_id: GameCode::random(),
-                settings: Settings::default()
+                settings: Settings::default(),
+                host: Uuid::nil()
             },
             Event::PlayerJoined {
                 session_id: Uuid::new_v4(),
@@ -75,21 +80,28 @@ mod test {
                 session_id: Uuid::new_v4(),
             },
             Event::RoundStarted {
-                time: Event::now(),
+                time: 1_000,
                 odds: None,
                 enemies: None,
+                commitment: None,
+                track: Track::Standard,
             },
             Event::RaceStarted {
-                time: Event::now() - 60,
+                time: 1_000,
+                reveal: None,
             },
             Event::PlacedBet(PlacedBet {
                 session_id: Uuid::new_v4(),
                 monster_id: Uuid::new_v4(),
-                amount: 1000
+                amount: 1000,
+                kind: BetKind::Win
             })
         ];
 
-        assert_eq!(FinishRace.process(&events), Some(Command::FinishRace(())));
+        assert_eq!(
+            FinishRace.process(&GameView::from(&events), &clock),
+            Some(Command::FinishRace(()))
+        );
 
         Ok(())
     }
@@ -1,18 +1,19 @@
-use im::Vector;
-
-use crate::models::{commands::Command, events::Event, projections};
+use crate::models::{commands::Command, projections::GameView};
+use crate::time::Clock;
 
 use super::ProcessManager;
 
 pub struct StartGame;
 
 impl ProcessManager for StartGame {
-    fn process(&self, events: &Vector<Event>) -> Option<Command> {
-        if !matches!(events.last(), Some(Event::PlayerReady { .. })) {
+    fn process(&self, game: &GameView, _clock: &dyn Clock) -> Option<Command> {
+        // Readying up isn't the only way to start, kicking the last player who wasn't ready or them
+        // leaving lets everyone else start too
+        if game.game_has_started() || game.active_player_count() == 0 {
             return None;
         }
 
-        projections::all_players_ready(events).then_some(Command::StartRound(()))
+        game.all_players_ready().then_some(Command::StartRound(()))
     }
 }
 
@@ -26,7 +27,9 @@ mod test {
         events::{Event, Settings},
         game_code::GameCode,
         process_managers::ProcessManager,
+        projections::GameView,
     };
+    use crate::time::SystemClock;
 
     use super::StartGame;
 
@@ -39,6 +42,7 @@ mod test {
             Event::GameCreated {
                 game_id: GameCode::random(),
                 settings: Settings::default(),
+                host: Uuid::nil(),
             },
             Event::PlayerJoined {
                 session_id: a,
@@ -47,7 +51,10 @@ mod test {
             },
         ]);
 
-        assert_eq!(None, StartGame.process(&events));
+        assert_eq!(
+            None,
+            StartGame.process(&GameView::from(&events), &SystemClock)
+        );
 
         events.push_back(Event::PlayerJoined {
             session_id: b,
@@ -67,6 +74,7 @@ mod test {
             Event::GameCreated {
                 game_id: GameCode::random(),
                 settings: Settings::default(),
+                host: Uuid::nil(),
             },
             Event::PlayerJoined {
                 session_id: a,
@@ -82,6 +90,9 @@ mod test {
             Event::PlayerReady { session_id: b },
         ]);
 
-        assert_eq!(Some(Command::StartRound(())), StartGame.process(&events));
+        assert_eq!(
+            Some(Command::StartRound(())),
+            StartGame.process(&GameView::from(&events), &SystemClock)
+        );
     }
 }
@@ -1,52 +1,39 @@
-use im::Vector;
 use tracing::instrument;
 
-use super::{Alarm, AlarmProcessor, ProcessManager};
-use crate::models::projections;
-use crate::models::{commands::Command, events::Event};
+use super::{AlarmProcessor, ProcessManager};
+use crate::models::{commands::Command, projections::GameView, timers::Timer};
 use crate::time::*;
 
-pub const PRE_GAME_TIMEOUT: u32 = 90;
-
 pub struct StartRace;
 
 impl AlarmProcessor for StartRace {
-    fn alarm(&self, events: &Vector<Event>) -> Option<Alarm> {
-        if !matches!(events.last(), Some(Event::RoundStarted { .. })) {
-            return None;
-        }
-
-        if projections::time_left_in_pregame(events).is_none() {
-            return None;
-        }
+    // The host can move the deadline, the timer follows it
+    fn alarm(&self, game: &GameView) -> Option<(Timer, SystemTime)> {
+        game.currently_betting()?;
 
-        Some(Alarm(Duration::from_secs(PRE_GAME_TIMEOUT as u64)))
+        Some((Timer::StartRace, game.pregame_deadline()?))
     }
 }
 
 impl ProcessManager for StartRace {
     #[instrument(skip_all)]
-    fn process(&self, events: &Vector<Event>) -> Option<Command> {
-        let Some(start) = projections::currently_betting(events) else {
+    fn process(&self, game: &GameView, clock: &dyn Clock) -> Option<Command> {
+        if game.currently_betting().is_none() {
             tracing::debug!("no betting in progress");
             return None;
-        };
+        }
 
-        if projections::all_players_have_bet(events) {
-            tracing::debug!("all players have bet");
+        if game.all_players_ready_to_race() {
+            tracing::debug!("all players are ready to race");
             return Some(Command::StartRace(()));
         }
 
-        if projections::time_left_in_pregame(events).is_none() {
+        let Some(deadline) = game.pregame_deadline() else {
             tracing::debug!("no timer for this round");
             return None;
-        }
+        };
 
-        if SystemTime::now()
-            >= UNIX_EPOCH
-                + Duration::from_secs(start as u64)
-                + Duration::from_secs(PRE_GAME_TIMEOUT as u64)
-        {
+        if clock.now() >= deadline {
             tracing::debug!("Starting race");
             return Some(Command::StartRace(()));
         }
@@ -65,9 +52,11 @@ mod test {
 
     use crate::models::{
         commands::Command,
-        events::{Event, PlacedBet},
+        events::{BetKind, Event, PlacedBet},
         game_code::GameCode,
         process_managers::ProcessManager,
+        projections::GameView,
+        tracks::Track,
     };
 
     use super::StartRace;
@@ -76,11 +65,13 @@ mod test {
     fn race_does_not_start() {
         let a: Uuid = Uuid::new_v4();
         let b = Uuid::new_v4();
+        let clock = FixedClock::at(1_000);
 
         let events = Vector::from_iter([
             Event::GameCreated {
                 game_id: GameCode::random(),
                 settings: Settings::default(),
+                host: Uuid::nil(),
             },
             Event::PlayerJoined {
                 session_id: a,
@@ -94,26 +85,29 @@ mod test {
             },
             Event::PlayerReady { session_id: a },
             Event::PlayerReady { session_id: b },
-            Event::start_round_now(),
+            Event::start_round_at(clock.timestamp()),
             Event::PlacedBet(PlacedBet {
                 session_id: a,
                 monster_id: Uuid::new_v4(),
                 amount: 100,
+                kind: BetKind::Win,
             }),
         ]);
 
-        assert_eq!(None, StartRace.process(&events));
+        assert_eq!(None, StartRace.process(&GameView::from(&events), &clock));
     }
 
     #[test]
     fn race_does_start() {
         let a = Uuid::new_v4();
         let b = Uuid::new_v4();
+        let clock = FixedClock::at(1_000);
 
         let events = Vector::from_iter([
             Event::GameCreated {
                 game_id: GameCode::random(),
                 settings: Settings::default(),
+                host: Uuid::nil(),
             },
             Event::PlayerJoined {
                 session_id: a,
@@ -127,20 +121,25 @@ mod test {
             },
             Event::PlayerReady { session_id: a },
             Event::PlayerReady { session_id: b },
-            Event::start_round_now(),
+            Event::start_round_at(clock.timestamp()),
             Event::PlacedBet(PlacedBet {
                 session_id: a,
                 monster_id: Uuid::new_v4(),
                 amount: 100,
+                kind: BetKind::Win,
             }),
             Event::PlacedBet(PlacedBet {
                 session_id: b,
                 monster_id: Uuid::new_v4(),
                 amount: 100,
+                kind: BetKind::Win,
             }),
         ]);
 
-        assert_eq!(Some(Command::StartRace(())), StartRace.process(&events));
+        assert_eq!(
+            Some(Command::StartRace(())),
+            StartRace.process(&GameView::from(&events), &clock)
+        );
     }
 
     #[test]
@@ -150,15 +149,14 @@ mod test {
         let a = Uuid::new_v4();
         let b = Uuid::new_v4();
 
-        let start = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap()
-            .as_secs() as u32;
+        let clock = ManualClock::at(1_000);
+        let start = clock.timestamp();
 
         let events = Vector::from_iter([
             Event::GameCreated {
                 game_id: GameCode::random(),
                 settings: Settings::default(),
+                host: Uuid::nil(),
             },
             Event::PlayerJoined {
                 session_id: a,
@@ -173,22 +171,35 @@ mod test {
             Event::PlayerReady { session_id: a },
             Event::PlayerReady { session_id: b },
             Event::RoundStarted {
-                time: start - 90,
+                time: start,
                 odds: None,
                 enemies: None,
+                commitment: None,
+                track: Track::Standard,
             },
             Event::RoundStarted {
-                time: start - 90,
+                time: start,
                 odds: None,
                 enemies: None,
+                commitment: None,
+                track: Track::Standard,
             },
             Event::PlacedBet(PlacedBet {
                 session_id: a,
                 monster_id: Uuid::new_v4(),
                 amount: 100,
+                kind: BetKind::Win,
             }),
         ]);
+        let game = GameView::from(&events);
+
+        clock.advance(Duration::from_secs(89));
+        assert_eq!(None, StartRace.process(&game, &clock));
 
-        assert_eq!(Some(Command::StartRace(())), StartRace.process(&events));
+        clock.advance(Duration::from_secs(1));
+        assert_eq!(
+            Some(Command::StartRace(())),
+            StartRace.process(&game, &clock)
+        );
     }
 }
@@ -1,42 +1,43 @@
-use im::Vector;
-
-use super::{Alarm, AlarmProcessor, ProcessManager};
-use crate::models::projections;
-use crate::models::{commands::Command, events::Event};
+use super::{AlarmProcessor, ProcessManager};
+use crate::models::{commands::Command, events::Event, projections::GameView, timers::Timer};
 use crate::time::*;
 
 pub struct StartRound;
 
-pub const SUMMARY_DURATION: f32 = 15.0;
-
 impl AlarmProcessor for StartRound {
-    fn alarm(&self, events: &Vector<Event>) -> Option<Alarm> {
-        if !matches!(events.last(), Some(Event::RaceFinished { .. })) {
+    fn alarm(&self, game: &GameView) -> Option<(Timer, SystemTime)> {
+        if game.game_finished() {
             return None;
         }
 
-        if projections::game_finished(events) {
-            return None;
-        }
+        let time = game.showing_summary()?;
 
-        Some(Alarm(Duration::from_secs_f32(SUMMARY_DURATION)))
+        Some((
+            Timer::StartRound,
+            UNIX_EPOCH
+                + Duration::from_secs(time as u64)
+                + Duration::from_secs(game.settings().summary_duration as u64),
+        ))
     }
 }
 
 impl ProcessManager for StartRound {
-    fn process(&self, events: &Vector<Event>) -> Option<Command> {
-        let Some(Event::RaceFinished { time, .. }) = events.last() else {
+    fn process(&self, game: &GameView, clock: &dyn Clock) -> Option<Command> {
+        if game.game_finished() {
             return None;
-        };
+        }
 
-        if projections::game_finished(events) {
-            return None;
+        // A voided round has nothing to summarise, go straight to the next one
+        if matches!(game.last_game_event(), Some(Event::RoundVoided { .. })) {
+            return Some(Command::StartRound(()));
         }
 
-        if SystemTime::now()
+        let time = game.showing_summary()?;
+
+        if clock.now()
             >= UNIX_EPOCH
-                + Duration::from_secs(*time as u64)
-                + Duration::from_secs_f32(SUMMARY_DURATION - 1.)
+                + Duration::from_secs(time as u64)
+                + Duration::from_secs(game.settings().summary_duration as u64 - 1)
         {
             return Some(Command::StartRound(()));
         }
@@ -1,325 +1,49 @@
-use std::{hash::Hash, time::Duration};
-
-use crate::{
-    models::{
-        events::OddsExt,
-        monsters::MONSTERS,
-        projections::race::{RaceResults, race_seed, race_seed_for_round},
-    },
-    time::*,
-};
+use std::hash::Hash;
 
-use rand::{Rng, SeedableRng, distributions::WeightedIndex, prelude::Distribution, rngs::StdRng};
+use rand::{Rng, SeedableRng, rngs::StdRng};
 
 use super::{
-    cards::{Card, Target},
-    events::{Event, Odds, Payout, PlacedBet, Settings},
-    game_code::GameCode,
+    cards::Card,
+    events::{Event, Odds},
     monsters::Monster,
-    process_managers::start_race::PRE_GAME_TIMEOUT,
+    tracks::Track,
 };
-use im::{HashMap, OrdMap, Vector};
-use tracing::instrument;
+use im::{OrdMap, Vector};
 use uuid::Uuid;
 
 pub mod race;
 
-pub fn player_count(events: &Vector<Event>) -> usize {
-    let mut count = 0;
+pub mod game_view;
+pub use game_view::GameView;
 
-    for event in events {
-        if let Event::PlayerJoined { .. } = event {
-            count += 1
-        }
-    }
+pub mod ledger;
+pub use ledger::{LedgerEntry, Transaction};
 
-    count
-}
+pub mod tote_board;
+pub use tote_board::{ToteBoard, ToteEntry};
+
+pub mod form;
+pub use form::{Finish, Form};
 
 #[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
 pub struct PlayerInfo {
     pub session_id: Uuid,
     pub name: String,
     pub ready: bool,
+    pub presence: Presence,
 }
 
-pub fn players(events: &Vector<Event>) -> OrdMap<Uuid, PlayerInfo> {
-    let mut map = OrdMap::new();
-
-    for event in events {
-        match event {
-            Event::PlayerJoined {
-                name, session_id, ..
-            } => {
-                map.insert(
-                    *session_id,
-                    PlayerInfo {
-                        session_id: *session_id,
-                        name: name.clone(),
-                        ready: false,
-                    },
-                );
-            }
-            Event::ChangedProfile { session_id, name } => {
-                if let Some(info) = map.get_mut(session_id) {
-                    info.name = name.clone();
-                }
-            }
-
-            Event::PlayerReady { session_id } => {
-                if let Some(info) = map.get_mut(session_id) {
-                    info.ready = true
-                }
-            }
-            _ => {}
-        }
-    }
-
-    map
-}
-
-pub fn player_info(events: &Vector<Event>, player: Uuid) -> Option<PlayerInfo> {
-    players(events).get(&player).cloned()
-}
-
-pub fn game_has_started(events: &Vector<Event>) -> bool {
-    for event in events {
-        if let Event::RoundStarted { .. } = event {
-            return true;
-        }
-    }
-
-    false
-}
-
-pub fn player_exists(events: &Vector<Event>, session_id: Uuid) -> bool {
-    for event in events {
-        if let Event::PlayerJoined {
-            session_id: inner_session_id,
-            ..
-        } = event
-        {
-            if session_id == *inner_session_id {
-                return true;
-            }
-        }
-    }
-
-    false
-}
-
-pub fn all_players_ready(events: &Vector<Event>) -> bool {
-    players(events).values().all(|player| player.ready)
-}
-
-pub fn minimum_bet(events: &Vector<Event>) -> i32 {
-    let mut starting_bet = 100;
-
-    for event in events {
-        if let Event::RaceFinished { .. } = event {
-            starting_bet += 100
-        }
-    }
-
-    starting_bet
-}
-
-pub fn placed_bets(events: &Vector<Event>) -> OrdMap<Uuid, Vector<PlacedBet>> {
-    let mut bets = OrdMap::<Uuid, Vector<PlacedBet>>::new();
-
-    for event in events {
-        match event {
-            Event::PlacedBet(bet) => bets.entry(bet.session_id).or_default().push_back(*bet),
-            Event::RaceFinished { .. } => bets.clear(),
-            _ => {}
-        }
-    }
-
-    bets
-}
-
-pub fn player_has_bet(events: &Vector<Event>, player_id: Uuid) -> bool {
-    let mut player_has_bet = false;
-
-    for event in events {
-        match event {
-            Event::PlacedBet(PlacedBet { session_id, .. }) if player_id == *session_id => {
-                player_has_bet = true
-            }
-            Event::RaceFinished { .. } => player_has_bet = false,
-            _ => {}
-        }
-    }
-
-    player_has_bet
-}
-
-pub fn all_players_have_bet(events: &Vector<Event>) -> bool {
-    let players = players(events);
-    let bets = placed_bets(events);
-
-    if players.is_empty() {
-        return false;
-    }
-
-    for player in players.keys() {
-        if !bets.contains_key(player) {
-            return false;
-        }
-    }
-
-    true
-}
-
-#[instrument(skip_all)]
-pub fn settings(events: &Vector<Event>) -> Settings {
-    match &events[0] {
-        Event::GameCreated { settings, .. } => *settings,
-        event => {
-            tracing::error!(?event, "first event wasn't game created");
-            return Settings::default();
-        }
-    }
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Presence {
+    #[default]
+    Connected,
+    // Disconnected at this time, they're still waited on until the AFK timeout
+    Away(u32),
+    Left,
 }
 
 pub const INFLATION_FACTOR: i32 = 110;
 
-pub fn all_account_balances(events: &Vector<Event>) -> OrdMap<Uuid, i32> {
-    let mut accounts = OrdMap::<Uuid, i32>::new();
-    let mut bets = Vector::new();
-
-    let mut maybe_odds = None;
-    let mut current_enemies: Option<&std::collections::HashMap<Uuid, Uuid>> = None;
-
-    for event in events {
-        match event {
-            Event::PlayerJoined { session_id, .. } => {
-                accounts.insert(*session_id, 1000);
-            }
-            Event::BoughtCard { session_id, .. } => {
-                accounts
-                    .entry(*session_id)
-                    .and_modify(|balance| *balance -= 100);
-            }
-            Event::BorrowedMoney { session_id, amount } => {
-                accounts
-                    .entry(*session_id)
-                    .and_modify(|balance| *balance += *amount as i32);
-            }
-            Event::PaidBackMoney { session_id, amount } => {
-                accounts
-                    .entry(*session_id)
-                    .and_modify(|balance| *balance -= *amount as i32);
-            }
-            Event::PlacedBet(bet) => {
-                bets.push_back(*bet);
-                accounts
-                    .entry(bet.session_id)
-                    .and_modify(|account| *account -= bet.amount);
-            }
-            Event::RoundStarted { odds, enemies, .. } => {
-                maybe_odds = *odds;
-                current_enemies = enemies.as_ref()
-            }
-            Event::RaceFinished {
-                results: RaceResults { first, .. },
-                ..
-            } => match settings(events).payout {
-                Payout::Odds => {
-                    let payouts = bets
-                        .iter()
-                        .filter(|bet| bet.monster_id == *first)
-                        .map(|bet| {
-                            let payout = maybe_odds.payout(bet.monster_id);
-
-                            (bet.session_id, (payout * (bet.amount as f32)) as i32)
-                        })
-                        .collect::<std::collections::HashMap<_, _>>();
-
-                    payouts.iter().for_each(|(session_id, payout)| {
-                        *accounts.entry(*session_id).or_default() += payout;
-                    });
-
-                    if let Some(enemies) = current_enemies {
-                        for (player_id, enemy_id) in enemies {
-                            match payouts.get(enemy_id) {
-                                // Players get money when their enemy doesn't have a payout
-                                None => {
-                                    *accounts.entry(*player_id).or_default() += 500;
-                                }
-                                _ => {}
-                            }
-                        }
-                    }
-
-                    bets.clear();
-                }
-                Payout::Pool => {
-                    let total_losses = bets
-                        .iter()
-                        .copied()
-                        .filter(|bet| bet.monster_id != *first)
-                        .fold(0, |total, bet| total + bet.amount);
-
-                    let total_wins = bets
-                        .iter()
-                        .copied()
-                        .filter(|bet| bet.monster_id == *first)
-                        .fold(0, |total, bet| total + bet.amount);
-
-                    bets.iter()
-                        .filter(|bet| bet.monster_id == *first)
-                        .for_each(|bet| {
-                            let balance = accounts.entry(bet.session_id).or_default();
-
-                            let amount = bet.amount
-                                + INFLATION_FACTOR * bet.amount * total_losses / total_wins / 100;
-
-                            *balance += amount;
-                        });
-
-                    bets.clear();
-                }
-            },
-            Event::PlayedCard {
-                session_id: source_uuid,
-                card: Card::Theft,
-                target: Target::Player(target_uuid),
-            } => {
-                assert_ne!(
-                    target_uuid, source_uuid,
-                    "Theft card cannot be played on self"
-                );
-
-                let target = accounts.get(target_uuid).copied().unwrap_or_default();
-                let source = accounts.get(source_uuid).copied().unwrap_or_default();
-
-                let amount = ((target as f32) * 0.2) as i32;
-
-                accounts.insert(*target_uuid, target - amount);
-                accounts.insert(*source_uuid, source + amount);
-            }
-            Event::PlayedCard {
-                card: Card::Crystals,
-                target: Target::Player(target),
-                ..
-            } => {
-                *accounts.entry(*target).or_default() += 1000;
-            }
-            _ => {}
-        };
-    }
-
-    accounts
-}
-
-pub fn account_balance(events: &Vector<Event>, player: Uuid) -> i32 {
-    all_account_balances(events)
-        .get(&player)
-        .copied()
-        .unwrap_or_default()
-}
-
 pub fn last_round(events: &Vector<Event>) -> Option<Vector<Event>> {
     let start = events
         .iter()
@@ -339,645 +63,52 @@ pub fn last_round(events: &Vector<Event>) -> Option<Vector<Event>> {
     Some(events.clone().slice(start..=end))
 }
 
-// TODO: There's an awkward duplication of logic here between this and `all_account_balances`
-// Unfortunately, there's slightly different with winnings ignoring the original bet but all_account_balances
-// needing to restore the original bet amount to the wallet
-pub fn winnings(events: &Vector<Event>) -> OrdMap<Uuid, i32> {
-    let mut winnings = OrdMap::new();
-    let mut did_win = OrdMap::new();
-    let mut current_enemies: Option<&std::collections::HashMap<Uuid, Uuid>> = None;
-    let mut bets = Vec::new();
-    let odds = pre_computed_odds(events);
-
-    for event in events {
-        match event {
-            Event::RoundStarted { enemies, .. } => {
-                winnings.clear();
-                did_win.clear();
-                bets.clear();
-                current_enemies = enemies.as_ref();
-            }
-            Event::PlacedBet(bet) => {
-                bets.push(*bet);
-            }
-            Event::RaceFinished {
-                results: RaceResults { first, .. },
-                ..
-            } => match settings(events).payout {
-                Payout::Odds => {
-                    for bet in bets.iter() {
-                        let balance = winnings.entry(bet.session_id).or_default();
-
-                        let amount = if bet.monster_id == *first {
-                            *did_win.entry(bet.session_id).or_default() = true;
-                            ((odds.payout(bet.monster_id) - 1.0) * (bet.amount as f32)) as i32
-                        } else {
-                            -1 * bet.amount
-                        };
-
-                        *balance += amount;
-                    }
-
-                    if let Some(current_enemies) = current_enemies {
-                        for (player_id, enemy_id) in current_enemies {
-                            match did_win.get(enemy_id) {
-                                None | Some(false) => {
-                                    *winnings.entry(*player_id).or_default() += 500;
-                                }
-                                Some(true) => {}
-                            }
-                        }
-                    }
-                }
-                Payout::Pool => {
-                    let total_losses = bets
-                        .iter()
-                        .copied()
-                        .filter(|bet| bet.monster_id != *first)
-                        .fold(0, |total, bet| total + bet.amount);
-
-                    let total_wins = bets
-                        .iter()
-                        .copied()
-                        .filter(|bet| bet.monster_id == *first)
-                        .fold(0, |total, bet| total + bet.amount);
-
-                    bets.iter().for_each(|bet| {
-                        let balance = winnings.entry(bet.session_id).or_default();
-
-                        let amount = if bet.monster_id == *first {
-                            INFLATION_FACTOR * bet.amount * total_losses / total_wins / 100
-                        } else {
-                            -bet.amount
-                        };
-
-                        *balance += amount;
-                    });
-                }
-            },
-            _ => {}
-        }
-    }
-
-    winnings
-}
-
-pub fn all_debt(events: &Vector<Event>) -> HashMap<Uuid, u32> {
-    let mut debt = HashMap::new();
-
-    for event in events {
-        match event {
-            Event::BorrowedMoney { session_id, amount } => {
-                *debt.entry(*session_id).or_default() += *amount
-            }
-            Event::PaidBackMoney { session_id, amount } => {
-                *debt.entry(*session_id).or_default() -= *amount
-            }
-            Event::RaceFinished { .. } => debt
-                .iter_mut()
-                .for_each(|(_, amount)| *amount = ((*amount as f32) * 1.051) as u32),
-            _ => {}
-        }
-    }
-
-    debt
-}
-
-pub fn debt(events: &Vector<Event>, player_id: Uuid) -> u32 {
-    all_debt(events)
-        .get(&player_id)
-        .copied()
-        .unwrap_or_default()
-}
-
-#[instrument(skip_all)]
-pub fn game_id(events: &Vector<Event>) -> GameCode {
-    match events.get(0) {
-        Some(Event::GameCreated { game_id, .. }) => *game_id,
-        None => GameCode::random(),
-        Some(event) => {
-            tracing::error!(?event, "first event wasn't game_created");
-            unreachable!()
-        }
-    }
-}
-
-pub fn round(events: &Vector<Event>) -> u32 {
-    let mut round = 0;
-
-    for event in events {
-        match event {
-            Event::RoundStarted { .. } => round += 1,
-            _ => {}
-        }
-    }
-
-    round
-}
-
-const DECK: [(usize, Card); 12] = [
-    (10, Card::Poison),
-    (10, Card::ExtraRations),
-    (10, Card::TasteTester),
-    (10, Card::PsyBlast),
-    (10, Card::Meditation),
-    (10, Card::TinfoilHat),
-    (3, Card::Nepotism),
-    (8, Card::Theft),
-    (8, Card::Extortion),
-    (0, Card::Stupify),
-    (4, Card::Scrutiny),
-    (5, Card::Crystals),
-];
-
-fn bought_cards(events: &Vector<Event>) -> u32 {
-    let mut count = 0;
-
-    for event in events {
-        match event {
-            Event::BoughtCard { .. } => count += 1,
-            _ => {}
-        }
-    }
-
-    count
-}
-
-pub fn draw_n_cards_from_deck<const N: usize>(events: &Vector<Event>, seed: u64) -> [Card; N] {
-    let dist = WeightedIndex::new(DECK.map(|(weight, _)| weight)).unwrap();
-    let race_seed = race_seed_for_round(events, bought_cards(events));
-
-    let mut rng = StdRng::seed_from_u64(race_seed as u64 ^ seed);
-
-    core::array::from_fn(|_| DECK[dist.sample(&mut rng)].1)
-}
-
-pub fn initial_cards(events: &Vector<Event>, player: Uuid) -> Vec<Card> {
-    match settings(events).starting_cards {
-        0 => vec![],
-        3 => {
-            let mut hand = vec![Card::Theft];
-
-            hand.append(&mut draw_n_cards_from_deck::<2>(events, player.as_u128() as u64).into());
-            hand
-        }
-        // TODO: Remove this horrible hack
-        _ => panic!("unsupported number of starting cards"),
-    }
-}
-
-pub fn cards_in_hand(events: &Vector<Event>, player: Uuid) -> Vec<Card> {
-    let mut cards = OrdMap::<Uuid, Vec<Card>>::new();
-
-    let remove_card_from_hand = |cards: &mut Vec<Card>, card| {
-        if let Some(index) = cards.iter().position(|it| *it == card) {
-            cards.remove(index);
-        }
-    };
-
-    for event in events {
-        match event {
-            Event::PlayerJoined {
-                session_id,
-                initial_cards,
-                ..
-            } => {
-                cards.insert(*session_id, initial_cards.clone());
-            }
-            Event::BoughtCard { session_id, card } => {
-                cards.entry(*session_id).or_default().push(*card)
-            }
-            Event::PlayedCard {
-                session_id: source,
-                card: Card::Extortion,
-                target: Target::Player(target),
-            } if source != target => {
-                let target_cards = cards.entry(*target).or_default();
-                let mut removed_cards = (0..2)
-                    .filter_map(|_| target_cards.pop())
-                    .collect::<Vec<_>>();
-
-                let source_cards = cards.entry(*source).or_default();
-
-                remove_card_from_hand(source_cards, Card::Extortion);
-                source_cards.append(&mut removed_cards);
-            }
-            Event::PlayedCard {
-                session_id, card, ..
-            } => remove_card_from_hand(cards.entry(*session_id).or_default(), *card),
-            _ => {}
-        }
-    }
-
-    cards.remove(&player).unwrap_or_default()
-}
-
-pub fn can_play_more_cards(events: &Vector<Event>, player: Uuid) -> bool {
-    // let max_cards = match player_count(events) {
-    //     0..=3 => 3,
-    //     4..=8 => 2,
-    //     9.. => 1,
-    // };
-    let max_cards = 1;
-
-    let mut count = 0;
-
-    // Watch out this iterator is backwards so we can short circuit
-    for event in events.iter().rev() {
-        match event {
-            Event::RoundStarted { .. } => return count < max_cards,
-            Event::PlayedCard {
-                session_id, card, ..
-            } if (*session_id == player && !card.is_free()) => count += 1,
-            Event::PlayedCard {
-                card: Card::Scrutiny,
-                target: Target::MultiplePlayers(targets),
-                ..
-            } if targets.contains(&player) => return false,
-            _ => {}
-        }
-    }
-
-    false
-}
-
-pub fn valid_target_for_card(events: &Vector<Event>, player: Uuid, target: Target) -> bool {
-    match target {
-        Target::Player(target) => player != target && player_exists(events, target),
-        Target::MultiplePlayers(targets) => targets
-            .iter()
-            .all(|target| valid_target_for_card(events, player, Target::Player(*target))),
-        Target::Monster(target) => {
-            let race_seed = race_seed(events);
-            let monsters = monsters(events, race_seed);
-
-            monsters
-                .iter()
-                .find(|monster| monster.uuid == target)
-                .is_some()
-        }
-    }
-}
-
 #[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Ord, Eq)]
 pub struct PlayedMonsterCard {
     pub card: Card,
     pub monster_id: Uuid,
 }
 
-pub fn unique_played_monster_cards(events: &Vector<Event>) -> Vec<PlayedMonsterCard> {
-    let mut cards = Vec::new();
-
-    for event in events.iter() {
-        match event {
-            Event::PlayedCard {
-                card,
-                target: Target::Monster(monster_id),
-                ..
-            } => cards.push(PlayedMonsterCard {
-                card: *card,
-                monster_id: *monster_id,
-            }),
-            Event::RoundStarted { .. } => cards.clear(),
-            Event::RaceFinished { .. } => cards.clear(),
-            _ => {}
-        }
-    }
-
-    cards.sort();
-    cards.dedup();
-
-    cards
-}
-
-pub fn poisoned(cards: &Vec<PlayedMonsterCard>, target: Uuid) -> bool {
-    let mut poisoned = false;
-    let mut countered = false;
-
-    for card in cards {
-        if card.monster_id != target {
-            continue;
-        }
-
-        match card.card {
-            Card::Poison => poisoned = true,
-            Card::TasteTester => countered = true,
-            _ => {}
-        }
-    }
-
-    poisoned && !countered
-}
-
-pub fn nepotism(cards: &Vec<PlayedMonsterCard>, target: Uuid) -> bool {
-    for card in cards {
-        if card.monster_id != target {
-            continue;
-        }
-
-        match card.card {
-            Card::Nepotism => return true,
-            _ => {}
-        }
-    }
-
-    false
-}
-
-pub fn extra_rations(cards: &Vec<PlayedMonsterCard>, target: Uuid) -> bool {
-    let mut extra_rations = false;
-    let mut countered = false;
-
-    for card in cards {
-        if card.monster_id != target {
-            continue;
-        }
-
-        match card.card {
-            Card::ExtraRations => extra_rations = true,
-            Card::TasteTester => countered = true,
-            _ => {}
-        }
-    }
-
-    extra_rations && !countered
-}
-
-pub fn psyblast(cards: &Vec<PlayedMonsterCard>, target: Uuid) -> bool {
-    let mut effect = false;
-    let mut countered = false;
-
-    for card in cards {
-        if card.monster_id != target {
-            continue;
-        }
-
-        match card.card {
-            Card::PsyBlast => effect = true,
-            Card::TinfoilHat => countered = true,
-            _ => {}
-        }
-    }
-
-    effect && !countered
-}
-
-pub fn meditation(cards: &Vec<PlayedMonsterCard>, target: Uuid) -> bool {
-    let mut effect = false;
-    let mut countered = false;
-
-    for card in cards {
-        if card.monster_id != target {
-            continue;
-        }
-
-        match card.card {
-            Card::Meditation => effect = true,
-            Card::TinfoilHat => countered = true,
-            _ => {}
-        }
-    }
-
-    effect && !countered
-}
-
-pub fn monsters(events: &Vector<Event>, race_seed: u32) -> [Monster; 3] {
-    use rand::seq::SliceRandom;
-
-    let mut rng = StdRng::seed_from_u64(race_seed as u64);
-
-    let mut monsters: [Monster; 3] = MONSTERS
-        .choose_multiple(&mut rng, 3)
-        .copied()
-        .collect::<Vec<_>>()
-        .try_into()
-        .unwrap();
-
-    let played_cards = unique_played_monster_cards(&events);
-
-    for monster in &mut monsters {
-        if poisoned(&played_cards, monster.uuid) {
-            monster.strength -= 3;
-        }
-
-        if extra_rations(&played_cards, monster.uuid) {
-            monster.strength += 2;
-        }
-
-        if psyblast(&played_cards, monster.uuid) {
-            monster.dexterity -= 3;
-        }
-
-        if meditation(&played_cards, monster.uuid) {
-            monster.dexterity += 2;
-        }
-
-        if nepotism(&played_cards, monster.uuid) {
-            monster.starting_position += 1.5;
-        }
-    }
-
-    monsters
-}
-
-pub fn pre_race_duration(events: &Vector<Event>) -> Duration {
-    let played_card = unique_played_monster_cards(&events).len() as u64;
-
-    Duration::from_secs(3 + 4 * played_card)
-}
-
-pub fn game_finished(events: &Vector<Event>) -> bool {
-    let settings = settings(events);
-    let mut rounds = 0;
-
-    for event in events.iter() {
-        match event {
-            Event::RaceFinished { .. } => rounds += 1,
-            Event::GameFinished => return true,
-            _ => {}
-        }
-    }
-
-    rounds >= settings.rounds
-}
-
-pub fn time_left_in_pregame(events: &Vector<Event>) -> Option<u64> {
-    if events
+// The cards played on a monster that haven't been countered by another card played on it
+pub fn effective_cards(cards: &[PlayedMonsterCard], target: Uuid) -> Vec<Card> {
+    let played = cards
         .iter()
-        .filter(|event| matches!(event, Event::RoundStarted { .. }))
-        .count()
-        <= 1
-    {
-        return None;
-    }
-
-    let Some(start) = events.iter().rev().find_map(|event| match event {
-        Event::RoundStarted { time, .. } => Some(*time),
-        _ => None,
-    }) else {
-        return None;
-    };
-
-    match (UNIX_EPOCH
-        + Duration::from_secs(start as u64)
-        + Duration::from_secs(PRE_GAME_TIMEOUT as u64))
-    .duration_since(SystemTime::now())
-    {
-        Ok(it) => Some(it.as_secs()),
-        Err(_) => Some(0),
-    }
-}
-
-pub fn pre_computed_odds(events: &Vector<Event>) -> Odds {
-    let monsters = monsters(events, race_seed(events));
+        .filter(|card| card.monster_id == target)
+        .map(|card| card.card)
+        .collect::<Vec<_>>();
 
-    events
+    played
         .iter()
-        .rev()
-        .find_map(|event| match event {
-            Event::RoundStarted {
-                odds: Some(odds), ..
-            } => Some(odds),
-            _ => None,
-        })
         .copied()
-        .unwrap_or_else(|| {
-            Odds([
-                (monsters[0].uuid, 1. / 3.),
-                (monsters[1].uuid, 1. / 3.),
-                (monsters[2].uuid, 1. / 3.),
-            ])
+        .filter(|card| {
+            !played
+                .iter()
+                .any(|other| other.definition().counters.contains(card))
         })
+        .collect()
 }
 
-pub fn odds(monsters: &[Monster; 3], seed: u32) -> Odds {
+pub fn odds(monsters: &[Monster], track: Track, seed: u32) -> Odds {
     let mut wins = OrdMap::<Uuid, u32>::new();
     let mut rng = StdRng::seed_from_u64(seed as u64);
 
     for _ in 0..1000 {
-        let (results, _) = race::results(monsters, rng.r#gen::<u32>());
-
-        *wins.entry(results.first).or_default() += 1;
-    }
-
-    Odds(monsters.map(|monster| {
-        (
-            monster.uuid,
-            wins.get(&monster.uuid).copied().unwrap_or_default() as f32 / 1000.,
-        )
-    }))
-}
-
-pub fn maximum_debt(events: &Vector<Event>) -> i32 {
-    let mut max_debt = 300;
-
-    for event in events {
-        match event {
-            Event::RoundStarted { .. } => max_debt += 200,
-            _ => {}
-        }
-    }
-
-    max_debt
-}
-
-pub fn results(events: &Vector<Event>) -> Option<RaceResults> {
-    for event in events.iter().rev() {
-        if let Event::RaceFinished { results, .. } = event {
-            return Some(*results);
-        }
-    }
-
-    None
-}
-
-pub fn victim_of_card(events: &Vector<Event>, player: Uuid) -> Option<(Card, String)> {
-    let players = players(events);
-
-    match events.last() {
-        Some(Event::PlayedCard {
-            card,
-            session_id,
-            target: Target::Player(target),
-        }) if *target == player => Some((*card, players.get(session_id).unwrap().name.clone())),
-        Some(Event::PlayedCard {
-            card,
-            session_id,
-            target: Target::MultiplePlayers(targets),
-        }) if targets.contains(&player) => {
-            Some((*card, players.get(session_id).unwrap().name.clone()))
-        }
-        _ => None,
-    }
-}
-
-// returns the Some(start time) of the race if its currently in progress, otherwise None
-pub fn currently_racing(events: &Vector<Event>) -> Option<u32> {
-    match events.iter().rev().find(|event| {
-        matches!(
-            event,
-            Event::RaceStarted { .. } | Event::RaceFinished { .. }
-        )
-    }) {
-        Some(Event::RaceStarted { time }) => Some(*time),
-        _ => None,
-    }
-}
+        let results = race::finishing_order(monsters, track, rng.r#gen::<u32>());
 
-// returns the Some(start time) of the betting round if its currently in progress, otherwise None
-pub fn currently_betting(events: &Vector<Event>) -> Option<u32> {
-    match events.iter().rev().find(|event| {
-        matches!(
-            event,
-            Event::RoundStarted { .. } | Event::RaceStarted { .. }
-        )
-    }) {
-        Some(Event::RoundStarted { time, .. }) => Some(*time),
-        _ => None,
+        *wins.entry(results.first()).or_default() += 1;
     }
-}
-
-pub fn enemy(events: &Vector<Event>, player_id: Uuid) -> Option<PlayerInfo> {
-    let all_players = players(events);
 
-    events.iter().rev().find_map(|event| match event {
-        Event::RoundStarted {
-            enemies: Some(enemies),
-            ..
-        } => all_players.get(enemies.get(&player_id)?).cloned(),
-        _ => None,
-    })
-}
-
-pub fn all_enemies(events: &Vector<Event>) -> std::collections::HashMap<Uuid, Uuid> {
-    let all_players = all_account_balances(events).into_iter().collect::<Vec<_>>();
-
-    if all_players.len() < 2 {
-        return std::collections::HashMap::new();
-    }
-
-    let mut rng = StdRng::seed_from_u64(2374628346287346 ^ race_seed(events) as u64);
-
-    let Ok(potential_enemies) =
-        WeightedIndex::new(all_players.iter().map(|&(_, balance)| balance.max(0)))
-    else {
-        return std::collections::HashMap::new();
-    };
-
-    let mut enemies = std::collections::HashMap::new();
-    for &(player_id, _) in &all_players {
-        loop {
-            let enemy_index = potential_enemies.sample(&mut rng);
-            let enemy_id = all_players[enemy_index].0;
-
-            if enemy_id != player_id {
-                enemies.insert(player_id, enemy_id);
-                break;
-            }
-        }
-    }
-
-    return enemies;
+    Odds(
+        monsters
+            .iter()
+            .map(|monster| {
+                (
+                    monster.uuid,
+                    wins.get(&monster.uuid).copied().unwrap_or_default() as f32 / 1000.,
+                )
+            })
+            .collect(),
+    )
 }
 
 #[cfg(test)]
@@ -987,23 +118,36 @@ mod tests {
     use uuid::Uuid;
 
     use crate::models::{
-        cards::{Card, Target},
-        events::{Event, Payout, PlacedBet, Settings},
+        cards::{Card, Modifier, Target},
+        events::{BetKind, Event, Payout, PlacedBet, Settings},
         game_code::GameCode,
-        projections::{self, RaceResults},
+        projections::race::RaceResults,
+        tracks::Track,
     };
 
-    use super::{INFLATION_FACTOR, all_account_balances};
+    use super::{GameView, INFLATION_FACTOR};
 
     fn init_tracing() {
         let _ = tracing_subscriber::fmt().pretty().try_init();
     }
 
+    // How much a card changes a monster's strength, according to the card registry
+    fn strength_delta(card: Card) -> i32 {
+        card.definition()
+            .modifiers
+            .iter()
+            .map(|modifier| match modifier {
+                Modifier::Strength(amount) => *amount,
+                _ => 0,
+            })
+            .sum()
+    }
+
     #[test]
     fn empty() {
         let events = Vector::new();
 
-        let accounts = all_account_balances(&events);
+        let accounts = GameView::from(&events).all_account_balances().clone();
 
         assert_eq!(
             accounts,
@@ -1022,6 +166,7 @@ mod tests {
             Event::GameCreated {
                 game_id: "ABCDEF".try_into().unwrap(),
                 settings: Settings::default(),
+                host: Uuid::nil(),
             },
             Event::PlayerJoined {
                 session_id: bob,
@@ -1035,7 +180,7 @@ mod tests {
             }
         ];
 
-        let accounts = all_account_balances(&events);
+        let accounts = GameView::from(&events).all_account_balances().clone();
 
         assert_eq!(
             accounts,
@@ -1053,7 +198,8 @@ mod tests {
         let events = vector![
             Event::GameCreated {
                 game_id: "ABCDEF".try_into().unwrap(),
-                settings: Settings::default()
+                settings: Settings::default(),
+                host: Uuid::nil()
             },
             Event::PlayerJoined {
                 session_id: bob,
@@ -1079,7 +225,7 @@ mod tests {
             }
         ];
 
-        let accounts = all_account_balances(&events);
+        let accounts = GameView::from(&events).all_account_balances().clone();
 
         assert_eq!(
             accounts,
@@ -1097,7 +243,8 @@ mod tests {
         let events = vector![
             Event::GameCreated {
                 game_id: "ABCDEF".try_into().unwrap(),
-                settings: Settings::default()
+                settings: Settings::default(),
+                host: Uuid::nil()
             },
             Event::PlayerJoined {
                 session_id: bob,
@@ -1119,7 +266,7 @@ mod tests {
             },
         ];
 
-        let accounts = all_account_balances(&events);
+        let accounts = GameView::from(&events).all_account_balances().clone();
 
         assert_eq!(
             accounts,
@@ -1137,7 +284,8 @@ mod tests {
         let events = vector![
             Event::GameCreated {
                 game_id: "ABCDEF".try_into().unwrap(),
-                settings: Settings::default()
+                settings: Settings::default(),
+                host: Uuid::nil()
             },
             Event::PlayerJoined {
                 session_id: bob,
@@ -1163,7 +311,7 @@ mod tests {
             }
         ];
 
-        let accounts = all_account_balances(&events);
+        let accounts = GameView::from(&events).all_account_balances().clone();
 
         assert_eq!(
             accounts,
@@ -1184,7 +332,8 @@ mod tests {
         let events = vector![
             Event::GameCreated {
                 game_id: "ABCDEF".try_into().unwrap(),
-                settings: Settings::default()
+                settings: Settings::default(),
+                host: Uuid::nil()
             },
             Event::PlayerJoined {
                 session_id: bob,
@@ -1199,16 +348,18 @@ mod tests {
             Event::PlacedBet(PlacedBet {
                 session_id: alice,
                 monster_id: monster_a,
-                amount: 200
+                amount: 200,
+                kind: BetKind::Win
             }),
             Event::PlacedBet(PlacedBet {
                 session_id: bob,
                 monster_id: monster_b,
-                amount: 500
+                amount: 500,
+                kind: BetKind::Win
             })
         ];
 
-        let accounts = all_account_balances(&events);
+        let accounts = GameView::from(&events).all_account_balances().clone();
 
         assert_eq!(
             accounts,
@@ -1233,7 +384,8 @@ mod tests {
                 settings: Settings {
                     payout: Payout::Pool,
                     ..Default::default()
-                }
+                },
+                host: Uuid::nil()
             },
             Event::PlayerJoined {
                 session_id: bob,
@@ -1248,24 +400,24 @@ mod tests {
             Event::PlacedBet(PlacedBet {
                 session_id: alice,
                 monster_id: monster_a,
-                amount: 200
+                amount: 200,
+                kind: BetKind::Win
             }),
             Event::PlacedBet(PlacedBet {
                 session_id: bob,
                 monster_id: monster_b,
-                amount: 500
+                amount: 500,
+                kind: BetKind::Win
             }),
             Event::RaceFinished {
-                time: Event::now(),
+                time: 0,
                 results: RaceResults {
-                    first: monster_a,
-                    second: monster_b,
-                    third: monster_c,
+                    placings: vec![monster_a, monster_b, monster_c]
                 }
             }
         ];
 
-        let accounts = all_account_balances(&events);
+        let accounts = GameView::from(&events).all_account_balances().clone();
 
         assert_eq!(
             accounts,
@@ -1290,7 +442,8 @@ mod tests {
                 settings: Settings {
                     payout: Payout::Odds,
                     ..Default::default()
-                }
+                },
+                host: Uuid::nil()
             },
             Event::PlayerJoined {
                 session_id: bob,
@@ -1305,24 +458,24 @@ mod tests {
             Event::PlacedBet(PlacedBet {
                 session_id: alice,
                 monster_id: monster_a,
-                amount: 200
+                amount: 200,
+                kind: BetKind::Win
             }),
             Event::PlacedBet(PlacedBet {
                 session_id: bob,
                 monster_id: monster_b,
-                amount: 500
+                amount: 500,
+                kind: BetKind::Win
             }),
             Event::RaceFinished {
-                time: Event::now(),
+                time: 0,
                 results: RaceResults {
-                    first: monster_a,
-                    second: monster_b,
-                    third: monster_c,
+                    placings: vec![monster_a, monster_b, monster_c]
                 }
             }
         ];
 
-        let accounts = all_account_balances(&events);
+        let accounts = GameView::from(&events).all_account_balances().clone();
 
         assert_eq!(
             accounts,
@@ -1347,7 +500,8 @@ mod tests {
                 settings: Settings {
                     payout: Payout::Pool,
                     ..Default::default()
-                }
+                },
+                host: Uuid::nil()
             },
             Event::PlayerJoined {
                 session_id: bob,
@@ -1362,60 +516,60 @@ mod tests {
             Event::PlacedBet(PlacedBet {
                 session_id: alice,
                 monster_id: monster_a,
-                amount: 200
+                amount: 200,
+                kind: BetKind::Win
             }),
             Event::PlacedBet(PlacedBet {
                 session_id: bob,
                 monster_id: monster_b,
-                amount: 500
+                amount: 500,
+                kind: BetKind::Win
             }),
             Event::RaceFinished {
-                time: Event::now(),
+                time: 0,
                 results: RaceResults {
-                    first: monster_a,
-                    second: monster_b,
-                    third: monster_c,
+                    placings: vec![monster_a, monster_b, monster_c]
                 }
             },
             Event::PlacedBet(PlacedBet {
                 session_id: alice,
                 monster_id: monster_a,
-                amount: 250
+                amount: 250,
+                kind: BetKind::Win
             }),
             Event::PlacedBet(PlacedBet {
                 session_id: bob,
                 monster_id: monster_b,
-                amount: 500
+                amount: 500,
+                kind: BetKind::Win
             }),
             Event::RaceFinished {
-                time: Event::now(),
+                time: 0,
                 results: RaceResults {
-                    first: monster_b,
-                    second: monster_a,
-                    third: monster_c,
+                    placings: vec![monster_b, monster_a, monster_c]
                 }
             },
             Event::PlacedBet(PlacedBet {
                 session_id: alice,
                 monster_id: monster_b,
-                amount: 50
+                amount: 50,
+                kind: BetKind::Win
             }),
             Event::PlacedBet(PlacedBet {
                 session_id: bob,
                 monster_id: monster_c,
-                amount: 300
+                amount: 300,
+                kind: BetKind::Win
             }),
             Event::RaceFinished {
-                time: Event::now(),
+                time: 0,
                 results: RaceResults {
-                    first: monster_c,
-                    second: monster_b,
-                    third: monster_a,
+                    placings: vec![monster_c, monster_b, monster_a]
                 }
             }
         ];
 
-        let accounts = all_account_balances(&events);
+        let accounts = GameView::from(&events).all_account_balances().clone();
 
         assert_eq!(
             accounts,
@@ -1440,7 +594,8 @@ mod tests {
                 settings: Settings {
                     payout: Payout::Odds,
                     ..Default::default()
-                }
+                },
+                host: Uuid::nil()
             },
             Event::PlayerJoined {
                 session_id: bob,
@@ -1455,60 +610,60 @@ mod tests {
             Event::PlacedBet(PlacedBet {
                 session_id: alice,
                 monster_id: monster_a,
-                amount: 200
+                amount: 200,
+                kind: BetKind::Win
             }),
             Event::PlacedBet(PlacedBet {
                 session_id: bob,
                 monster_id: monster_b,
-                amount: 500
+                amount: 500,
+                kind: BetKind::Win
             }),
             Event::RaceFinished {
-                time: Event::now(),
+                time: 0,
                 results: RaceResults {
-                    first: monster_a,
-                    second: monster_b,
-                    third: monster_c,
+                    placings: vec![monster_a, monster_b, monster_c]
                 }
             },
             Event::PlacedBet(PlacedBet {
                 session_id: alice,
                 monster_id: monster_a,
-                amount: 250
+                amount: 250,
+                kind: BetKind::Win
             }),
             Event::PlacedBet(PlacedBet {
                 session_id: bob,
                 monster_id: monster_b,
-                amount: 500
+                amount: 500,
+                kind: BetKind::Win
             }),
             Event::RaceFinished {
-                time: Event::now(),
+                time: 0,
                 results: RaceResults {
-                    first: monster_b,
-                    second: monster_a,
-                    third: monster_c,
+                    placings: vec![monster_b, monster_a, monster_c]
                 }
             },
             Event::PlacedBet(PlacedBet {
                 session_id: alice,
                 monster_id: monster_b,
-                amount: 50
+                amount: 50,
+                kind: BetKind::Win
             }),
             Event::PlacedBet(PlacedBet {
                 session_id: bob,
                 monster_id: monster_c,
-                amount: 300
+                amount: 300,
+                kind: BetKind::Win
             }),
             Event::RaceFinished {
-                time: Event::now(),
+                time: 0,
                 results: RaceResults {
-                    first: monster_c,
-                    second: monster_b,
-                    third: monster_a,
+                    placings: vec![monster_c, monster_b, monster_a]
                 }
             }
         ];
 
-        let accounts = all_account_balances(&events);
+        let accounts = GameView::from(&events).all_account_balances().clone();
 
         assert_eq!(
             accounts,
@@ -1533,7 +688,8 @@ mod tests {
                 settings: Settings {
                     payout: Payout::Pool,
                     ..Default::default()
-                }
+                },
+                host: Uuid::nil()
             },
             Event::PlayerJoined {
                 session_id: bob,
@@ -1552,19 +708,19 @@ mod tests {
             Event::PlacedBet(PlacedBet {
                 session_id: alice,
                 monster_id: monster_a,
-                amount: 200
+                amount: 200,
+                kind: BetKind::Win
             }),
             Event::PlacedBet(PlacedBet {
                 session_id: bob,
                 monster_id: monster_b,
-                amount: 500
+                amount: 500,
+                kind: BetKind::Win
             }),
             Event::RaceFinished {
-                time: Event::now(),
+                time: 0,
                 results: RaceResults {
-                    first: monster_a,
-                    second: monster_b,
-                    third: monster_c,
+                    placings: vec![monster_a, monster_b, monster_c]
                 }
             },
             Event::BoughtCard {
@@ -1578,41 +734,41 @@ mod tests {
             Event::PlacedBet(PlacedBet {
                 session_id: alice,
                 monster_id: monster_a,
-                amount: 250
+                amount: 250,
+                kind: BetKind::Win
             }),
             Event::PlacedBet(PlacedBet {
                 session_id: bob,
                 monster_id: monster_b,
-                amount: 400
+                amount: 400,
+                kind: BetKind::Win
             }),
             Event::BorrowedMoney {
                 session_id: alice,
                 amount: 100
             },
             Event::RaceFinished {
-                time: Event::now(),
+                time: 0,
                 results: RaceResults {
-                    first: monster_b,
-                    second: monster_a,
-                    third: monster_c,
+                    placings: vec![monster_b, monster_a, monster_c]
                 }
             },
             Event::PlacedBet(PlacedBet {
                 session_id: alice,
                 monster_id: monster_b,
-                amount: 50
+                amount: 50,
+                kind: BetKind::Win
             }),
             Event::PlacedBet(PlacedBet {
                 session_id: bob,
                 monster_id: monster_c,
-                amount: 300
+                amount: 300,
+                kind: BetKind::Win
             }),
             Event::RaceFinished {
-                time: Event::now(),
+                time: 0,
                 results: RaceResults {
-                    first: monster_c,
-                    second: monster_b,
-                    third: monster_a,
+                    placings: vec![monster_c, monster_b, monster_a]
                 }
             },
             Event::PaidBackMoney {
@@ -1621,7 +777,7 @@ mod tests {
             }
         ];
 
-        let accounts = all_account_balances(&events);
+        let accounts = GameView::from(&events).all_account_balances().clone();
 
         assert_eq!(
             accounts,
@@ -1646,7 +802,8 @@ mod tests {
                 settings: Settings {
                     payout: Payout::Odds,
                     ..Default::default()
-                }
+                },
+                host: Uuid::nil()
             },
             Event::PlayerJoined {
                 session_id: bob,
@@ -1665,19 +822,19 @@ mod tests {
             Event::PlacedBet(PlacedBet {
                 session_id: alice,
                 monster_id: monster_a,
-                amount: 200
+                amount: 200,
+                kind: BetKind::Win
             }),
             Event::PlacedBet(PlacedBet {
                 session_id: bob,
                 monster_id: monster_b,
-                amount: 500
+                amount: 500,
+                kind: BetKind::Win
             }),
             Event::RaceFinished {
-                time: Event::now(),
+                time: 0,
                 results: RaceResults {
-                    first: monster_a,
-                    second: monster_b,
-                    third: monster_c,
+                    placings: vec![monster_a, monster_b, monster_c]
                 }
             },
             Event::BoughtCard {
@@ -1691,41 +848,41 @@ mod tests {
             Event::PlacedBet(PlacedBet {
                 session_id: alice,
                 monster_id: monster_a,
-                amount: 250
+                amount: 250,
+                kind: BetKind::Win
             }),
             Event::PlacedBet(PlacedBet {
                 session_id: bob,
                 monster_id: monster_b,
-                amount: 400
+                amount: 400,
+                kind: BetKind::Win
             }),
             Event::BorrowedMoney {
                 session_id: alice,
                 amount: 100
             },
             Event::RaceFinished {
-                time: Event::now(),
+                time: 0,
                 results: RaceResults {
-                    first: monster_b,
-                    second: monster_a,
-                    third: monster_c,
+                    placings: vec![monster_b, monster_a, monster_c]
                 }
             },
             Event::PlacedBet(PlacedBet {
                 session_id: alice,
                 monster_id: monster_b,
-                amount: 50
+                amount: 50,
+                kind: BetKind::Win
             }),
             Event::PlacedBet(PlacedBet {
                 session_id: bob,
                 monster_id: monster_c,
-                amount: 300
+                amount: 300,
+                kind: BetKind::Win
             }),
             Event::RaceFinished {
-                time: Event::now(),
+                time: 0,
                 results: RaceResults {
-                    first: monster_c,
-                    second: monster_b,
-                    third: monster_a,
+                    placings: vec![monster_c, monster_b, monster_a]
                 }
             },
             Event::PaidBackMoney {
@@ -1734,7 +891,7 @@ mod tests {
             }
         ];
 
-        let accounts = all_account_balances(&events);
+        let accounts = GameView::from(&events).all_account_balances().clone();
 
         assert_eq!(
             accounts,
@@ -1760,7 +917,8 @@ mod tests {
                 settings: Settings {
                     payout: Payout::Pool,
                     ..Default::default()
-                }
+                },
+                host: Uuid::nil()
             },
             Event::PlayerJoined {
                 name: "Alice".into(),
@@ -1780,34 +938,36 @@ mod tests {
             Event::PlacedBet(PlacedBet {
                 session_id: alice,
                 monster_id: monster_a,
-                amount: 100
+                amount: 100,
+                kind: BetKind::Win
             }),
             Event::PlacedBet(PlacedBet {
                 session_id: alice,
                 monster_id: monster_b,
-                amount: 100
+                amount: 100,
+                kind: BetKind::Win
             }),
             Event::PlacedBet(PlacedBet {
                 session_id: bob,
                 monster_id: monster_b,
-                amount: 100
+                amount: 100,
+                kind: BetKind::Win
             }),
             Event::PlacedBet(PlacedBet {
                 session_id: carol,
                 monster_id: monster_a,
-                amount: 100
+                amount: 100,
+                kind: BetKind::Win
             }),
             Event::RaceFinished {
-                time: Event::now(),
+                time: 0,
                 results: RaceResults {
-                    first: monster_b,
-                    second: monster_a,
-                    third: monster_c
+                    placings: vec![monster_b, monster_a, monster_c]
                 }
             }
         ];
 
-        let winnings = super::winnings(&events);
+        let winnings = GameView::from(&events).winnings().clone();
 
         assert_eq!(
             winnings,
@@ -1824,7 +984,8 @@ mod tests {
         let events = vector![
             Event::GameCreated {
                 game_id: GameCode::random(),
-                settings: Settings::default()
+                settings: Settings::default(),
+                host: Uuid::nil()
             },
             Event::PlayerJoined {
                 name: "Alice".into(),
@@ -1839,10 +1000,12 @@ mod tests {
                 time: 0,
                 odds: None,
                 enemies: None,
+                commitment: None,
+                track: Track::Standard,
             },
         ];
 
-        let monsters = super::monsters(&events, 0);
+        let monsters = GameView::from(&events).monsters(0);
 
         let mut post_poison_events = events.clone();
         post_poison_events.push_back(Event::PlayedCard {
@@ -1851,9 +1014,12 @@ mod tests {
             target: Target::Monster(monsters[0].uuid),
         });
 
-        let post_poison_monsters = super::monsters(&post_poison_events, 0);
+        let post_poison_monsters = GameView::from(&post_poison_events).monsters(0);
 
-        assert_eq!(monsters[0].strength - 2, post_poison_monsters[0].strength);
+        assert_eq!(
+            monsters[0].strength + strength_delta(Card::Poison),
+            post_poison_monsters[0].strength
+        );
         assert_eq!(monsters[1].strength, post_poison_monsters[1].strength);
         assert_eq!(monsters[2].strength, post_poison_monsters[2].strength);
     }
@@ -1867,7 +1033,8 @@ mod tests {
         let events = vector![
             Event::GameCreated {
                 game_id: GameCode::random(),
-                settings: Settings::default()
+                settings: Settings::default(),
+                host: Uuid::nil()
             },
             Event::PlayerJoined {
                 name: "Alice".into(),
@@ -1886,10 +1053,12 @@ mod tests {
                 time: 0,
                 odds: None,
                 enemies: None,
+                commitment: None,
+                track: Track::Standard,
             },
         ];
 
-        let monsters = super::monsters(&events, 0);
+        let monsters = GameView::from(&events).monsters(0);
 
         let mut post_poison_events = events.clone();
         post_poison_events.push_back(Event::PlayedCard {
@@ -1903,7 +1072,7 @@ mod tests {
             target: Target::Monster(monsters[0].uuid),
         });
 
-        let post_poison_monsters = super::monsters(&post_poison_events, 0);
+        let post_poison_monsters = GameView::from(&post_poison_events).monsters(0);
 
         assert_eq!(monsters[0].strength, post_poison_monsters[0].strength);
         assert_eq!(monsters[1].strength, post_poison_monsters[1].strength);
@@ -1919,7 +1088,8 @@ mod tests {
         let events = vector![
             Event::GameCreated {
                 game_id: GameCode::random(),
-                settings: Settings::default()
+                settings: Settings::default(),
+                host: Uuid::nil()
             },
             Event::PlayerJoined {
                 name: "Alice".into(),
@@ -1934,10 +1104,12 @@ mod tests {
                 time: 0,
                 odds: None,
                 enemies: None,
+                commitment: None,
+                track: Track::Standard,
             },
         ];
 
-        let monsters = super::monsters(&events, 0);
+        let monsters = GameView::from(&events).monsters(0);
 
         let mut post_rations_events = events.clone();
         post_rations_events.push_back(Event::PlayedCard {
@@ -1946,9 +1118,12 @@ mod tests {
             target: Target::Monster(monsters[0].uuid),
         });
 
-        let post_poison_monsters = super::monsters(&post_rations_events, 0);
+        let post_poison_monsters = GameView::from(&post_rations_events).monsters(0);
 
-        assert_eq!(monsters[0].strength + 1, post_poison_monsters[0].strength);
+        assert_eq!(
+            monsters[0].strength + strength_delta(Card::ExtraRations),
+            post_poison_monsters[0].strength
+        );
         assert_eq!(monsters[1].strength, post_poison_monsters[1].strength);
         assert_eq!(monsters[2].strength, post_poison_monsters[2].strength);
     }
@@ -1962,7 +1137,8 @@ mod tests {
         let events = vector![
             Event::GameCreated {
                 game_id: GameCode::random(),
-                settings: Settings::default()
+                settings: Settings::default(),
+                host: Uuid::nil()
             },
             Event::PlayerJoined {
                 name: "Alice".into(),
@@ -1981,10 +1157,12 @@ mod tests {
                 time: 0,
                 odds: None,
                 enemies: None,
+                commitment: None,
+                track: Track::Standard,
             },
         ];
 
-        let monsters = super::monsters(&events, 0);
+        let monsters = GameView::from(&events).monsters(0);
 
         let mut post_rations_events = events.clone();
         post_rations_events.push_back(Event::PlayedCard {
@@ -1998,7 +1176,7 @@ mod tests {
             target: Target::Monster(monsters[0].uuid),
         });
 
-        let post_poison_monsters = super::monsters(&post_rations_events, 0);
+        let post_poison_monsters = GameView::from(&post_rations_events).monsters(0);
 
         assert_eq!(monsters[0].strength, post_poison_monsters[0].strength);
         assert_eq!(monsters[1].strength, post_poison_monsters[1].strength);
@@ -2014,7 +1192,8 @@ mod tests {
         let events = vector![
             Event::GameCreated {
                 game_id: GameCode::random(),
-                settings: Settings::default()
+                settings: Settings::default(),
+                host: Uuid::nil()
             },
             Event::PlayerJoined {
                 name: "Alice".into(),
@@ -2031,7 +1210,7 @@ mod tests {
             },
         ];
 
-        let cards_in_hand = super::cards_in_hand(&events, alice);
+        let cards_in_hand = GameView::from(&events).cards_in_hand(alice);
 
         assert_eq!(cards_in_hand, vec![Card::Poison, Card::TasteTester]);
     }
@@ -2040,7 +1219,7 @@ mod tests {
     fn starting_cards() {
         init_tracing();
 
-        // Initial cards are sensitive to player UUID & GameCode
+        // Initial cards are sensitive to the GameCode and the game's secret
         let alice = Uuid::from_u128(1);
 
         let game = Event::GameCreated {
@@ -2049,6 +1228,7 @@ mod tests {
                 starting_cards: 3,
                 ..Default::default()
             },
+            host: Uuid::nil(),
         };
 
         let events = vector![
@@ -2056,15 +1236,15 @@ mod tests {
             Event::PlayerJoined {
                 name: "Alice".into(),
                 session_id: alice,
-                initial_cards: projections::initial_cards(&vector![game], alice)
+                initial_cards: GameView::from(&vector![game]).initial_cards()
             }
         ];
 
-        let cards_in_hand = super::cards_in_hand(&events, alice);
+        let cards_in_hand = GameView::from(&events).cards_in_hand(alice);
 
         assert_eq!(
             cards_in_hand,
-            vec![Card::Theft, Card::TasteTester, Card::Meditation]
+            vec![Card::Theft, Card::Meditation, Card::TinfoilHat]
         );
     }
 
@@ -2077,7 +1257,8 @@ mod tests {
         let events = vector![
             Event::GameCreated {
                 game_id: GameCode::random(),
-                settings: Settings::default()
+                settings: Settings::default(),
+                host: Uuid::nil()
             },
             Event::PlayerJoined {
                 name: "Alice".into(),
@@ -2103,7 +1284,7 @@ mod tests {
             }
         ];
 
-        let cards_in_hand = super::cards_in_hand(&events, alice);
+        let cards_in_hand = GameView::from(&events).cards_in_hand(alice);
 
         assert_eq!(cards_in_hand, vec![Card::Poison, Card::TasteTester]);
     }
@@ -2112,12 +1293,13 @@ mod tests {
     fn drawing_a_card_is_deterministic() {
         let events = vector![Event::GameCreated {
             game_id: GameCode::random(),
-            settings: Settings::default()
+            settings: Settings::default(),
+            host: Uuid::nil()
         }];
 
         assert_eq!(
-            super::draw_n_cards_from_deck::<1>(&events, 0),
-            super::draw_n_cards_from_deck::<1>(&events, 0)
+            GameView::from(&events).draw_n_cards_from_deck::<1>(),
+            GameView::from(&events).draw_n_cards_from_deck::<1>()
         );
     }
 
@@ -2131,7 +1313,8 @@ mod tests {
         let events = vector![
             Event::GameCreated {
                 game_id: GameCode::random(),
-                settings: Settings::default()
+                settings: Settings::default(),
+                host: Uuid::nil()
             },
             Event::PlayerJoined {
                 name: "Alice".into(),
@@ -2154,7 +1337,7 @@ mod tests {
             }
         ];
 
-        let balances = super::all_account_balances(&events);
+        let balances = GameView::from(&events).all_account_balances().clone();
 
         assert_eq!(balances[&alice], 1100);
         assert_eq!(balances[&bob], 800);
@@ -2170,7 +1353,8 @@ mod tests {
         let events = vector![
             Event::GameCreated {
                 game_id: GameCode::try_from("ABCDEF").unwrap(),
-                settings: Settings::default()
+                settings: Settings::default(),
+                host: Uuid::nil()
             },
             Event::PlayerJoined {
                 name: "Alice".into(),
@@ -2197,8 +1381,11 @@ mod tests {
             }
         ];
 
-        assert_eq!(super::cards_in_hand(&events, alice), vec![Card::Poison]);
-        assert_eq!(super::cards_in_hand(&events, bob), vec![]);
+        assert_eq!(
+            GameView::from(&events).cards_in_hand(alice),
+            vec![Card::Poison]
+        );
+        assert_eq!(GameView::from(&events).cards_in_hand(bob), vec![]);
     }
 
     #[test]
@@ -2211,7 +1398,8 @@ mod tests {
         let events = vector![
             Event::GameCreated {
                 game_id: GameCode::try_from("ABCDEF").unwrap(),
-                settings: Settings::default()
+                settings: Settings::default(),
+                host: Uuid::nil()
             },
             Event::PlayerJoined {
                 name: "Alice".into(),
@@ -2243,10 +1431,10 @@ mod tests {
         ];
 
         assert_eq!(
-            super::cards_in_hand(&events, alice),
+            GameView::from(&events).cards_in_hand(alice),
             vec![Card::PsyBlast, Card::Poison]
         );
-        assert_eq!(super::cards_in_hand(&events, bob), vec![]);
+        assert_eq!(GameView::from(&events).cards_in_hand(bob), vec![]);
     }
 
     #[test]
@@ -2259,7 +1447,8 @@ mod tests {
         let events = vector![
             Event::GameCreated {
                 game_id: GameCode::try_from("ABCDEF").unwrap(),
-                settings: Settings::default()
+                settings: Settings::default(),
+                host: Uuid::nil()
             },
             Event::PlayerJoined {
                 name: "Alice".into(),
@@ -2295,10 +1484,13 @@ mod tests {
         ];
 
         assert_eq!(
-            super::cards_in_hand(&events, alice),
+            GameView::from(&events).cards_in_hand(alice),
             vec![Card::Meditation, Card::PsyBlast]
         );
-        assert_eq!(super::cards_in_hand(&events, bob), vec![Card::Poison]);
+        assert_eq!(
+            GameView::from(&events).cards_in_hand(bob),
+            vec![Card::Poison]
+        );
     }
 
     #[ignore]
@@ -2318,7 +1510,8 @@ mod tests {
         let mut events = vector![
             Event::GameCreated {
                 game_id: GameCode::random(),
-                settings: Settings::default()
+                settings: Settings::default(),
+                host: Uuid::nil()
             },
             Event::PlayerJoined {
                 name: "Alice".into(),
@@ -2342,6 +1535,8 @@ mod tests {
                 time: 0,
                 odds: None,
                 enemies: None,
+                commitment: None,
+                track: Track::Standard,
             },
             Event::BoughtCard {
                 session_id: alice,
@@ -2362,7 +1557,7 @@ mod tests {
         ];
 
         assert!(
-            super::can_play_more_cards(&events, alice),
+            GameView::from(&events).can_play_more_cards(alice),
             "Player should be able to play cards initially"
         );
 
@@ -2372,7 +1567,7 @@ mod tests {
             target: Target::Monster(Uuid::new_v4()),
         });
         assert!(
-            super::can_play_more_cards(&events, alice),
+            GameView::from(&events).can_play_more_cards(alice),
             "A three player game should allow a player to play at least two card"
         );
 
@@ -2383,7 +1578,7 @@ mod tests {
         });
 
         assert!(
-            super::can_play_more_cards(&events, alice),
+            GameView::from(&events).can_play_more_cards(alice),
             "A three player game should allow a player to play at least three cards"
         );
 
@@ -2394,7 +1589,7 @@ mod tests {
         });
 
         assert!(
-            !super::can_play_more_cards(&events, alice),
+            !GameView::from(&events).can_play_more_cards(alice),
             "A three player game should allow a player to play no more than three cards"
         );
 
@@ -2405,7 +1600,7 @@ mod tests {
         });
 
         assert!(
-            !super::can_play_more_cards(&events, carol),
+            !GameView::from(&events).can_play_more_cards(carol),
             "Player should not be able to play cards after being subject to scrutiny"
         );
 
@@ -2413,9 +1608,11 @@ mod tests {
             time: 1,
             odds: None,
             enemies: None,
+            commitment: None,
+            track: Track::Standard,
         });
         assert!(
-            super::can_play_more_cards(&events, alice),
+            GameView::from(&events).can_play_more_cards(alice),
             "Player should be able to play cards at the start of a new round"
         );
     }
@@ -2431,6 +1628,7 @@ mod tests {
             [Event::GameCreated {
                 game_id: GameCode::random(),
                 settings: Settings::default(),
+                host: Uuid::nil(),
             }]
             .into_iter()
             .chain(players.iter().clone().flat_map(|id| {
@@ -2447,11 +1645,13 @@ mod tests {
                 time: 0,
                 odds: None,
                 enemies: None,
+                commitment: None,
+                track: Track::Standard,
             }]),
         );
 
         assert!(
-            super::can_play_more_cards(&events, players[0]),
+            GameView::from(&events).can_play_more_cards(players[0]),
             "Player should be able to play cards initially"
         );
 
@@ -2461,7 +1661,7 @@ mod tests {
             target: Target::Monster(Uuid::new_v4()),
         });
         assert!(
-            super::can_play_more_cards(&events, players[0]),
+            GameView::from(&events).can_play_more_cards(players[0]),
             "A three player game should allow a player to play at least two card"
         );
 
@@ -2472,12 +1672,12 @@ mod tests {
         });
 
         assert!(
-            !super::can_play_more_cards(&events, players[0]),
+            !GameView::from(&events).can_play_more_cards(players[0]),
             "A 6 player game should allow a player to play no more than two cards"
         );
 
         assert!(
-            super::can_play_more_cards(&events, players[1]),
+            GameView::from(&events).can_play_more_cards(players[1]),
             "Other players playing cards should not affect other players ability to play cards"
         );
     }
@@ -2492,6 +1692,7 @@ mod tests {
             [Event::GameCreated {
                 game_id: GameCode::random(),
                 settings: Settings::default(),
+                host: Uuid::nil(),
             }]
             .into_iter()
             .chain(players.iter().clone().flat_map(|id| {
@@ -2508,11 +1709,13 @@ mod tests {
                 time: 0,
                 odds: None,
                 enemies: None,
+                commitment: None,
+                track: Track::Standard,
             }]),
         );
 
         assert!(
-            super::can_play_more_cards(&events, players[0]),
+            GameView::from(&events).can_play_more_cards(players[0]),
             "Player should be able to play cards initially"
         );
 
@@ -2522,12 +1725,12 @@ mod tests {
             target: Target::Monster(Uuid::new_v4()),
         });
         assert!(
-            !super::can_play_more_cards(&events, players[0]),
+            !GameView::from(&events).can_play_more_cards(players[0]),
             "A nine player game should allow only a single card to be played"
         );
 
         assert!(
-            super::can_play_more_cards(&events, players[1]),
+            GameView::from(&events).can_play_more_cards(players[1]),
             "Other players playing cards should not affect other players ability to play cards"
         );
     }
@@ -2622,7 +1825,8 @@ mod tests {
                 settings: Settings {
                     payout: Payout::Pool,
                     ..Default::default()
-                }
+                },
+                host: Uuid::nil()
             },
             Event::PlayerJoined {
                 name: "Alice".into(),
@@ -2646,6 +1850,8 @@ mod tests {
                 time: 0,
                 odds: None,
                 enemies: None,
+                commitment: None,
+                track: Track::Standard,
             }
         ];
 
@@ -2657,21 +1863,23 @@ mod tests {
                     session_id,
                     monster_id,
                     amount,
+                    kind: BetKind::Win,
                 }));
             }
 
-            events.push_back(Event::RaceStarted { time: 0 });
+            events.push_back(Event::RaceStarted {
+                time: 0,
+                reveal: None,
+            });
             events.push_back(Event::RaceFinished {
                 time: 0,
                 results: RaceResults {
-                    first: monster_a,
-                    second: monster_b,
-                    third: monster_c,
+                    placings: vec![monster_a, monster_b, monster_c],
                 },
             });
 
-            let accounts = super::all_account_balances(&events);
-            let winnings = super::winnings(&events);
+            let accounts = GameView::from(&events).all_account_balances().clone();
+            let winnings = GameView::from(&events).winnings().clone();
 
             for (session_id, expected_winnings) in results {
                 assert_eq!(
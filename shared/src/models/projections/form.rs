@@ -0,0 +1,99 @@
+use crate::models::cards::Modifier;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Finish {
+    pub round: u32,
+    // 1-based
+    pub position: usize,
+    pub runners: usize,
+}
+
+// A monster's record in the races run so far this game, oldest first
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Form {
+    pub finishes: Vec<Finish>,
+}
+
+impl Form {
+    pub fn races_run(&self) -> usize {
+        self.finishes.len()
+    }
+
+    pub fn wins(&self) -> usize {
+        self.finishes
+            .iter()
+            .filter(|finish| finish.position == 1)
+            .count()
+    }
+
+    pub fn last(&self, count: usize) -> &[Finish] {
+        &self.finishes[self.finishes.len().saturating_sub(count)..]
+    }
+
+    fn ran_in(&self, round: u32) -> Option<&Finish> {
+        self.finishes
+            .iter()
+            .rev()
+            .find(|finish| finish.round == round)
+    }
+
+    // Winning the last race puts a monster on a hot streak, running in both of the last two races
+    // tires it out
+    pub fn hot_streak(&self, round: u32) -> bool {
+        round > 1
+            && self
+                .ran_in(round - 1)
+                .is_some_and(|finish| finish.position == 1)
+    }
+
+    pub fn fatigued(&self, round: u32) -> bool {
+        round > 2 && self.ran_in(round - 1).is_some() && self.ran_in(round - 2).is_some()
+    }
+
+    // Applied to the monster's stats before they're turned into race weights, like cards are
+    pub fn modifiers(&self, round: u32) -> Vec<Modifier> {
+        let mut modifiers = vec![];
+
+        if self.hot_streak(round) {
+            modifiers.push(Modifier::Dexterity(1));
+        }
+
+        if self.fatigued(round) {
+            modifiers.push(Modifier::Strength(-1));
+        }
+
+        modifiers
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::models::cards::Modifier;
+
+    use super::{Finish, Form};
+
+    #[test]
+    fn recent_races_change_the_modifiers() {
+        let finish = |round, position| Finish {
+            round,
+            position,
+            runners: 3,
+        };
+
+        let form = Form {
+            finishes: vec![finish(1, 3), finish(2, 2), finish(3, 1)],
+        };
+
+        assert_eq!(form.races_run(), 3);
+        assert_eq!(form.wins(), 1);
+        assert_eq!(form.last(2), &[finish(2, 2), finish(3, 1)]);
+
+        assert_eq!(
+            form.modifiers(4),
+            vec![Modifier::Dexterity(1), Modifier::Strength(-1)]
+        );
+        assert_eq!(form.modifiers(3), vec![Modifier::Strength(-1)]);
+        assert_eq!(form.modifiers(5), vec![]);
+        assert_eq!(Form::default().modifiers(2), vec![]);
+    }
+}
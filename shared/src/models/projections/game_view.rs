@@ -0,0 +1,1406 @@
+use std::{collections::BTreeMap, ops::Deref, sync::Arc, time::Duration};
+
+use im::{HashMap, OrdMap, OrdSet, Vector};
+use rand::{
+    RngCore, SeedableRng, distributions::WeightedIndex, prelude::Distribution, rngs::StdRng,
+    seq::SliceRandom,
+};
+use uuid::Uuid;
+
+use crate::{
+    models::{
+        cards::{CARDS, Card, PlayerEffect, Target},
+        events::{Event, Odds, OddsExt, Payout, PlacedBet, Settings},
+        fairness::{self, Digest, Secret},
+        game_code::GameCode,
+        monsters::{MONSTERS, Monster},
+        projections::{
+            Finish, Form, INFLATION_FACTOR, LedgerEntry, PlayedMonsterCard, PlayerInfo, Presence,
+            ToteBoard, Transaction, effective_cards,
+            race::{self, RaceResults},
+        },
+        rng::{self, Stream},
+        roster::MonsterDefinition,
+        timers::Timer,
+        tracks::Track,
+    },
+    time::*,
+};
+
+// An incrementally maintained view of a game, every query is answered from state that is updated
+// one event at a time by `apply` rather than re-folding the whole event log.
+#[derive(Debug, Clone, Default)]
+pub struct GameView {
+    events: Vector<Event>,
+
+    game_id: Option<GameCode>,
+    settings: Settings,
+    host: Uuid,
+    // None for the built-in monsters
+    roster: Option<Arc<[MonsterDefinition]>>,
+
+    players: OrdMap<Uuid, PlayerInfo>,
+    kicked: OrdSet<Uuid>,
+    // Names of everyone watching without a seat, and those waiting for one at the next round
+    spectators: OrdMap<Uuid, String>,
+    queued: OrdMap<Uuid, String>,
+    ledger: Vector<LedgerEntry>,
+    accounts: OrdMap<Uuid, i32>,
+    debt: HashMap<Uuid, u32>,
+    hands: OrdMap<Uuid, Vec<Card>>,
+
+    round: u32,
+    races_finished: usize,
+    finished: bool,
+    bought_cards: u32,
+    cards_dealt: u64,
+
+    round_started_at: Option<u32>,
+    betting: Option<u32>,
+    racing: Option<u32>,
+    paused_at: Option<u32>,
+    summary_started_at: Option<u32>,
+    // When each pending timer is due, in seconds since the epoch
+    timers: OrdMap<Timer, u32>,
+    // Seconds added to this round's pregame timer by the host, including time spent paused
+    timer_extension: u32,
+
+    odds: Option<Odds>,
+    enemies: Option<std::collections::HashMap<Uuid, Uuid>>,
+    track: Track,
+
+    // Only ever set on the server, see `fairness`
+    secret: Option<Secret>,
+    commitment: Option<Digest>,
+    reveal: Option<Digest>,
+    // The field the last committed race was run with, kept so it can be checked when asked
+    raced_monsters: Option<Vec<Monster>>,
+
+    bets: OrdMap<Uuid, Vector<PlacedBet>>,
+    ready_to_race: OrdSet<Uuid>,
+    winnings: OrdMap<Uuid, i32>,
+    results: Option<RaceResults>,
+
+    played_monster_cards: Vec<PlayedMonsterCard>,
+    form: OrdMap<Uuid, Form>,
+    cards_played: OrdMap<Uuid, usize>,
+    scrutinised: OrdSet<Uuid>,
+}
+
+impl Deref for GameView {
+    type Target = Vector<Event>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.events
+    }
+}
+
+impl From<&Vector<Event>> for GameView {
+    fn from(events: &Vector<Event>) -> Self {
+        let mut view = GameView::default();
+
+        for event in events {
+            view.update(event);
+        }
+
+        view.events = events.clone();
+
+        view
+    }
+}
+
+impl From<Vector<Event>> for GameView {
+    fn from(events: Vector<Event>) -> Self {
+        GameView::from(&events)
+    }
+}
+
+impl FromIterator<Event> for GameView {
+    fn from_iter<T: IntoIterator<Item = Event>>(iter: T) -> Self {
+        let mut view = GameView::default();
+
+        for event in iter {
+            view.apply(event);
+        }
+
+        view
+    }
+}
+
+impl GameView {
+    pub fn apply(&mut self, event: Event) {
+        self.update(&event);
+        self.events.push_back(event);
+    }
+
+    pub fn events(&self) -> &Vector<Event> {
+        &self.events
+    }
+
+    pub fn last_game_event(&self) -> Option<&Event> {
+        self.events
+            .iter()
+            .rev()
+            .find(|event| !event.is_bookkeeping())
+    }
+
+    fn update(&mut self, event: &Event) {
+        match event {
+            Event::GameCreated {
+                game_id,
+                settings,
+                host,
+            } => {
+                self.game_id = Some(*game_id);
+                self.settings = *settings;
+                self.host = *host;
+            }
+            Event::RosterChosen { monsters } => {
+                self.roster = Some(monsters.as_slice().into());
+            }
+            Event::PlayerJoined {
+                session_id,
+                name,
+                initial_cards,
+            } => {
+                self.players.insert(
+                    *session_id,
+                    PlayerInfo {
+                        session_id: *session_id,
+                        name: name.clone(),
+                        ready: false,
+                        presence: Presence::Connected,
+                    },
+                );
+                self.record(
+                    *session_id,
+                    Transaction::StartingBalance,
+                    self.settings.starting_balance,
+                );
+                self.hands.insert(*session_id, initial_cards.clone());
+                self.cards_dealt += initial_cards.len() as u64;
+                self.spectators.remove(session_id);
+            }
+            Event::SpectatorJoined { session_id, name } => {
+                self.spectators.insert(*session_id, name.clone());
+            }
+            Event::PlayerQueued { session_id, name } => {
+                self.queued.insert(*session_id, name.clone());
+            }
+            Event::LatePlayerJoined {
+                session_id,
+                name,
+                initial_cards,
+                balance,
+            } => {
+                self.players.insert(
+                    *session_id,
+                    PlayerInfo {
+                        session_id: *session_id,
+                        name: name.clone(),
+                        ready: true,
+                        presence: Presence::Connected,
+                    },
+                );
+                self.record(*session_id, Transaction::CatchUpBalance, *balance);
+                self.hands.insert(*session_id, initial_cards.clone());
+                self.cards_dealt += initial_cards.len() as u64;
+                self.spectators.remove(session_id);
+                self.queued.remove(session_id);
+            }
+            Event::ChangedProfile { session_id, name } => {
+                if let Some(info) = self.players.get_mut(session_id) {
+                    info.name = name.clone();
+                }
+            }
+            Event::PlayerReady { session_id } => {
+                if let Some(info) = self.players.get_mut(session_id) {
+                    info.ready = true;
+                }
+            }
+            Event::PlayerKicked { session_id } => {
+                // Their stakes go with them, otherwise they'd still have a share of the pool
+                self.players.remove(session_id);
+                self.hands.remove(session_id);
+                self.accounts.remove(session_id);
+                self.debt.remove(session_id);
+                self.bets.remove(session_id);
+                self.kicked.insert(*session_id);
+
+                // Nobody gets a rival bonus for a rival who isn't racing any more
+                if let Some(enemies) = self.enemies.as_mut() {
+                    enemies.retain(|player, enemy| player != session_id && enemy != session_id);
+                }
+            }
+            Event::PlayerConnected { session_id } => {
+                self.set_presence(*session_id, Presence::Connected)
+            }
+            Event::PlayerDisconnected { session_id, time } => {
+                self.set_presence(*session_id, Presence::Away(*time))
+            }
+            Event::PlayerLeft { session_id } => self.set_presence(*session_id, Presence::Left),
+            Event::RoundStarted {
+                time,
+                odds,
+                enemies,
+                commitment,
+                track,
+            } => {
+                self.round += 1;
+                self.round_started_at = Some(*time);
+                self.betting = Some(*time);
+                self.paused_at = None;
+                self.summary_started_at = None;
+                self.timer_extension = 0;
+
+                self.odds = odds.clone();
+                self.enemies = enemies.clone();
+                self.track = *track;
+
+                self.commitment = *commitment;
+                self.reveal = None;
+                self.raced_monsters = None;
+
+                self.winnings.clear();
+                self.played_monster_cards.clear();
+                self.cards_played.clear();
+                self.scrutinised.clear();
+                self.ready_to_race.clear();
+            }
+            Event::BoughtCard { session_id, card } => {
+                self.bought_cards += 1;
+                self.cards_dealt += 1;
+                self.record(
+                    *session_id,
+                    Transaction::CardPurchase { card: *card },
+                    -self.settings.card_price,
+                );
+
+                self.hands.entry(*session_id).or_default().push(*card);
+            }
+            Event::PlayedCard {
+                session_id,
+                card,
+                target,
+            } => self.play_card(*session_id, *card, target),
+            Event::BorrowedMoney { session_id, amount } => {
+                self.record(*session_id, Transaction::Loan, *amount as i32);
+            }
+            Event::PaidBackMoney { session_id, amount } => {
+                self.record(*session_id, Transaction::Repayment, -(*amount as i32));
+            }
+            Event::PlacedBet(bet) => {
+                self.record(
+                    bet.session_id,
+                    Transaction::BetStake {
+                        monster_id: bet.monster_id,
+                    },
+                    -bet.amount,
+                );
+
+                self.bets.entry(bet.session_id).or_default().push_back(*bet);
+            }
+            Event::PlayerReadyToRace { session_id } => {
+                self.ready_to_race.insert(*session_id);
+            }
+            Event::GamePaused { time } => self.paused_at = Some(*time),
+            Event::GameResumed { time } => {
+                if let Some(paused_at) = self.paused_at.take() {
+                    self.timer_extension += time.saturating_sub(paused_at);
+                }
+            }
+            Event::TimerExtended { seconds } => self.timer_extension += seconds,
+            Event::RaceStarted { time, reveal } => {
+                self.betting = None;
+                self.racing = Some(*time);
+                self.paused_at = None;
+                self.reveal = *reveal;
+            }
+            Event::RaceFinished { time, results } => {
+                self.summary_started_at = Some(*time);
+
+                // Has to be taken while the monsters still have this race's cards and form
+                if self.commitment.is_some() && self.reveal.is_some() {
+                    self.raced_monsters = Some(self.monsters(self.race_seed()));
+                }
+
+                self.racing = None;
+                self.races_finished += 1;
+                self.results = Some(results.clone());
+
+                for (index, monster_id) in results.placings.iter().enumerate() {
+                    self.form
+                        .entry(*monster_id)
+                        .or_default()
+                        .finishes
+                        .push(Finish {
+                            round: self.round,
+                            position: index + 1,
+                            runners: results.placings.len(),
+                        });
+                }
+
+                self.settle_bets(results);
+                self.charge_interest();
+
+                self.bets.clear();
+                self.played_monster_cards.clear();
+            }
+            Event::TimerScheduled { timer, at } => {
+                self.timers.insert(*timer, *at);
+            }
+            Event::TimerFired { timer } => {
+                self.timers.remove(timer);
+            }
+            Event::RoundVoided { .. } => self.void_round(),
+            Event::GameFinished => {
+                self.finished = true;
+                self.summary_started_at = None;
+            }
+        }
+    }
+
+    fn set_presence(&mut self, player: Uuid, presence: Presence) {
+        if let Some(info) = self.players.get_mut(&player) {
+            info.presence = presence;
+        }
+    }
+
+    fn void_round(&mut self) {
+        let bets = self
+            .bets
+            .values()
+            .flat_map(|bets| bets.iter().copied())
+            .collect::<Vec<_>>();
+
+        for bet in bets {
+            self.record(
+                bet.session_id,
+                Transaction::Refund {
+                    monster_id: bet.monster_id,
+                },
+                bet.amount,
+            );
+        }
+
+        self.betting = None;
+        self.racing = None;
+        self.summary_started_at = None;
+        self.paused_at = None;
+
+        self.bets.clear();
+        self.winnings.clear();
+        self.played_monster_cards.clear();
+    }
+
+    fn play_card(&mut self, source: Uuid, card: Card, target: &Target) {
+        let remove_card_from_hand = |cards: &mut Vec<Card>, card| {
+            if let Some(index) = cards.iter().position(|it| *it == card) {
+                cards.remove(index);
+            }
+        };
+
+        let effect = card.definition().player_effect;
+
+        match (effect, target) {
+            (Some(PlayerEffect::StealCards { count }), Target::Player(target))
+                if source != *target =>
+            {
+                let target_cards = self.hands.entry(*target).or_default();
+                let mut removed_cards = (0..count)
+                    .filter_map(|_| target_cards.pop())
+                    .collect::<Vec<_>>();
+
+                let source_cards = self.hands.entry(source).or_default();
+
+                remove_card_from_hand(source_cards, card);
+                source_cards.append(&mut removed_cards);
+            }
+            _ => remove_card_from_hand(self.hands.entry(source).or_default(), card),
+        }
+
+        match (effect, target) {
+            (Some(PlayerEffect::StealBalance { percent }), Target::Player(target)) => {
+                assert_ne!(*target, source, "{} cannot be played on self", card.name());
+
+                let amount = self.account_balance(*target) * percent / 100;
+
+                self.record(
+                    *target,
+                    Transaction::TheftTransfer {
+                        counterparty: source,
+                    },
+                    -amount,
+                );
+                self.record(
+                    source,
+                    Transaction::TheftTransfer {
+                        counterparty: *target,
+                    },
+                    amount,
+                );
+            }
+            (Some(PlayerEffect::GiftCrystals { amount }), Target::Player(target)) => {
+                self.record(*target, Transaction::CrystalsGift { from: source }, amount);
+            }
+            (Some(PlayerEffect::BlockCards), Target::MultiplePlayers(targets)) => {
+                self.scrutinised.extend(targets.iter().copied());
+            }
+            (_, Target::Monster(monster_id)) => {
+                let played = PlayedMonsterCard {
+                    card,
+                    monster_id: *monster_id,
+                };
+
+                if let Err(index) = self.played_monster_cards.binary_search(&played) {
+                    self.played_monster_cards.insert(index, played);
+                }
+            }
+            _ => {}
+        }
+
+        if !card.is_free() {
+            *self.cards_played.entry(source).or_default() += 1;
+        }
+    }
+
+    fn settle_bets(&mut self, results: &RaceResults) {
+        let bets = self
+            .bets
+            .values()
+            .flat_map(|bets| bets.iter().copied())
+            .collect::<Vec<_>>();
+
+        match self.settings.payout {
+            Payout::Odds => {
+                let mut did_win = OrdSet::new();
+
+                // Every monster in the race is in the results
+                let odds = self.odds.clone().unwrap_or_else(|| {
+                    tracing::warn!("no odds for the race, paying out even odds");
+                    Odds::even(results.placings.iter().copied())
+                });
+
+                for bet in bets.iter().filter(|bet| bet.won(results)) {
+                    let payout = odds.bet_payout(bet.monster_id, bet.kind);
+
+                    did_win.insert(bet.session_id);
+                    self.record(
+                        bet.session_id,
+                        Transaction::Payout {
+                            monster_id: bet.monster_id,
+                        },
+                        (payout * (bet.amount as f32)) as i32,
+                    );
+                }
+
+                for (player_id, enemy_id) in self.enemies.clone().unwrap_or_default() {
+                    // Players get money when their enemy doesn't have a payout
+                    if !did_win.contains(&enemy_id) && self.players.contains_key(&player_id) {
+                        self.record(player_id, Transaction::RivalBonus { rival: enemy_id }, 500);
+                    }
+                }
+            }
+            Payout::Pool => {
+                // Each kind of bet has its own pool, winning place bets only share the place pool
+                let mut pools = BTreeMap::<_, Vec<PlacedBet>>::new();
+
+                for bet in bets {
+                    pools.entry(bet.kind.name()).or_default().push(bet);
+                }
+
+                for pool in pools.into_values() {
+                    let (winners, losers): (Vec<_>, Vec<_>) =
+                        pool.into_iter().partition(|bet| bet.won(results));
+
+                    let total_losses = losers.iter().fold(0, |total, bet| total + bet.amount);
+                    let total_wins = winners.iter().fold(0, |total, bet| total + bet.amount);
+
+                    for bet in winners {
+                        let amount =
+                            INFLATION_FACTOR * bet.amount * total_losses / total_wins / 100;
+
+                        self.record(
+                            bet.session_id,
+                            Transaction::Payout {
+                                monster_id: bet.monster_id,
+                            },
+                            bet.amount + amount,
+                        );
+                    }
+                }
+            }
+        }
+
+        let round = self.round;
+        self.winnings.clear();
+
+        for entry in self
+            .ledger
+            .iter()
+            .rev()
+            .take_while(|entry| entry.round == round)
+        {
+            if entry.counts_towards_winnings() {
+                *self.winnings.entry(entry.player).or_default() += entry.amount;
+            }
+        }
+    }
+
+    fn charge_interest(&mut self) {
+        let debts = self.debt.clone();
+
+        for (player, debt) in debts {
+            let interest = ((debt as f32) * (1.0 + self.settings.interest_rate)) as u32 - debt;
+
+            if interest > 0 {
+                self.record(player, Transaction::Interest, interest as i32);
+            }
+        }
+    }
+
+    fn record(&mut self, player: Uuid, transaction: Transaction, amount: i32) {
+        let entry = LedgerEntry {
+            round: self.round,
+            player,
+            transaction,
+            amount,
+        };
+
+        *self.accounts.entry(player).or_default() += entry.balance_change();
+
+        if entry.debt_change() != 0 {
+            let debt = self.debt.entry(player).or_default();
+            *debt = debt.saturating_add_signed(entry.debt_change());
+        }
+
+        self.ledger.push_back(entry);
+    }
+
+    // None until the game has been created
+    pub fn game_id(&self) -> Option<GameCode> {
+        self.game_id
+    }
+
+    pub fn settings(&self) -> Settings {
+        if self.game_id.is_none() && !self.events.is_empty() {
+            tracing::error!("first event wasn't game created");
+        }
+
+        self.settings
+    }
+
+    pub fn round(&self) -> u32 {
+        self.round
+    }
+
+    pub fn is_host(&self, session_id: Uuid) -> bool {
+        !session_id.is_nil() && session_id == self.host
+    }
+
+    pub fn was_kicked(&self, session_id: Uuid) -> bool {
+        self.kicked.contains(&session_id)
+    }
+
+    pub fn player_count(&self) -> usize {
+        self.players.len()
+    }
+
+    pub fn is_spectator(&self, session_id: Uuid) -> bool {
+        self.spectators.contains_key(&session_id) || self.queued.contains_key(&session_id)
+    }
+
+    pub fn spectators(&self) -> &OrdMap<Uuid, String> {
+        &self.spectators
+    }
+
+    pub fn is_queued(&self, session_id: Uuid) -> bool {
+        self.queued.contains_key(&session_id)
+    }
+
+    pub fn queued_players(&self) -> &OrdMap<Uuid, String> {
+        &self.queued
+    }
+
+    // The median balance of everyone already playing, so late joiners are neither ahead nor
+    // hopelessly behind
+    pub fn catch_up_balance(&self) -> i32 {
+        let mut balances = self
+            .players
+            .keys()
+            .map(|player| self.account_balance(*player))
+            .collect::<Vec<_>>();
+
+        balances.sort();
+
+        match balances.len() {
+            0 => self.settings.starting_balance,
+            len if len % 2 == 0 => (balances[len / 2 - 1] + balances[len / 2]) / 2,
+            len => balances[len / 2],
+        }
+    }
+
+    pub fn players(&self) -> &OrdMap<Uuid, PlayerInfo> {
+        &self.players
+    }
+
+    pub fn player_info(&self, player: Uuid) -> Option<PlayerInfo> {
+        self.players.get(&player).cloned()
+    }
+
+    pub fn player_exists(&self, player: Uuid) -> bool {
+        self.players.contains_key(&player)
+    }
+
+    pub fn game_has_started(&self) -> bool {
+        self.round > 0
+    }
+
+    // Players who have left keep their balance but are no longer waited on
+    pub fn active_players(&self) -> impl Iterator<Item = &PlayerInfo> {
+        self.players
+            .values()
+            .filter(|player| player.presence != Presence::Left)
+    }
+
+    pub fn active_player_count(&self) -> usize {
+        self.active_players().count()
+    }
+
+    pub fn all_players_ready(&self) -> bool {
+        self.active_players().all(|player| player.ready)
+    }
+
+    // When a disconnected player will be treated as having left
+    pub fn afk_deadline(&self, player: Uuid) -> Option<SystemTime> {
+        let Presence::Away(since) = self.players.get(&player)?.presence else {
+            return None;
+        };
+
+        Some(UNIX_EPOCH + Duration::from_secs(since as u64 + self.settings.afk_timeout as u64))
+    }
+
+    pub fn next_afk_deadline(&self) -> Option<(Uuid, SystemTime)> {
+        self.players
+            .keys()
+            .filter_map(|player| Some((*player, self.afk_deadline(*player)?)))
+            .min_by_key(|(_, deadline)| *deadline)
+    }
+
+    pub fn minimum_bet(&self) -> i32 {
+        100 + 100 * self.races_finished as i32
+    }
+
+    pub fn maximum_debt(&self) -> i32 {
+        300 + 200 * self.round as i32
+    }
+
+    pub fn placed_bets(&self) -> &OrdMap<Uuid, Vector<PlacedBet>> {
+        &self.bets
+    }
+
+    pub fn tote_board(&self) -> ToteBoard {
+        let monsters = self
+            .monsters(self.race_seed())
+            .iter()
+            .map(|monster| monster.uuid)
+            .collect::<Vec<_>>();
+
+        ToteBoard::new(&monsters, self.bets.values().flatten())
+    }
+
+    pub fn player_has_bet(&self, player: Uuid) -> bool {
+        self.bets.contains_key(&player)
+    }
+
+    pub fn all_players_have_bet(&self) -> bool {
+        self.active_player_count() > 0
+            && self
+                .active_players()
+                .all(|player| self.bets.contains_key(&player.session_id))
+    }
+
+    // Placing a bet means the player is done for the round, ready to race lets them skip betting
+    pub fn player_is_ready_to_race(&self, player: Uuid) -> bool {
+        self.player_has_bet(player) || self.ready_to_race.contains(&player)
+    }
+
+    pub fn all_players_ready_to_race(&self) -> bool {
+        self.active_player_count() > 0
+            && self
+                .active_players()
+                .all(|player| self.player_is_ready_to_race(player.session_id))
+    }
+
+    pub fn all_account_balances(&self) -> &OrdMap<Uuid, i32> {
+        &self.accounts
+    }
+
+    pub fn account_balance(&self, player: Uuid) -> i32 {
+        self.accounts.get(&player).copied().unwrap_or_default()
+    }
+
+    pub fn ledger(&self) -> &Vector<LedgerEntry> {
+        &self.ledger
+    }
+
+    pub fn statement(&self, player: Uuid) -> Vec<LedgerEntry> {
+        self.ledger
+            .iter()
+            .filter(|entry| entry.player == player)
+            .copied()
+            .collect()
+    }
+
+    pub fn winnings(&self) -> &OrdMap<Uuid, i32> {
+        &self.winnings
+    }
+
+    pub fn all_debt(&self) -> &HashMap<Uuid, u32> {
+        &self.debt
+    }
+
+    pub fn debt(&self, player: Uuid) -> u32 {
+        self.debt.get(&player).copied().unwrap_or_default()
+    }
+
+    pub fn cards_in_hand(&self, player: Uuid) -> Vec<Card> {
+        self.hands.get(&player).cloned().unwrap_or_default()
+    }
+
+    pub fn bought_cards(&self) -> u32 {
+        self.bought_cards
+    }
+
+    pub fn can_play_more_cards(&self, player: Uuid) -> bool {
+        // let max_cards = match player_count(events) {
+        //     0..=3 => 3,
+        //     4..=8 => 2,
+        //     9.. => 1,
+        // };
+        let max_cards = 1;
+
+        if !self.game_has_started() || self.scrutinised.contains(&player) {
+            return false;
+        }
+
+        self.cards_played.get(&player).copied().unwrap_or_default() < max_cards
+    }
+
+    pub fn valid_target_for_card(&self, player: Uuid, target: Target) -> bool {
+        match target {
+            Target::Player(target) => player != target && self.player_exists(target),
+            Target::MultiplePlayers(targets) => targets
+                .iter()
+                .all(|target| self.valid_target_for_card(player, Target::Player(*target))),
+            Target::Monster(target) => self
+                .monsters(self.race_seed())
+                .iter()
+                .any(|monster| monster.uuid == target),
+        }
+    }
+
+    pub fn unique_played_monster_cards(&self) -> &Vec<PlayedMonsterCard> {
+        &self.played_monster_cards
+    }
+
+    // Have to use u32 instead of u64 because JS can't handle u64
+    pub fn race_seed_for_round(&self, round: u32) -> u32 {
+        let game_id =
+            u32::from_be_bytes(self.game_id.unwrap_or_default().bytes().as_chunks::<4>().0[0]);
+
+        game_id.wrapping_add(round)
+    }
+
+    pub fn race_seed(&self) -> u32 {
+        self.race_seed_for_round(self.round)
+    }
+
+    pub fn with_secret(mut self, secret: Secret) -> Self {
+        self.secret = Some(secret);
+        self
+    }
+
+    // Clients never make random decisions themselves, without the secret they fall back to an all
+    // zero secret so that projections which need a stream still work
+    fn rng(&self, stream: Stream, index: u64) -> StdRng {
+        rng::rng(
+            &self.secret.unwrap_or_default(),
+            self.game_id.unwrap_or_default(),
+            stream,
+            index,
+        )
+    }
+
+    // Seeds the Monte Carlo simulation of the odds for `round`
+    pub fn odds_seed(&self, round: u32) -> u32 {
+        self.rng(Stream::Odds, round as u64).next_u32()
+    }
+
+    // Only the server can pick the track, clients learn it from `RoundStarted`
+    pub fn track_for_round(&self, round: u32) -> Track {
+        *Track::ALL
+            .choose(&mut self.rng(Stream::Tracks, round as u64))
+            .unwrap()
+    }
+
+    pub fn track(&self) -> Track {
+        self.track
+    }
+
+    // The value committed to when `round` starts, only available on the server
+    pub fn reveal_for_round(&self, round: u32) -> Option<Digest> {
+        Some(Digest::reveal(&self.secret?, self.game_id?, round))
+    }
+
+    pub fn commitment(&self) -> Option<Digest> {
+        self.commitment
+    }
+
+    pub fn reveal(&self) -> Option<Digest> {
+        self.reveal
+    }
+
+    // The race itself is run from the revealed seed, the public race seed only picks the monsters.
+    // Games from before seeds were committed to fall back to the public seed.
+    pub fn results_seed(&self) -> u32 {
+        match self.reveal {
+            Some(reveal) => reveal.seed(),
+            None => self.race_seed(),
+        }
+    }
+
+    // Whether the last race was run from the seed committed to before betting, `None` if the round
+    // didn't commit to a seed
+    pub fn race_verified(&self) -> Option<bool> {
+        let (commitment, reveal) = self.commitment.zip(self.reveal)?;
+        let monsters = self.raced_monsters.as_ref()?;
+        let results = self.results.as_ref()?;
+
+        // The drawn order is always the order the race is run in, so there's no need to run it
+        Some(
+            fairness::verify(commitment, reveal)
+                && race::finishing_order(monsters, self.track, reveal.seed()) == *results,
+        )
+    }
+
+    pub fn monsters(&self, race_seed: u32) -> Vec<Monster> {
+        let mut rng = StdRng::seed_from_u64(race_seed as u64);
+
+        let mut monsters = match &self.roster {
+            Some(roster) => roster
+                .choose_multiple(&mut rng, self.settings.field_size)
+                .cloned()
+                .map(Monster::from)
+                .collect::<Vec<_>>(),
+            None => MONSTERS
+                .choose_multiple(&mut rng, self.settings.field_size)
+                .cloned()
+                .collect(),
+        };
+
+        let round = self.race_round();
+
+        for monster in &mut monsters {
+            for card in effective_cards(&self.played_monster_cards, monster.uuid) {
+                for modifier in card.definition().modifiers {
+                    modifier.apply(monster);
+                }
+            }
+
+            for modifier in self.form(monster.uuid).modifiers(round) {
+                modifier.apply(monster);
+            }
+        }
+
+        monsters
+    }
+
+    // The round whose race is being run, or is about to be between finishing one race and starting
+    // the next round
+    fn race_round(&self) -> u32 {
+        if self.races_finished as u32 >= self.round {
+            self.round + 1
+        } else {
+            self.round
+        }
+    }
+
+    pub fn form(&self, monster_id: Uuid) -> Form {
+        self.form.get(&monster_id).cloned().unwrap_or_default()
+    }
+
+    pub fn hot_streak(&self, monster_id: Uuid) -> bool {
+        self.form(monster_id).hot_streak(self.race_round())
+    }
+
+    pub fn fatigued(&self, monster_id: Uuid) -> bool {
+        self.form(monster_id).fatigued(self.race_round())
+    }
+
+    pub fn race_duration(&self) -> f32 {
+        let monsters = self.monsters(self.race_seed());
+
+        let (_, jumps) = race::results(&monsters, self.track, self.results_seed());
+
+        jumps.last().unwrap().end
+    }
+
+    pub fn pre_race_duration(&self) -> Duration {
+        Duration::from_secs(3 + 4 * self.played_monster_cards.len() as u64)
+    }
+
+    pub fn pre_computed_odds(&self) -> Odds {
+        self.odds.clone().unwrap_or_else(|| {
+            Odds::even(
+                self.monsters(self.race_seed())
+                    .iter()
+                    .map(|monster| monster.uuid),
+            )
+        })
+    }
+
+    // Whether `GameFinished` has been written, `game_finished` is true as soon as the last race is run
+    pub fn finish_recorded(&self) -> bool {
+        self.finished
+    }
+
+    pub fn game_finished(&self) -> bool {
+        self.finished || self.races_finished >= self.settings.rounds
+    }
+
+    pub fn paused(&self) -> bool {
+        self.paused_at.is_some()
+    }
+
+    // When the race will start if not everyone is ready, every round has one. The timer stands still
+    // while the game is paused.
+    pub fn pregame_deadline(&self) -> Option<SystemTime> {
+        let start = self.round_started_at?;
+
+        Some(
+            UNIX_EPOCH
+                + Duration::from_secs(start as u64)
+                + Duration::from_secs(self.settings.pre_game_timeout as u64)
+                + Duration::from_secs(self.timer_extension as u64),
+        )
+    }
+
+    pub fn time_left_in_pregame(&self, clock: &dyn Clock) -> Option<u64> {
+        let now = match self.paused_at {
+            Some(paused_at) => UNIX_EPOCH + Duration::from_secs(paused_at as u64),
+            None => clock.now(),
+        };
+
+        match self.pregame_deadline()?.duration_since(now) {
+            Ok(it) => Some(it.as_secs()),
+            Err(_) => Some(0),
+        }
+    }
+
+    // returns Some(finish time) of the last race while its results are being shown, otherwise None
+    pub fn showing_summary(&self) -> Option<u32> {
+        self.summary_started_at
+    }
+
+    pub fn pending_timers(&self) -> &OrdMap<Timer, u32> {
+        &self.timers
+    }
+
+    pub fn due_timers(&self, now: SystemTime) -> Vec<Timer> {
+        let now = now.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+
+        self.timers
+            .iter()
+            .filter(|(_, at)| **at as u64 <= now)
+            .map(|(timer, _)| *timer)
+            .collect()
+    }
+
+    pub fn next_timer(&self) -> Option<(Timer, SystemTime)> {
+        self.timers
+            .iter()
+            .min_by_key(|(_, at)| **at)
+            .map(|(timer, at)| (*timer, UNIX_EPOCH + Duration::from_secs(*at as u64)))
+    }
+
+    pub fn results(&self) -> Option<RaceResults> {
+        self.results.clone()
+    }
+
+    // returns the Some(start time) of the race if its currently in progress, otherwise None
+    pub fn currently_racing(&self) -> Option<u32> {
+        self.racing
+    }
+
+    // returns the Some(start time) of the betting round if its currently in progress, otherwise None
+    pub fn currently_betting(&self) -> Option<u32> {
+        self.betting
+    }
+
+    pub fn victim_of_card(&self, player: Uuid) -> Option<(Card, String)> {
+        match self.last_game_event() {
+            Some(Event::PlayedCard {
+                card,
+                session_id,
+                target: Target::Player(target),
+            }) if *target == player => Some((*card, self.players.get(session_id)?.name.clone())),
+            Some(Event::PlayedCard {
+                card,
+                session_id,
+                target: Target::MultiplePlayers(targets),
+            }) if targets.contains(&player) => {
+                Some((*card, self.players.get(session_id)?.name.clone()))
+            }
+            _ => None,
+        }
+    }
+
+    pub fn enemy(&self, player: Uuid) -> Option<PlayerInfo> {
+        let enemy = self.enemies.as_ref()?.get(&player)?;
+
+        self.players.get(enemy).cloned()
+    }
+
+    pub fn all_enemies(&self) -> std::collections::HashMap<Uuid, Uuid> {
+        let all_players = self.accounts.clone().into_iter().collect::<Vec<_>>();
+
+        if all_players.len() < 2 {
+            return std::collections::HashMap::new();
+        }
+
+        let mut rng = self.rng(Stream::Enemies, self.round as u64);
+
+        let Ok(potential_enemies) =
+            WeightedIndex::new(all_players.iter().map(|&(_, balance)| balance.max(0)))
+        else {
+            return std::collections::HashMap::new();
+        };
+
+        let mut enemies = std::collections::HashMap::new();
+        for &(player_id, _) in &all_players {
+            loop {
+                let enemy_index = potential_enemies.sample(&mut rng);
+                let enemy_id = all_players[enemy_index].0;
+
+                if enemy_id != player_id {
+                    enemies.insert(player_id, enemy_id);
+                    break;
+                }
+            }
+        }
+
+        enemies
+    }
+
+    // Each deal starts a new stream after every card dealt so far
+    fn deck(&self) -> impl Iterator<Item = Card> {
+        let dist = WeightedIndex::new(CARDS.map(|definition| definition.deck_weight)).unwrap();
+        let mut rng = self.rng(Stream::Cards, self.cards_dealt);
+
+        std::iter::repeat_with(move || CARDS[dist.sample(&mut rng)].card)
+    }
+
+    pub fn draw_n_cards_from_deck<const N: usize>(&self) -> [Card; N] {
+        let mut deck = self.deck();
+
+        core::array::from_fn(|_| deck.next().unwrap())
+    }
+
+    // Everyone starts with a Theft card, the rest of their hand is drawn from the deck
+    pub fn initial_cards(&self) -> Vec<Card> {
+        match self.settings.starting_cards {
+            0 => vec![],
+            n => std::iter::once(Card::Theft)
+                .chain(self.deck().take(n - 1))
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use im::{Vector, vector};
+    use uuid::Uuid;
+
+    use crate::models::{
+        cards::{Card, Target},
+        events::{BetKind, Event, Odds, OddsExt, Payout, PlacedBet, Settings},
+        game_code::GameCode,
+        projections::{INFLATION_FACTOR, Transaction, race::RaceResults},
+    };
+
+    use super::GameView;
+
+    fn example_game(alice: Uuid, bob: Uuid, winner: Uuid) -> Vector<Event> {
+        vector![
+            Event::GameCreated {
+                game_id: GameCode::random(),
+                settings: Settings::default(),
+                host: Uuid::nil(),
+            },
+            Event::PlayerJoined {
+                session_id: alice,
+                name: "Alice".into(),
+                initial_cards: vec![Card::Theft],
+            },
+            Event::PlayerJoined {
+                session_id: bob,
+                name: "Bob".into(),
+                initial_cards: vec![],
+            },
+            Event::PlayerReady { session_id: alice },
+            Event::PlayerReady { session_id: bob },
+            Event::start_round_at(0),
+            Event::BorrowedMoney {
+                session_id: bob,
+                amount: 200,
+            },
+            Event::PlayedCard {
+                session_id: alice,
+                card: Card::Theft,
+                target: Target::Player(bob),
+            },
+            Event::PlacedBet(PlacedBet {
+                session_id: alice,
+                monster_id: winner,
+                amount: 100,
+                kind: BetKind::Win,
+            }),
+            Event::PlacedBet(PlacedBet {
+                session_id: alice,
+                monster_id: winner,
+                amount: 200,
+                kind: BetKind::Win,
+            }),
+            Event::PlacedBet(PlacedBet {
+                session_id: bob,
+                monster_id: Uuid::new_v4(),
+                amount: 300,
+                kind: BetKind::Win,
+            }),
+            Event::RaceStarted {
+                time: 0,
+                reveal: None
+            },
+            Event::RaceFinished {
+                time: 0,
+                results: RaceResults {
+                    placings: vec![winner, Uuid::new_v4(), Uuid::new_v4()]
+                },
+            },
+        ]
+    }
+
+    #[test]
+    fn incremental_matches_fold() {
+        let alice = Uuid::new_v4();
+        let bob = Uuid::new_v4();
+
+        let events = example_game(alice, bob, Uuid::new_v4());
+
+        let folded = GameView::from(&events);
+        let mut incremental = GameView::default();
+
+        for event in events.iter().cloned() {
+            incremental.apply(event);
+        }
+
+        assert_eq!(folded.events(), incremental.events());
+        assert_eq!(folded.players(), incremental.players());
+        assert_eq!(
+            folded.all_account_balances(),
+            incremental.all_account_balances()
+        );
+        assert_eq!(folded.winnings(), incremental.winnings());
+        assert_eq!(folded.all_debt(), incremental.all_debt());
+        assert_eq!(
+            folded.cards_in_hand(alice),
+            incremental.cards_in_hand(alice)
+        );
+        assert_eq!(folded.round(), incremental.round());
+        assert_eq!(folded.results(), incremental.results());
+        assert_eq!(folded.race_seed(), incremental.race_seed());
+    }
+
+    #[test]
+    fn every_winning_bet_is_paid_out() {
+        let alice = Uuid::new_v4();
+        let bob = Uuid::new_v4();
+
+        let view = GameView::from(&example_game(alice, bob, Uuid::new_v4()));
+
+        // Alice stole 20% of Bob's 1200, then had both of her bets (100 + 200) pay out at 3x
+        assert_eq!(view.account_balance(alice), 1000 + 240 - 300 + 900);
+        assert_eq!(view.winnings().get(&alice).copied(), Some(600));
+        assert!(view.placed_bets().is_empty());
+        assert_eq!(view.debt(bob), 210);
+    }
+
+    #[test]
+    fn combination_bets_settle_against_the_placings() {
+        let alice = Uuid::new_v4();
+        let bob = Uuid::new_v4();
+        let placings = vec![Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4()];
+
+        let bet = |session_id, monster_id, kind| {
+            Event::PlacedBet(PlacedBet {
+                session_id,
+                monster_id,
+                amount: 100,
+                kind,
+            })
+        };
+
+        for payout in [Payout::Odds, Payout::Pool] {
+            let events = vector![
+                Event::GameCreated {
+                    game_id: GameCode::random(),
+                    settings: Settings {
+                        payout,
+                        ..Settings::default()
+                    },
+                    host: Uuid::nil(),
+                },
+                Event::PlayerJoined {
+                    session_id: alice,
+                    name: "Alice".into(),
+                    initial_cards: vec![],
+                },
+                Event::PlayerJoined {
+                    session_id: bob,
+                    name: "Bob".into(),
+                    initial_cards: vec![],
+                },
+                Event::start_round_at(0),
+                bet(alice, placings[1], BetKind::Place),
+                bet(bob, placings[2], BetKind::Place),
+                bet(
+                    alice,
+                    placings[0],
+                    BetKind::Exacta {
+                        second: placings[1]
+                    }
+                ),
+                bet(
+                    bob,
+                    placings[0],
+                    BetKind::Trifecta {
+                        second: placings[2],
+                        third: placings[1],
+                    }
+                ),
+                Event::RaceStarted {
+                    time: 0,
+                    reveal: None
+                },
+                Event::RaceFinished {
+                    time: 0,
+                    results: RaceResults {
+                        placings: placings.clone()
+                    },
+                },
+            ];
+
+            let view = GameView::from(&events);
+
+            // Without pre-computed odds bets pay out as if every runner was as likely to win, in the
+            // pools only the place pool has a losing stake for Alice to win and nobody wins the
+            // trifecta pool
+            let even = Odds::even(placings.iter().copied());
+            let paid = |monster_id, kind| (even.bet_payout(monster_id, kind) * 100.0) as i32;
+
+            let expected = match payout {
+                Payout::Odds => {
+                    paid(placings[1], BetKind::Place)
+                        + paid(
+                            placings[0],
+                            BetKind::Exacta {
+                                second: placings[1],
+                            },
+                        )
+                        - 200
+                }
+                Payout::Pool => (100 + INFLATION_FACTOR) + 100 - 200,
+            };
+
+            assert_eq!(view.winnings().get(&alice).copied(), Some(expected));
+            assert_eq!(view.winnings().get(&bob).copied(), Some(-200));
+        }
+    }
+
+    #[test]
+    fn form_carries_over_to_the_next_race() {
+        let mut view = GameView::from(&vector![
+            Event::GameCreated {
+                game_id: GameCode::random(),
+                settings: Settings::default(),
+                host: Uuid::nil(),
+            },
+            Event::start_round_at(0),
+        ]);
+
+        let field = view.monsters(view.race_seed());
+        let winner = &field[0];
+
+        view.apply(Event::start_race_at(0));
+        view.apply(Event::RaceFinished {
+            time: 0,
+            results: RaceResults {
+                placings: field.iter().map(|monster| monster.uuid).collect(),
+            },
+        });
+
+        assert_eq!(view.form(winner.uuid).wins(), 1);
+        assert_eq!(view.form(field[2].uuid).last(5)[0].position, 3);
+        assert!(view.hot_streak(winner.uuid));
+        assert!(!view.fatigued(winner.uuid));
+
+        // Between rounds the monsters are already those of the next race
+        let rested = view.monsters(view.race_seed());
+        assert_eq!(rested[0].dexterity, winner.dexterity + 1);
+        assert_eq!(rested[1], field[1]);
+    }
+
+    #[test]
+    fn statement_itemises_every_transaction() {
+        let alice = Uuid::new_v4();
+        let bob = Uuid::new_v4();
+
+        let view = GameView::from(&example_game(alice, bob, Uuid::new_v4()));
+        let statement = view.statement(bob);
+
+        assert_eq!(
+            statement
+                .iter()
+                .map(|entry| (entry.round, entry.transaction.label(), entry.amount))
+                .collect::<Vec<_>>(),
+            vec![
+                (0, "Starting balance", 1000),
+                (1, "Loan", 200),
+                (1, "Theft", -240),
+                (1, "Bet", -300),
+                (1, "Interest", 10),
+            ]
+        );
+        assert_eq!(
+            statement[2].transaction,
+            Transaction::TheftTransfer {
+                counterparty: alice
+            }
+        );
+
+        let balance = statement
+            .iter()
+            .map(|entry| entry.balance_change())
+            .sum::<i32>();
+        let debt = statement
+            .iter()
+            .map(|entry| entry.debt_change())
+            .sum::<i32>();
+
+        assert_eq!(view.account_balance(bob), balance);
+        assert_eq!(view.debt(bob) as i32, debt);
+        assert_eq!(view.winnings().get(&bob).copied(), Some(-300));
+    }
+}
@@ -0,0 +1,75 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::models::cards::Card;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Transaction {
+    StartingBalance,
+    // Players who join part way through start with what everyone else has on average
+    CatchUpBalance,
+    BetStake { monster_id: Uuid },
+    Payout { monster_id: Uuid },
+    RivalBonus { rival: Uuid },
+    CardPurchase { card: Card },
+    TheftTransfer { counterparty: Uuid },
+    CrystalsGift { from: Uuid },
+    Loan,
+    Repayment,
+    Interest,
+    Refund { monster_id: Uuid },
+}
+
+impl Transaction {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Transaction::StartingBalance => "Starting balance",
+            Transaction::CatchUpBalance => "Catch-up balance",
+            Transaction::BetStake { .. } => "Bet",
+            Transaction::Payout { .. } => "Payout",
+            Transaction::RivalBonus { .. } => "Rival bonus",
+            Transaction::CardPurchase { .. } => "Card purchase",
+            Transaction::TheftTransfer { .. } => "Theft",
+            Transaction::CrystalsGift { .. } => "Crystals",
+            Transaction::Loan => "Loan",
+            Transaction::Repayment => "Repayment",
+            Transaction::Interest => "Interest",
+            Transaction::Refund { .. } => "Refund",
+        }
+    }
+}
+
+// A single movement of crystals for one player. The amount is the change in the player's balance,
+// except for interest which only ever accrues on their debt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LedgerEntry {
+    pub round: u32,
+    pub player: Uuid,
+    pub transaction: Transaction,
+    pub amount: i32,
+}
+
+impl LedgerEntry {
+    pub fn balance_change(&self) -> i32 {
+        match self.transaction {
+            Transaction::Interest => 0,
+            _ => self.amount,
+        }
+    }
+
+    pub fn debt_change(&self) -> i32 {
+        match self.transaction {
+            Transaction::Loan | Transaction::Repayment | Transaction::Interest => self.amount,
+            _ => 0,
+        }
+    }
+
+    pub fn counts_towards_winnings(&self) -> bool {
+        matches!(
+            self.transaction,
+            Transaction::BetStake { .. }
+                | Transaction::Payout { .. }
+                | Transaction::RivalBonus { .. }
+        )
+    }
+}
@@ -1,6 +1,6 @@
 use std::f32::consts::PI;
 
-use im::Vector;
+use libm::sinf;
 use rand::{
     Rng, SeedableRng,
     distributions::{Uniform, WeightedIndex},
@@ -8,20 +8,31 @@ use rand::{
     rngs::StdRng,
 };
 use serde::{Deserialize, Serialize};
-use tracing::instrument;
 use uuid::Uuid;
 
-use crate::models::{
-    events::Event,
-    monsters::Monster,
-    projections::{game_id, monsters, round},
-};
+use crate::models::{monsters::Monster, tracks::Track};
 
-#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+// Every monster in the race in the order they crossed the finish line
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct RaceResults {
-    pub first: Uuid,
-    pub second: Uuid,
-    pub third: Uuid,
+    pub placings: Vec<Uuid>,
+}
+
+impl RaceResults {
+    pub fn first(&self) -> Uuid {
+        self.placings[0]
+    }
+
+    pub fn place(&self, position: usize) -> Option<Uuid> {
+        self.placings.get(position - 1).copied()
+    }
+
+    pub fn position(&self, monster_id: Uuid) -> Option<usize> {
+        self.placings
+            .iter()
+            .position(|id| *id == monster_id)
+            .map(|index| index + 1)
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
@@ -49,6 +60,10 @@ impl Ord for Jump {
     }
 }
 
+// Races are run on the server, in the browser and in Bevy, and all of them have to agree on the
+// result. Basic f32 arithmetic is exactly specified by IEEE 754 on every target we build for, but
+// `f32::sin` isn't, it goes to the platform's libm natively and to a Rust port on wasm32. Anything
+// beyond arithmetic has to come from the `libm` crate so that every target runs the same code.
 #[derive(Debug, Clone, Copy)]
 pub struct RacerParams {
     pub start: f32,
@@ -74,10 +89,10 @@ const RACE_TRACK_LENGTH: f32 = 10.0;
 
 impl From<RacerParams> for Racer {
     fn from(params: RacerParams) -> Self {
-        let linear_offset = params.start - params.amplitude * (params.phase).sin();
+        let linear_offset = params.start - params.amplitude * sinf(params.phase);
         let gradient = (RACE_TRACK_LENGTH
             - linear_offset
-            - params.amplitude * (params.frequency * params.end_time + params.phase).sin())
+            - params.amplitude * sinf(params.frequency * params.end_time + params.phase))
             / params.end_time;
 
         Self {
@@ -94,50 +109,63 @@ impl Racer {
     pub fn sample(&self, time: f32) -> f32 {
         self.gradient * time
             + self.linear_offset
-            + self.amplitude * (self.frequency * time + self.phase).sin()
+            + self.amplitude * sinf(self.frequency * time + self.phase)
     }
 }
 
 const STAT_TABLE: [f32; 11] = [-1.0, -0.5, 0.0, 0.3, 0.4, 0.5, 0.6, 0.75, 0.9, 1.0, 1.2];
 
-pub fn results(monsters: &[Monster; 3], seed: u32) -> (RaceResults, Vec<Jump>) {
-    let mut rng = StdRng::seed_from_u64(seed as u64);
+fn weight(monster: &Monster, track: Track) -> f32 {
+    (STAT_TABLE[monster.strength.clamp(0, 10) as usize] * track.strength_factor()
+        + STAT_TABLE[monster.dexterity.clamp(0, 10) as usize] * track.dexterity_factor()
+        + monster.starting_position)
+        .clamp(0.001, 3.0)
+}
 
-    let mut weights = WeightedIndex::new(monsters.map(
-        |Monster {
-             strength,
-             dexterity,
-             starting_position,
-             ..
-         }| {
-            (STAT_TABLE[strength.clamp(0, 10) as usize]
-                + STAT_TABLE[dexterity.clamp(0, 10) as usize]
-                + starting_position)
-                .clamp(0.001, 3.0)
-        },
-    ))
-    .unwrap();
+// The index of every monster in the order they'll finish. These are the first draws from the race's
+// RNG, everything after only decides how the race looks.
+fn draw_order(monsters: &[Monster], track: Track, rng: &mut StdRng) -> Vec<usize> {
+    let mut weights =
+        WeightedIndex::new(monsters.iter().map(|monster| weight(monster, track))).unwrap();
 
-    let first = weights.sample(&mut rng);
-    weights.update_weights(&[(first, &0.0)]).unwrap();
+    let mut order = Vec::with_capacity(monsters.len());
 
-    let second = weights.sample(&mut rng);
-    weights.update_weights(&[(second, &0.0)]).unwrap();
+    for place in 0..monsters.len() {
+        let index = weights.sample(rng);
+        order.push(index);
 
-    let third = weights.sample(&mut rng);
+        if place + 1 < monsters.len() {
+            weights.update_weights(&[(index, &0.0)]).unwrap();
+        }
+    }
 
-    let times = {
-        let first_time = 8.0 + 2.0 * rng.r#gen::<f32>();
-        let second_time = first_time + 0.1 + (rng.r#gen::<f32>() + 1.0) / 2.0;
-        let third_time = second_time + 0.1 + (rng.r#gen::<f32>() + 1.0) / 2.0;
+    order
+}
 
-        [first_time, second_time, third_time]
-    };
+// The least time between two monsters crossing the line
+const FINISH_GAP: f32 = 0.05;
 
-    let mut jumps = Vec::new();
+// Runs the race, handing every jump to `on_jump` in the order they're generated. The random draws
+// don't depend on what's done with the jumps, so every caller sees the same race for the same seed.
+fn simulate(monsters: &[Monster], track: Track, seed: u32, mut on_jump: impl FnMut(Jump)) {
+    let mut rng = StdRng::seed_from_u64(seed as u64);
+
+    let order = draw_order(monsters, track, &mut rng);
+
+    // The stage is always RACE_TRACK_LENGTH long, longer tracks are run at the same speed
+    let pace = track.length() / RACE_TRACK_LENGTH;
 
-    for (place, &index) in [first, second, third].iter().enumerate() {
-        let monster = monsters[index];
+    let mut times = Vec::with_capacity(monsters.len());
+    times.push(pace * (8.0 + 2.0 * rng.r#gen::<f32>()));
+
+    for place in 1..monsters.len() {
+        times.push(times[place - 1] + pace * 0.1 + pace * (rng.r#gen::<f32>() + 1.0) / 2.0);
+    }
+
+    let mut finished_at = None;
+
+    for (place, &index) in order.iter().enumerate() {
+        let monster = &monsters[index];
 
         let racer = Racer::from(RacerParams {
             start: monster.starting_position,
@@ -149,6 +177,9 @@ pub fn results(monsters: &[Monster; 3], seed: u32) -> (RaceResults, Vec<Jump>) {
 
         let mut time = 0.;
 
+        // A slow last jump could otherwise land after the monster drawn to finish behind it
+        let earliest_finish = finished_at.map_or(0.0, |finished_at| finished_at + FINISH_GAP);
+
         let dexterity = (monster.dexterity.clamp(0, 10) as f32) / 10.;
 
         loop {
@@ -165,20 +196,33 @@ pub fn results(monsters: &[Monster; 3], seed: u32) -> (RaceResults, Vec<Jump>) {
                 distance = 10.2;
             }
 
-            jumps.push(Jump {
+            let mut end = time + jump_time;
+
+            if distance >= 10.0 {
+                end = end.max(earliest_finish);
+                finished_at = Some(end);
+            }
+
+            on_jump(Jump {
                 monster_id: index,
                 start: time,
-                end: time + jump_time,
+                end,
                 distance,
             });
 
-            time += jump_time;
+            time = end;
 
             if distance >= 10.0 {
                 break;
             }
         }
     }
+}
+
+pub fn results(monsters: &[Monster], track: Track, seed: u32) -> (RaceResults, Vec<Jump>) {
+    let mut jumps = Vec::new();
+
+    simulate(monsters, track, seed, |jump| jumps.push(jump));
 
     jumps.sort();
 
@@ -187,38 +231,30 @@ pub fn results(monsters: &[Monster; 3], seed: u32) -> (RaceResults, Vec<Jump>) {
         .filter(|item| item.distance >= 10.)
         .collect::<Vec<_>>();
 
-    finishes.sort_by(|a, b| a.end.partial_cmp(&b.end).unwrap());
+    finishes.sort_by(|a, b| a.end.total_cmp(&b.end));
 
     (
         RaceResults {
-            first: monsters[finishes[0].monster_id].uuid,
-            second: monsters[finishes[1].monster_id].uuid,
-            third: monsters[finishes[2].monster_id].uuid,
+            placings: finishes
+                .iter()
+                .map(|finish| monsters[finish.monster_id].uuid)
+                .collect(),
         },
         jumps,
     )
 }
 
-pub fn race_duration(events: &Vector<Event>) -> f32 {
-    let race_seed = race_seed(events);
-    let monsters = monsters(events, race_seed);
-
-    let (_, jumps) = results(&monsters, race_seed);
-
-    jumps.last().unwrap().end
-}
-
-// Have to use u32 instead of u64 because JS can't handle u64
-#[instrument(skip_all)]
-pub fn race_seed_for_round(events: &Vector<Event>, round: u32) -> u32 {
-    let game_id = u32::from_be_bytes(game_id(events).bytes().as_chunks::<4>().0[0]);
-    let seed = game_id.wrapping_add(round);
-
-    seed
-}
+// The placings the race is drawn with, for when a race only needs to be sampled rather than shown.
+// `simulate` holds every monster to its drawn place, so these are always the placings in `results`.
+pub fn finishing_order(monsters: &[Monster], track: Track, seed: u32) -> RaceResults {
+    let mut rng = StdRng::seed_from_u64(seed as u64);
 
-pub fn race_seed(events: &Vector<Event>) -> u32 {
-    race_seed_for_round(events, round(events))
+    RaceResults {
+        placings: draw_order(monsters, track, &mut rng)
+            .into_iter()
+            .map(|index| monsters[index].uuid)
+            .collect(),
+    }
 }
 
 #[cfg(test)]
@@ -227,14 +263,119 @@ mod test {
 
     use crate::models::{
         monsters::MONSTERS,
-        projections::race::{self, RACE_TRACK_LENGTH, Racer, RacerParams},
+        projections::race::{self, Jump, RACE_TRACK_LENGTH, Racer, RacerParams},
+        tracks::Track,
     };
 
     #[quickcheck]
     pub fn same_outcome_for_same_seed(seed: u32) -> bool {
-        let monsters = &[MONSTERS[0], MONSTERS[2], MONSTERS[3]];
+        let monsters = &[
+            MONSTERS[0].clone(),
+            MONSTERS[2].clone(),
+            MONSTERS[3].clone(),
+        ];
+
+        race::results(monsters, Track::Standard, seed)
+            == race::results(monsters, Track::Standard, seed)
+    }
+
+    #[quickcheck]
+    pub fn finishing_order_matches_results(seed: u32) -> bool {
+        let monsters = &MONSTERS[0..8];
+
+        Track::ALL.into_iter().all(|track| {
+            race::finishing_order(monsters, track, seed) == race::results(monsters, track, seed).0
+        })
+    }
+
+    #[quickcheck]
+    pub fn every_runner_is_placed(seed: u32) -> bool {
+        let monsters = &MONSTERS[0..8];
+        let (results, _) = race::results(monsters, Track::Marathon, seed);
+
+        results.placings.len() == monsters.len()
+            && monsters
+                .iter()
+                .all(|monster| results.position(monster.uuid).is_some())
+    }
+
+    // FNV-1a over every field of every jump, any difference in the maths shows up here
+    fn checksum(jumps: &[Jump]) -> u64 {
+        jumps
+            .iter()
+            .flat_map(|jump| {
+                [
+                    jump.monster_id as u32,
+                    jump.start.to_bits(),
+                    jump.end.to_bits(),
+                    jump.distance.to_bits(),
+                ]
+            })
+            .fold(0xcbf29ce484222325, |hash, word| {
+                (hash ^ word as u64).wrapping_mul(0x100000001b3)
+            })
+    }
+
+    // Recorded natively, the same seeds have to produce exactly the same races on wasm32. Only update
+    // these if the race model is meant to change, old games will replay differently.
+    const GOLDEN_RACES: [(usize, u32, &[usize], u64); 10] = [
+        (3, 0, &[2, 1, 0], 0xdaf8bcc0abae17c5),
+        (3, 1, &[2, 1, 0], 0xc4c075847c320a7c),
+        (3, 42, &[0, 1, 2], 0x8951b6a25db75214),
+        (3, 1234567, &[1, 0, 2], 0xaf428937401699a5),
+        (3, u32::MAX, &[1, 0, 2], 0xab3f2d25cb68ca67),
+        (8, 0, &[6, 5, 3, 4, 7, 0, 2, 1], 0x79d9b22fdc017f59),
+        (8, 1, &[6, 7, 4, 3, 0, 2, 1, 5], 0x94b1b17b4f136ca6),
+        (8, 42, &[1, 4, 2, 5, 7, 3, 6, 0], 0x7823accfc53bf451),
+        (8, 1234567, &[5, 2, 0, 3, 4, 1, 6, 7], 0x1eddae02f3b075d6),
+        (8, u32::MAX, &[4, 1, 5, 2, 0, 3, 7, 6], 0x674af83a73ae9d58),
+    ];
+
+    #[test]
+    fn golden_races() {
+        for (runners, seed, placings, jumps) in GOLDEN_RACES {
+            let monsters = &MONSTERS[..runners];
+            let (results, trace) = race::results(monsters, Track::Standard, seed);
+
+            assert_eq!(
+                results.placings,
+                placings
+                    .iter()
+                    .map(|index| monsters[*index].uuid)
+                    .collect::<Vec<_>>(),
+                "placings for {runners} runners with seed {seed}"
+            );
+            assert_eq!(
+                checksum(&trace),
+                jumps,
+                "jumps for {runners} runners with seed {seed}"
+            );
+        }
+    }
+
+    #[test]
+    fn tracks_favour_their_stat() {
+        // Mawshroom is all strength and Cactoro is more dexterous
+        let monsters = &[MONSTERS[2].clone(), MONSTERS[0].clone()];
+
+        let wins = |track| {
+            (0..1000)
+                .filter(|seed| {
+                    race::finishing_order(monsters, track, *seed).first() == monsters[0].uuid
+                })
+                .count()
+        };
+
+        assert!(wins(Track::LowGravity) > wins(Track::Standard));
+        assert!(wins(Track::ObstacleCourse) < wins(Track::Standard));
+
+        let duration = |track| {
+            let (_, jumps) = race::results(monsters, track, 0);
+            jumps.iter().map(|jump| jump.end).fold(0.0, f32::max)
+        };
 
-        race::results(monsters, seed) == race::results(monsters, seed)
+        assert!(duration(Track::Sprint) < duration(Track::Standard));
+        assert!(duration(Track::Marathon) > duration(Track::Standard));
     }
 
     macro_rules! assert_racer {
@@ -0,0 +1,112 @@
+use uuid::Uuid;
+
+use crate::models::events::{BetKind, PlacedBet};
+
+use super::INFLATION_FACTOR;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ToteEntry {
+    pub monster_id: Uuid,
+    pub staked: i32,
+    pub share: f32,
+    // What a winning bet of one crystal would currently return, including the stake. There is
+    // nothing to divide the pool between until somebody backs the monster.
+    pub payout: Option<f32>,
+}
+
+// The implied odds of the win pool in a `Payout::Pool` game, from the bets placed so far
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ToteBoard {
+    pub total: i32,
+    pub entries: Vec<ToteEntry>,
+}
+
+impl ToteBoard {
+    pub fn new<'a>(monsters: &[Uuid], bets: impl IntoIterator<Item = &'a PlacedBet>) -> Self {
+        let bets = bets
+            .into_iter()
+            .filter(|bet| bet.kind == BetKind::Win)
+            .collect::<Vec<_>>();
+
+        let total = bets.iter().map(|bet| bet.amount).sum::<i32>();
+
+        let entries = monsters
+            .iter()
+            .map(|monster_id| {
+                let staked = bets
+                    .iter()
+                    .filter(|bet| bet.monster_id == *monster_id)
+                    .map(|bet| bet.amount)
+                    .sum::<i32>();
+
+                let share = match total {
+                    0 => 0.0,
+                    total => staked as f32 / total as f32,
+                };
+
+                let payout = (staked > 0).then(|| {
+                    let losses = (total - staked) as f32;
+
+                    1.0 + (INFLATION_FACTOR as f32 / 100.0) * losses / staked as f32
+                });
+
+                ToteEntry {
+                    monster_id: *monster_id,
+                    staked,
+                    share,
+                    payout,
+                }
+            })
+            .collect();
+
+        ToteBoard { total, entries }
+    }
+
+    pub fn entry(&self, monster_id: Uuid) -> Option<&ToteEntry> {
+        self.entries
+            .iter()
+            .find(|entry| entry.monster_id == monster_id)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use uuid::Uuid;
+
+    use crate::models::{
+        events::{BetKind, PlacedBet},
+        projections::INFLATION_FACTOR,
+    };
+
+    use super::ToteBoard;
+
+    #[test]
+    fn returns_match_the_pool_settlement() {
+        let [a, b, c] = [Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4()];
+
+        let bet = |monster_id, amount, kind| PlacedBet {
+            session_id: Uuid::new_v4(),
+            monster_id,
+            amount,
+            kind,
+        };
+
+        let bets = [
+            bet(a, 100, BetKind::Win),
+            bet(a, 100, BetKind::Win),
+            bet(b, 200, BetKind::Win),
+            bet(b, 500, BetKind::Place),
+        ];
+
+        let board = ToteBoard::new(&[a, b, c], &bets);
+
+        assert_eq!(board.total, 400);
+        assert_eq!(board.entry(a).map(|entry| entry.share), Some(0.5));
+        assert_eq!(board.entry(c).map(|entry| entry.staked), Some(0));
+        assert_eq!(board.entry(c).and_then(|entry| entry.payout), None);
+
+        // A 100 crystal bet on `a` would settle at 100 + INFLATION_FACTOR * 100 * 200 / 200 / 100
+        let payout = board.entry(a).and_then(|entry| entry.payout).unwrap();
+        assert!((payout * 100.0 - (100 + INFLATION_FACTOR) as f32).abs() < 1e-3);
+    }
+}
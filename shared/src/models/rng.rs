@@ -0,0 +1,74 @@
+use rand::{SeedableRng, rngs::StdRng};
+
+use super::{fairness::Secret, game_code::GameCode};
+
+// Every random decision the server makes is drawn from its own stream, seeded from the game's
+// secret. The streams can't be predicted from the game code, drawing from one doesn't shift any of
+// the others, and the same secret always produces the same draws so a game can be replayed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stream {
+    Cards,
+    Race,
+    Enemies,
+    Odds,
+    Tracks,
+}
+
+impl Stream {
+    fn context(&self) -> &'static [u8] {
+        match self {
+            Stream::Cards => b"cards",
+            Stream::Race => b"race",
+            Stream::Enemies => b"enemies",
+            Stream::Odds => b"odds",
+            Stream::Tracks => b"tracks",
+        }
+    }
+}
+
+pub fn seed(secret: &Secret, game_id: GameCode, stream: Stream, index: u64) -> [u8; 32] {
+    let mut hasher = blake3::Hasher::new_keyed(secret);
+    hasher.update(stream.context());
+    hasher.update(&game_id.bytes());
+    hasher.update(&index.to_le_bytes());
+
+    hasher.finalize().into()
+}
+
+pub fn rng(secret: &Secret, game_id: GameCode, stream: Stream, index: u64) -> StdRng {
+    StdRng::from_seed(seed(secret, game_id, stream, index))
+}
+
+#[cfg(test)]
+mod test {
+    use rand::Rng;
+
+    use crate::models::{fairness::random_secret, game_code::GameCode};
+
+    use super::{Stream, rng};
+
+    #[test]
+    fn streams_are_independent_and_repeatable() {
+        let secret = random_secret();
+        let game_id = GameCode::random();
+
+        let draw = |secret, stream, index| rng(secret, game_id, stream, index).r#gen::<u64>();
+
+        assert_eq!(
+            draw(&secret, Stream::Cards, 0),
+            draw(&secret, Stream::Cards, 0)
+        );
+        assert_ne!(
+            draw(&secret, Stream::Cards, 0),
+            draw(&secret, Stream::Cards, 1)
+        );
+        assert_ne!(
+            draw(&secret, Stream::Cards, 0),
+            draw(&secret, Stream::Enemies, 0)
+        );
+        assert_ne!(
+            draw(&secret, Stream::Cards, 0),
+            draw(&random_secret(), Stream::Cards, 0)
+        );
+    }
+}
@@ -0,0 +1,491 @@
+use std::{
+    collections::{HashMap, HashSet},
+    ops::RangeInclusive,
+    path::Path,
+    sync::OnceLock,
+};
+
+use anyhow::{Context, anyhow, bail};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::monsters::{MONSTERS, Monster};
+
+// The largest field a game can be created with, every monster in it has to be different
+const MIN_ROSTER_SIZE: usize = 8;
+
+// The stats the race weights are looked up from, anything outside this is clamped
+const STAT_RANGE: RangeInclusive<i32> = 0..=10;
+
+// A monster as it's written in a roster file, anything left out is taken from `Monster::default()`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MonsterDefinition {
+    pub name: String,
+    pub blueprint_name: String,
+    pub uuid: Uuid,
+    pub dexterity: i32,
+    pub strength: i32,
+    pub starting_position: f32,
+    pub idle_animation: String,
+    pub jump_animation: String,
+    pub dance_animation: String,
+    pub death_animation: String,
+    pub jump_delay: f32,
+    pub jump_end: f32,
+    pub scale: f32,
+}
+
+impl Default for MonsterDefinition {
+    fn default() -> Self {
+        let monster = Monster::default();
+
+        MonsterDefinition {
+            name: monster.name.to_string(),
+            blueprint_name: monster.blueprint_name.to_string(),
+            uuid: monster.uuid,
+            dexterity: monster.dexterity,
+            strength: monster.strength,
+            starting_position: monster.starting_position,
+            idle_animation: monster.idle_animation.to_string(),
+            jump_animation: monster.jump_animation.to_string(),
+            dance_animation: monster.dance_animation.to_string(),
+            death_animation: monster.death_animation.to_string(),
+            jump_delay: monster.jump_delay,
+            jump_end: monster.jump_end,
+            scale: monster.scale,
+        }
+    }
+}
+
+impl From<&Monster> for MonsterDefinition {
+    fn from(monster: &Monster) -> Self {
+        MonsterDefinition {
+            name: monster.name.to_string(),
+            blueprint_name: monster.blueprint_name.to_string(),
+            uuid: monster.uuid,
+            dexterity: monster.dexterity,
+            strength: monster.strength,
+            starting_position: monster.starting_position,
+            idle_animation: monster.idle_animation.to_string(),
+            jump_animation: monster.jump_animation.to_string(),
+            dance_animation: monster.dance_animation.to_string(),
+            death_animation: monster.death_animation.to_string(),
+            jump_delay: monster.jump_delay,
+            jump_end: monster.jump_end,
+            scale: monster.scale,
+        }
+    }
+}
+
+impl From<MonsterDefinition> for Monster {
+    fn from(definition: MonsterDefinition) -> Self {
+        Monster {
+            name: definition.name.into(),
+            blueprint_name: definition.blueprint_name.into(),
+            uuid: definition.uuid,
+            dexterity: definition.dexterity,
+            strength: definition.strength,
+            starting_position: definition.starting_position,
+            idle_animation: definition.idle_animation.into(),
+            jump_animation: definition.jump_animation.into(),
+            dance_animation: definition.dance_animation.into(),
+            death_animation: definition.death_animation.into(),
+            jump_delay: definition.jump_delay,
+            jump_end: definition.jump_end,
+            scale: definition.scale,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Roster {
+    monsters: Vec<Monster>,
+}
+
+impl Default for Roster {
+    fn default() -> Self {
+        Roster {
+            monsters: MONSTERS.to_vec(),
+        }
+    }
+}
+
+impl Roster {
+    pub fn from_ron(input: &str) -> anyhow::Result<Self> {
+        let definitions = ron::from_str::<Vec<MonsterDefinition>>(input)
+            .context("failed to parse monster roster from RON")?;
+
+        Ok(Roster {
+            monsters: definitions.into_iter().map(Monster::from).collect(),
+        })
+    }
+
+    pub fn from_json(input: &str) -> anyhow::Result<Self> {
+        let definitions = serde_json::from_str::<Vec<MonsterDefinition>>(input)
+            .context("failed to parse monster roster from JSON")?;
+
+        Ok(Roster {
+            monsters: definitions.into_iter().map(Monster::from).collect(),
+        })
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        let input = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read monster roster {}", path.display()))?;
+
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some("ron") => Roster::from_ron(&input),
+            Some("json") => Roster::from_json(&input),
+            _ => bail!(
+                "unknown monster roster format {}, expected .ron or .json",
+                path.display()
+            ),
+        }
+    }
+
+    pub fn monsters(&self) -> &[Monster] {
+        &self.monsters
+    }
+
+    pub fn monster(&self, uuid: Uuid) -> Option<&Monster> {
+        self.monsters.iter().find(|monster| monster.uuid == uuid)
+    }
+
+    pub fn definitions(&self) -> Vec<MonsterDefinition> {
+        self.monsters.iter().map(MonsterDefinition::from).collect()
+    }
+
+    pub fn is_built_in(&self) -> bool {
+        *self == Roster::default()
+    }
+
+    pub fn validate(&self, assets: &AssetManifest) -> anyhow::Result<()> {
+        if self.monsters.len() < MIN_ROSTER_SIZE {
+            bail!(
+                "a monster roster needs at least {MIN_ROSTER_SIZE} monsters to fill a field, found {}",
+                self.monsters.len()
+            );
+        }
+
+        let mut uuids = HashSet::new();
+
+        for monster in &self.monsters {
+            let name = &monster.name;
+
+            if name.is_empty() {
+                bail!("monster {} has no name", monster.uuid);
+            }
+
+            if monster.uuid.is_nil() {
+                bail!("{name} has no uuid");
+            }
+
+            if !uuids.insert(monster.uuid) {
+                bail!(
+                    "{name} has the same uuid as another monster, {}",
+                    monster.uuid
+                );
+            }
+
+            for (stat, value) in [
+                ("dexterity", monster.dexterity),
+                ("strength", monster.strength),
+            ] {
+                if !STAT_RANGE.contains(&value) {
+                    bail!("{name} has {stat} {value}, expected a value in {STAT_RANGE:?}");
+                }
+            }
+
+            if !(monster.scale.is_finite() && monster.scale > 0.0) {
+                bail!(
+                    "{name} has scale {}, expected it to be positive",
+                    monster.scale
+                );
+            }
+
+            if !(monster.jump_delay >= 0.0 && monster.jump_end >= 0.0) {
+                bail!("{name} has a negative jump timing");
+            }
+
+            if !monster.starting_position.is_finite() {
+                bail!("{name} has starting position {}", monster.starting_position);
+            }
+
+            if !assets.models.contains(monster.blueprint_name.as_ref()) {
+                bail!(
+                    "{name} uses {}, which isn't one of the models in all.assets.ron",
+                    monster.blueprint_name
+                );
+            }
+
+            // Animations can only be checked for the models whose files have been read
+            if let Some(animations) = assets.animations.get(monster.blueprint_name.as_ref()) {
+                for animation in [
+                    &monster.idle_animation,
+                    &monster.jump_animation,
+                    &monster.dance_animation,
+                    &monster.death_animation,
+                ] {
+                    if !animations.contains(animation.as_ref()) {
+                        bail!(
+                            "{name} uses the animation {animation}, which isn't in {}",
+                            monster.blueprint_name
+                        );
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    // The roster new games are created with. A custom one is written to the game's event log, see
+    // `Event::RosterChosen`, as the clients have no way of loading it themselves. Fails if a roster is
+    // already in use.
+    pub fn install(self) -> anyhow::Result<()> {
+        ROSTER
+            .set(self)
+            .map_err(|_| anyhow!("a monster roster has already been installed"))
+    }
+}
+
+static ROSTER: OnceLock<Roster> = OnceLock::new();
+
+pub fn roster() -> &'static Roster {
+    ROSTER.get_or_init(Roster::default)
+}
+
+// Installs the roster named by the MONSTER_ROSTER environment variable, if there is one, after
+// checking it against the assets in `assets_dir`
+pub fn install_from_env(assets_dir: impl AsRef<Path>) -> anyhow::Result<()> {
+    let Ok(path) = std::env::var("MONSTER_ROSTER") else {
+        return Ok(());
+    };
+
+    let roster = Roster::load(&path)?;
+    roster
+        .validate(&AssetManifest::load(assets_dir)?)
+        .with_context(|| format!("invalid monster roster {path}"))?;
+
+    tracing::info!("using monster roster {path}");
+
+    roster.install()
+}
+
+#[derive(Debug, Deserialize)]
+enum AssetPaths {
+    File { path: String },
+    Files { paths: Vec<String> },
+}
+
+#[derive(Debug, Deserialize)]
+struct DynamicAssets(HashMap<String, AssetPaths>);
+
+// The models listed under "models" in all.assets.ron, and the animations in each of them
+#[derive(Debug, Clone, Default)]
+pub struct AssetManifest {
+    pub models: HashSet<String>,
+    pub animations: HashMap<String, HashSet<String>>,
+}
+
+impl AssetManifest {
+    pub fn from_ron(input: &str) -> anyhow::Result<Self> {
+        let DynamicAssets(assets) =
+            ron::from_str(input).context("failed to parse all.assets.ron")?;
+
+        let models = match assets.get("models") {
+            Some(AssetPaths::Files { paths }) => paths.iter().cloned().collect(),
+            Some(AssetPaths::File { path }) => HashSet::from([path.clone()]),
+            None => bail!("all.assets.ron has no models"),
+        };
+
+        Ok(AssetManifest {
+            models,
+            animations: HashMap::new(),
+        })
+    }
+
+    // Reads all.assets.ron from `dir`, along with the animations in every model it lists
+    pub fn load(dir: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let dir = dir.as_ref();
+        let mut manifest = AssetManifest::from_ron(
+            &std::fs::read_to_string(dir.join("all.assets.ron"))
+                .with_context(|| format!("failed to read all.assets.ron in {}", dir.display()))?,
+        )?;
+
+        for model in &manifest.models {
+            let bytes = std::fs::read(dir.join(model))
+                .with_context(|| format!("failed to read model {model}"))?;
+
+            manifest.animations.insert(
+                model.clone(),
+                gltf_animations(&bytes)
+                    .with_context(|| format!("failed to read the animations in {model}"))?,
+            );
+        }
+
+        Ok(manifest)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct Gltf {
+    #[serde(default)]
+    animations: Vec<GltfAnimation>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GltfAnimation {
+    name: Option<String>,
+}
+
+// The names of the animations in a .glb or .gltf file, only the JSON part of the file is needed
+fn gltf_animations(bytes: &[u8]) -> anyhow::Result<HashSet<String>> {
+    // A binary glTF has a 12 byte header followed by the JSON chunk's length and type
+    let json = match bytes.strip_prefix(b"glTF") {
+        Some(binary) => {
+            let chunk = binary.get(8..).unwrap_or_default();
+
+            let Some((&[a, b, c, d], rest)) = chunk.split_first_chunk::<4>() else {
+                bail!("truncated glTF header");
+            };
+
+            let Some(json) = rest.strip_prefix(b"JSON") else {
+                bail!("binary glTF doesn't start with a JSON chunk");
+            };
+
+            json.get(..u32::from_le_bytes([a, b, c, d]) as usize)
+                .context("truncated glTF JSON chunk")?
+        }
+        None => bytes,
+    };
+
+    let gltf = serde_json::from_slice::<Gltf>(json)?;
+
+    Ok(gltf
+        .animations
+        .into_iter()
+        .filter_map(|animation| animation.name)
+        .collect())
+}
+
+#[cfg(test)]
+mod test {
+    use im::vector;
+    use uuid::Uuid;
+
+    use crate::models::{events::Event, monsters::MONSTERS, projections::GameView};
+
+    use super::{AssetManifest, MonsterDefinition, Roster};
+
+    fn game_assets() -> AssetManifest {
+        AssetManifest::load(concat!(env!("CARGO_MANIFEST_DIR"), "/../game/assets")).unwrap()
+    }
+
+    #[test]
+    fn built_in_roster_matches_the_game_assets() -> anyhow::Result<()> {
+        Roster::default().validate(&game_assets())
+    }
+
+    #[test]
+    fn rosters_fill_in_defaults_and_are_validated() -> anyhow::Result<()> {
+        let assets = game_assets();
+
+        let custom = (1..=8)
+            .map(|n| {
+                format!(
+                    r#"(name: "Blob {n}", uuid: "{}", blueprint_name: "library/Monster_Alien.glb", strength: {n})"#,
+                    Uuid::from_u128(n)
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let roster = Roster::from_ron(&format!("[{}]", custom.join(",")))?;
+        roster.validate(&assets)?;
+
+        let blob = roster.monster(Uuid::from_u128(3)).unwrap();
+        assert_eq!(blob.strength, 3);
+        assert_eq!(blob.dexterity, 5);
+        assert_eq!(blob.idle_animation, MONSTERS[0].idle_animation);
+
+        let invalid = |first: String| {
+            let mut custom = custom.clone();
+            custom[0] = first;
+            Roster::from_ron(&format!("[{}]", custom.join(",")))
+                .and_then(|roster| roster.validate(&assets))
+                .is_err()
+        };
+        let with = |change: &str| custom[0].replace(")", &format!(", {change})"));
+
+        assert!(invalid(custom[0].replace(
+            &Uuid::from_u128(1).to_string(),
+            &Uuid::from_u128(2).to_string()
+        )));
+        assert!(invalid(with("dexterity: 11")));
+        assert!(invalid(with(
+            r#"jump_animation: "CharacterArmature|Backflip""#
+        )));
+        assert!(invalid(custom[0].replace("Alien", "Missing")));
+        assert!(!invalid(with("scale: 2.0")));
+
+        let json = serde_json::to_string(
+            &MONSTERS
+                .iter()
+                .map(|monster| {
+                    serde_json::json!({
+                        "name": monster.name,
+                        "uuid": monster.uuid,
+                        "blueprint_name": monster.blueprint_name,
+                    })
+                })
+                .collect::<Vec<_>>(),
+        )?;
+
+        assert_eq!(Roster::from_json(&json)?.monsters().len(), MONSTERS.len());
+
+        Ok(())
+    }
+
+    #[test]
+    fn games_race_the_roster_in_their_event_log() {
+        let custom = (1..=8)
+            .map(|n| MonsterDefinition {
+                name: format!("Blob {n}"),
+                uuid: Uuid::from_u128(n),
+                blueprint_name: "library/Monster_Alien.glb".to_string(),
+                ..Default::default()
+            })
+            .collect::<Vec<_>>();
+
+        let custom_game = GameView::from(&vector![
+            Event::new_game(),
+            Event::RosterChosen {
+                monsters: custom.clone(),
+            },
+        ]);
+        let built_in_game = GameView::from(&vector![Event::new_game()]);
+
+        for seed in 0..20 {
+            assert!(custom_game.monsters(seed).iter().all(|monster| {
+                custom
+                    .iter()
+                    .any(|definition| definition.uuid == monster.uuid)
+            }));
+            assert!(
+                built_in_game
+                    .monsters(seed)
+                    .iter()
+                    .all(|monster| MONSTERS.contains(monster))
+            );
+        }
+
+        assert!(Roster::default().is_built_in());
+        assert!(
+            !Roster::from_json(&serde_json::to_string(&custom).unwrap())
+                .unwrap()
+                .is_built_in()
+        );
+    }
+}
@@ -0,0 +1,114 @@
+use std::{fmt::Display, str::FromStr};
+
+use anyhow::bail;
+use uuid::Uuid;
+
+// The identity the server runs its own commands under, like the process managers starting the race.
+// Player ids are always derived from a token and are never nil, so no request can act as it.
+pub const SYSTEM: Uuid = Uuid::nil();
+
+// The secret a player's browser keeps in an http only cookie. Nothing else ever sees it, events and
+// projections only hold the player id derived from it, so knowing someone's player id isn't enough
+// to act as them.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct SessionToken(Uuid);
+
+impl SessionToken {
+    pub fn random() -> Self {
+        SessionToken(Uuid::new_v4())
+    }
+
+    pub fn player_id(&self) -> Uuid {
+        player_id(self.0)
+    }
+}
+
+// One way, so a player id can't be turned back into the token it came from
+pub fn player_id(token: Uuid) -> Uuid {
+    derive("deep-space-derby 2024 player id", token)
+}
+
+// Games from before player ids were derived used the token itself, and it was sent to everyone in
+// the game. `upcast_v7` moves them over with this instead of `player_id`, so replaying one of those
+// tokens as a cookie can't be used to act as its player.
+pub fn legacy_player_id(token: Uuid) -> Uuid {
+    derive("deep-space-derby 2024 legacy player id", token)
+}
+
+fn derive(context: &str, token: Uuid) -> Uuid {
+    let digest = blake3::derive_key(context, token.as_bytes());
+
+    let mut bytes = [0; 16];
+    bytes.copy_from_slice(&digest[..16]);
+
+    uuid::Builder::from_random_bytes(bytes).into_uuid()
+}
+
+impl Display for SessionToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl FromStr for SessionToken {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let token = Uuid::parse_str(s)?;
+
+        if token.is_nil() {
+            bail!("session tokens can't be nil");
+        }
+
+        Ok(SessionToken(token))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use uuid::Uuid;
+
+    use super::{SYSTEM, SessionToken, legacy_player_id, player_id};
+
+    #[test]
+    fn player_ids_are_derived_from_tokens() -> anyhow::Result<()> {
+        let token = SessionToken::random();
+
+        assert_eq!(token.player_id(), token.player_id());
+        assert_ne!(token.player_id(), SessionToken::random().player_id());
+        assert_ne!(token.player_id().to_string(), token.to_string());
+
+        assert_eq!(token.to_string().parse::<SessionToken>()?, token);
+        assert_ne!(player_id(Uuid::nil()), SYSTEM);
+
+        Ok(())
+    }
+
+    #[test]
+    fn nil_and_malformed_tokens_are_rejected() {
+        for token in [
+            Uuid::nil().to_string(),
+            "".to_string(),
+            "not a token".to_string(),
+            format!("{}.{}", Uuid::new_v4(), blake3::hash(b"forged").to_hex()),
+        ] {
+            assert!(
+                token.parse::<SessionToken>().is_err(),
+                "{token:?} was accepted"
+            );
+        }
+    }
+
+    #[test]
+    fn replayed_legacy_tokens_are_refused() -> anyhow::Result<()> {
+        // Legacy tokens were sent to everyone in the game, so they can't act as the player they
+        // belonged to
+        let legacy = Uuid::new_v4();
+        let replayed = legacy.to_string().parse::<SessionToken>()?;
+
+        assert_ne!(replayed.player_id(), legacy_player_id(legacy));
+        assert_ne!(legacy_player_id(legacy), legacy);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,11 @@
+use serde::{Deserialize, Serialize};
+
+// The process managers that wake the game up at a particular time. Each has at most one pending
+// timer, scheduling it again moves it.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum Timer {
+    StartRace,
+    FinishRace,
+    StartRound,
+    DropIdlePlayers,
+}
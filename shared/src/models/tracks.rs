@@ -0,0 +1,68 @@
+use serde::{Deserialize, Serialize};
+
+// The course a round's race is run on. Every track is drawn on the same stage, a longer track just
+// takes longer to cross it.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Track {
+    #[default]
+    Standard,
+    Sprint,
+    Marathon,
+    LowGravity,
+    ObstacleCourse,
+}
+
+impl Track {
+    pub const ALL: [Track; 5] = [
+        Track::Standard,
+        Track::Sprint,
+        Track::Marathon,
+        Track::LowGravity,
+        Track::ObstacleCourse,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Track::Standard => "Standard Track",
+            Track::Sprint => "Sprint",
+            Track::Marathon => "Marathon",
+            Track::LowGravity => "Low Gravity",
+            Track::ObstacleCourse => "Obstacle Course",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            Track::Standard => "No surprises, may the best monster win",
+            Track::Sprint => "A short dash to the line",
+            Track::Marathon => "A long slog, it's anyone's race",
+            Track::LowGravity => "Big jumps go even further, strength is king",
+            Track::ObstacleCourse => "Quick feet are needed to get around, dexterity is king",
+        }
+    }
+
+    pub fn length(&self) -> f32 {
+        match self {
+            Track::Sprint => 7.0,
+            Track::Marathon => 14.0,
+            _ => 10.0,
+        }
+    }
+
+    // How much each stat counts towards a monster's race weight
+    pub fn strength_factor(&self) -> f32 {
+        match self {
+            Track::LowGravity => 1.5,
+            Track::ObstacleCourse => 0.5,
+            _ => 1.0,
+        }
+    }
+
+    pub fn dexterity_factor(&self) -> f32 {
+        match self {
+            Track::LowGravity => 0.5,
+            Track::ObstacleCourse => 1.5,
+            _ => 1.0,
+        }
+    }
+}
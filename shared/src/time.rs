@@ -3,3 +3,66 @@ pub use std::time::*;
 
 #[cfg(target_arch = "wasm32")]
 pub use web_time::*;
+
+use std::sync::Mutex;
+
+// Where the game logic gets the current time from, so that deadlines can be tested without waiting
+// on the wall clock
+pub trait Clock: std::fmt::Debug + Send + Sync {
+    fn now(&self) -> SystemTime;
+
+    // The current time in seconds since the epoch, as stored in events
+    fn timestamp(&self) -> u32 {
+        self.now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs() as u32
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+}
+
+// Always reports the same time
+#[derive(Debug, Clone, Copy)]
+pub struct FixedClock(pub SystemTime);
+
+impl FixedClock {
+    pub fn at(timestamp: u32) -> Self {
+        Self(UNIX_EPOCH + Duration::from_secs(timestamp as u64))
+    }
+}
+
+impl Clock for FixedClock {
+    fn now(&self) -> SystemTime {
+        self.0
+    }
+}
+
+// Stands still until it's moved on by hand
+#[derive(Debug)]
+pub struct ManualClock(Mutex<SystemTime>);
+
+impl ManualClock {
+    pub fn at(timestamp: u32) -> Self {
+        Self(Mutex::new(
+            UNIX_EPOCH + Duration::from_secs(timestamp as u64),
+        ))
+    }
+
+    pub fn advance(&self, duration: Duration) {
+        *self.0.lock().unwrap() += duration;
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> SystemTime {
+        *self.0.lock().unwrap()
+    }
+}